@@ -0,0 +1,2 @@
+ALTER TABLE sessions
+ADD COLUMN session_type VARCHAR(9) NOT NULL DEFAULT 'Focus';
@@ -0,0 +1,2 @@
+ALTER TABLE sessions
+ADD COLUMN planned_duration INTEGER;
@@ -0,0 +1,2 @@
+ALTER TABLE sessions
+ADD COLUMN is_manual BOOLEAN NOT NULL DEFAULT 0;
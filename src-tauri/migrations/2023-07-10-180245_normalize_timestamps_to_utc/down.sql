@@ -0,0 +1,13 @@
+UPDATE sessions
+SET
+  finished_at = CAST(strftime ('%s', finished_at, 'unixepoch', 'localtime') AS INTEGER)
+WHERE
+  is_manual = 0;
+
+UPDATE intents
+SET
+  created_at = CAST(strftime ('%s', created_at, 'unixepoch', 'localtime') AS INTEGER);
+
+UPDATE tasks
+SET
+  created_at = CAST(strftime ('%s', created_at, 'unixepoch', 'localtime') AS INTEGER);
@@ -0,0 +1,23 @@
+-- Columns defaulting to `strftime('%s', 'now', 'localtime')` were stored shifted by
+-- the local UTC offset. The `utc` modifier shifts every row back by the offset in effect
+-- at its own time, so rows from either side of a DST change are converted correctly.
+UPDATE sessions
+SET
+  finished_at = CAST(strftime ('%s', finished_at, 'unixepoch', 'utc') AS INTEGER)
+WHERE
+  is_manual = 0;
+
+UPDATE intents
+SET
+  created_at = CAST(strftime ('%s', created_at, 'unixepoch', 'utc') AS INTEGER);
+
+UPDATE tasks
+SET
+  created_at = CAST(strftime ('%s', created_at, 'unixepoch', 'utc') AS INTEGER);
+
+-- Completed tasks stored `finished_at` as a UTC datetime string.
+UPDATE tasks
+SET
+  finished_at = CAST(strftime ('%s', finished_at) AS INTEGER)
+WHERE
+  typeof (finished_at) = 'text';
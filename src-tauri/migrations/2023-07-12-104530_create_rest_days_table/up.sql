@@ -0,0 +1,5 @@
+CREATE TABLE
+  IF NOT EXISTS rest_days (
+    id INTEGER NOT NULL PRIMARY KEY,
+    day DATE NOT NULL UNIQUE
+  );
@@ -0,0 +1,13 @@
+DROP TRIGGER search_index_intents_insert;
+DROP TRIGGER search_index_intents_update;
+DROP TRIGGER search_index_intents_delete;
+DROP TRIGGER search_index_tasks_insert;
+DROP TRIGGER search_index_tasks_update;
+DROP TRIGGER search_index_tasks_delete;
+DROP TRIGGER search_index_tags_insert;
+DROP TRIGGER search_index_tags_update;
+DROP TRIGGER search_index_tags_delete;
+DROP TRIGGER search_index_sessions_insert;
+DROP TRIGGER search_index_sessions_update;
+DROP TRIGGER search_index_sessions_delete;
+DROP TABLE search_index;
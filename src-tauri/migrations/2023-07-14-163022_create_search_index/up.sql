@@ -0,0 +1,76 @@
+-- Full-text index over the searchable text of intents, tasks, tags and sessions.
+-- `kind` and `item_id` point back to the indexed row, only `body` is searchable.
+-- The rowid is derived from both, `kind_code * 2^32 + item_id` with intents, tasks, tags
+-- and sessions coded 1 to 4, so the triggers find rows by rowid instead of scanning.
+CREATE VIRTUAL TABLE search_index USING fts5 (
+  kind UNINDEXED,
+  item_id UNINDEXED,
+  body,
+  tokenize = 'unicode61 remove_diacritics 2'
+);
+
+INSERT INTO search_index (rowid, kind, item_id, body)
+SELECT 1 * 4294967296 + id, 'intent', id, label FROM intents;
+INSERT INTO search_index (rowid, kind, item_id, body)
+SELECT 2 * 4294967296 + id, 'task', id, body FROM tasks;
+INSERT INTO search_index (rowid, kind, item_id, body)
+SELECT 3 * 4294967296 + id, 'tag', id, label FROM tags;
+INSERT INTO search_index (rowid, kind, item_id, body)
+SELECT 4 * 4294967296 + id, 'session', id, summary FROM sessions WHERE summary IS NOT NULL AND summary != '';
+
+-- Intents
+CREATE TRIGGER search_index_intents_insert AFTER INSERT ON intents BEGIN
+  INSERT INTO search_index (rowid, kind, item_id, body)
+  VALUES (1 * 4294967296 + new.id, 'intent', new.id, new.label);
+END;
+CREATE TRIGGER search_index_intents_update AFTER UPDATE OF label ON intents BEGIN
+  DELETE FROM search_index WHERE rowid = 1 * 4294967296 + old.id;
+  INSERT INTO search_index (rowid, kind, item_id, body)
+  VALUES (1 * 4294967296 + new.id, 'intent', new.id, new.label);
+END;
+CREATE TRIGGER search_index_intents_delete AFTER DELETE ON intents BEGIN
+  DELETE FROM search_index WHERE rowid = 1 * 4294967296 + old.id;
+END;
+
+-- Tasks
+CREATE TRIGGER search_index_tasks_insert AFTER INSERT ON tasks BEGIN
+  INSERT INTO search_index (rowid, kind, item_id, body)
+  VALUES (2 * 4294967296 + new.id, 'task', new.id, new.body);
+END;
+CREATE TRIGGER search_index_tasks_update AFTER UPDATE OF body ON tasks BEGIN
+  DELETE FROM search_index WHERE rowid = 2 * 4294967296 + old.id;
+  INSERT INTO search_index (rowid, kind, item_id, body)
+  VALUES (2 * 4294967296 + new.id, 'task', new.id, new.body);
+END;
+CREATE TRIGGER search_index_tasks_delete AFTER DELETE ON tasks BEGIN
+  DELETE FROM search_index WHERE rowid = 2 * 4294967296 + old.id;
+END;
+
+-- Tags
+CREATE TRIGGER search_index_tags_insert AFTER INSERT ON tags BEGIN
+  INSERT INTO search_index (rowid, kind, item_id, body)
+  VALUES (3 * 4294967296 + new.id, 'tag', new.id, new.label);
+END;
+CREATE TRIGGER search_index_tags_update AFTER UPDATE OF label ON tags BEGIN
+  DELETE FROM search_index WHERE rowid = 3 * 4294967296 + old.id;
+  INSERT INTO search_index (rowid, kind, item_id, body)
+  VALUES (3 * 4294967296 + new.id, 'tag', new.id, new.label);
+END;
+CREATE TRIGGER search_index_tags_delete AFTER DELETE ON tags BEGIN
+  DELETE FROM search_index WHERE rowid = 3 * 4294967296 + old.id;
+END;
+
+-- Sessions, only the ones with a summary are indexed
+CREATE TRIGGER search_index_sessions_insert AFTER INSERT ON sessions
+WHEN new.summary IS NOT NULL AND new.summary != '' BEGIN
+  INSERT INTO search_index (rowid, kind, item_id, body)
+  VALUES (4 * 4294967296 + new.id, 'session', new.id, new.summary);
+END;
+CREATE TRIGGER search_index_sessions_update AFTER UPDATE OF summary ON sessions BEGIN
+  DELETE FROM search_index WHERE rowid = 4 * 4294967296 + old.id;
+  INSERT INTO search_index (rowid, kind, item_id, body)
+  SELECT 4 * 4294967296 + new.id, 'session', new.id, new.summary WHERE new.summary IS NOT NULL AND new.summary != '';
+END;
+CREATE TRIGGER search_index_sessions_delete AFTER DELETE ON sessions BEGIN
+  DELETE FROM search_index WHERE rowid = 4 * 4294967296 + old.id;
+END;
@@ -0,0 +1,3 @@
+DROP TRIGGER intents_detach_children;
+DROP INDEX intents_parent_id;
+ALTER TABLE intents DROP COLUMN parent_id;
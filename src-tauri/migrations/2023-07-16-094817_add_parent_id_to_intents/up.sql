@@ -0,0 +1,9 @@
+-- No foreign key so the column can be dropped again, the app checks that parents exist
+-- and the trigger below detaches children of deleted intents.
+ALTER TABLE intents ADD COLUMN parent_id INTEGER;
+
+CREATE INDEX intents_parent_id ON intents (parent_id);
+
+CREATE TRIGGER intents_detach_children AFTER DELETE ON intents BEGIN
+  UPDATE intents SET parent_id = NULL WHERE parent_id = old.id;
+END;
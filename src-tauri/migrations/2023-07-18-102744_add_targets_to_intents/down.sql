@@ -0,0 +1,3 @@
+ALTER TABLE intents DROP COLUMN budget;
+ALTER TABLE intents DROP COLUMN target;
+ALTER TABLE intents DROP COLUMN target_period;
@@ -0,0 +1,4 @@
+-- Target and budget are in minutes, counted per ISO week or over the intent's lifetime
+ALTER TABLE intents ADD COLUMN target_period VARCHAR(6) NOT NULL DEFAULT 'Weekly';
+ALTER TABLE intents ADD COLUMN target BIGINT;
+ALTER TABLE intents ADD COLUMN budget BIGINT;
@@ -0,0 +1,4 @@
+ALTER TABLE intents DROP COLUMN position;
+ALTER TABLE intents DROP COLUMN description;
+ALTER TABLE intents DROP COLUMN icon;
+ALTER TABLE intents DROP COLUMN color;
@@ -0,0 +1,10 @@
+ALTER TABLE intents ADD COLUMN color VARCHAR(7);
+ALTER TABLE intents ADD COLUMN icon TEXT;
+ALTER TABLE intents ADD COLUMN description TEXT;
+-- Position among the intents sharing the same parent
+ALTER TABLE intents ADD COLUMN position INTEGER NOT NULL DEFAULT 0;
+
+UPDATE intents SET position = (
+  SELECT COUNT(*) FROM intents siblings
+  WHERE siblings.parent_id IS intents.parent_id AND siblings.id < intents.id
+);
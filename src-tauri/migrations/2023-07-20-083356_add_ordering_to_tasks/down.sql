@@ -0,0 +1,4 @@
+DROP INDEX tasks_due_date;
+ALTER TABLE tasks DROP COLUMN due_date;
+ALTER TABLE tasks DROP COLUMN priority;
+ALTER TABLE tasks DROP COLUMN position;
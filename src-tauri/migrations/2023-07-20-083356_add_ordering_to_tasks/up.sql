@@ -0,0 +1,12 @@
+-- Position among the tasks of the same intent
+ALTER TABLE tasks ADD COLUMN position INTEGER NOT NULL DEFAULT 0;
+-- 0 low, 1 normal, 2 high
+ALTER TABLE tasks ADD COLUMN priority INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE tasks ADD COLUMN due_date DATE;
+
+UPDATE tasks SET position = (
+  SELECT COUNT(*) FROM tasks siblings
+  WHERE siblings.intent_id = tasks.intent_id AND siblings.id < tasks.id
+);
+
+CREATE INDEX tasks_due_date ON tasks (due_date);
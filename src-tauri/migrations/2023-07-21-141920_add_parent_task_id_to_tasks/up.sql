@@ -0,0 +1,4 @@
+-- No foreign key, like `intents.parent_id`, the app deletes subtasks along with their parent.
+ALTER TABLE tasks ADD COLUMN parent_task_id INTEGER;
+
+CREATE INDEX tasks_parent_task_id ON tasks (parent_task_id);
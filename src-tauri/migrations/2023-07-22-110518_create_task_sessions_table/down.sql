@@ -0,0 +1,2 @@
+DROP TABLE task_sessions;
+ALTER TABLE tasks DROP COLUMN estimated_pomodoros;
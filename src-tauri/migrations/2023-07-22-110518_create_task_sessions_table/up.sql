@@ -0,0 +1,12 @@
+ALTER TABLE tasks ADD COLUMN estimated_pomodoros INTEGER;
+
+CREATE TABLE
+  IF NOT EXISTS task_sessions (
+    id INTEGER NOT NULL PRIMARY KEY,
+    task_id INTEGER NOT NULL,
+    session_id INTEGER NOT NULL,
+    FOREIGN KEY (task_id) REFERENCES tasks (id) ON DELETE CASCADE,
+    FOREIGN KEY (session_id) REFERENCES sessions (id) ON DELETE CASCADE
+  );
+
+CREATE UNIQUE INDEX task_sessions_task_id_session_id ON task_sessions (task_id, session_id);
@@ -0,0 +1,5 @@
+ALTER TABLE tasks ADD COLUMN recurrence TEXT;
+-- First task of a recurring series, shared by every instance generated from it.
+ALTER TABLE tasks ADD COLUMN series_id INTEGER;
+
+CREATE INDEX tasks_series_id ON tasks (series_id);
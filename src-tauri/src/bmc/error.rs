@@ -0,0 +1,26 @@
+use ts_rs::TS;
+
+#[derive(thiserror::Error, TS, Debug)]
+#[ts(export, export_to = "../src/bindings/")]
+pub enum BmcError {
+    #[error("InvalidTimeRange")]
+    InvalidTimeRange,
+    #[error("OverlappingSession")]
+    OverlappingSession,
+    #[error("InvalidDuration")]
+    InvalidDuration,
+    #[error("IntentCycle")]
+    IntentCycle,
+    #[error("InvalidColor")]
+    InvalidColor,
+    #[error("InvalidParentTask")]
+    InvalidParentTask,
+    #[error("InvalidEstimate")]
+    InvalidEstimate,
+    #[error("InvalidRecurrence")]
+    InvalidRecurrence,
+    #[error("MissingToday")]
+    MissingToday,
+    #[error("UnresolvedDay")]
+    UnresolvedDay,
+}
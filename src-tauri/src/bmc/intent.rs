@@ -1,13 +1,21 @@
+use std::collections::HashMap;
+
 use diesel::prelude::*;
 use diesel::SqliteConnection;
 
+use crate::day::DayBoundary;
 use crate::models::CreateIntent;
 use crate::models::Intent;
+use crate::models::IntentNode;
+use crate::models::IntentTargetPeriod;
+use crate::models::IntentTargetProgress;
+use crate::models::SetIntentDetails;
+use crate::models::SetIntentTarget;
 use crate::models::Tag;
 use crate::models::UpdateIntent;
-use crate::prelude::Result;
+use crate::prelude::{Error, Result};
 
-use super::BaseBmc;
+use super::{BaseBmc, BmcError, GetStatsOptions, StatsBmc, StatsGroupBy};
 
 pub struct IntentBmc {}
 
@@ -15,8 +23,18 @@ impl IntentBmc {
     pub fn create(conn: &mut SqliteConnection, data: &CreateIntent) -> Result<i32> {
         use crate::db::schema::intents;
 
+        if let Some(parent_id) = data.parent_id {
+            // Makes sure the parent exists, there's no foreign key on `parent_id`.
+            Self::get(conn, parent_id)?;
+        }
+        let created_at = chrono::Utc::now().timestamp();
+        let position = Self::next_position(conn, data.parent_id)?;
         diesel::insert_into(intents::table)
-            .values(data)
+            .values((
+                data,
+                intents::created_at.eq(created_at),
+                intents::position.eq(position),
+            ))
             .execute(conn)?;
 
         BaseBmc::get_last_insert_id(conn)
@@ -43,13 +61,158 @@ impl IntentBmc {
         Ok(intent)
     }
 
-    pub fn get_list(conn: &mut SqliteConnection) -> Result<Vec<Intent>> {
+    /// Moves an intent under another one, `None` makes it a top level intent.
+    /// Rejects parents which are the intent itself or one of its descendants.
+    pub fn set_parent(conn: &mut SqliteConnection, id: i32, parent_id: Option<i32>) -> Result<i32> {
+        use crate::db::schema::intents::dsl;
+
+        conn.transaction::<_, Error, _>(|conn| {
+            let mut ancestor_id = parent_id;
+            while let Some(current_id) = ancestor_id {
+                if current_id == id {
+                    return Err(BmcError::IntentCycle.into());
+                }
+                ancestor_id = Self::get(conn, current_id)?.parent_id;
+            }
+
+            if Self::get(conn, id)?.parent_id == parent_id {
+                return Ok(id);
+            }
+            // Goes after the intents already nested under the new parent.
+            let position = Self::next_position(conn, parent_id)?;
+            diesel::update(dsl::intents.find(id))
+                .set((dsl::parent_id.eq(parent_id), dsl::position.eq(position)))
+                .execute(conn)?;
+            Ok(id)
+        })
+    }
+
+    /// Moves an intent to the given position among the intents sharing its parent,
+    /// positions past the end move it last.
+    pub fn move_to(conn: &mut SqliteConnection, id: i32, position: i32) -> Result<i32> {
+        use crate::db::schema::intents::dsl;
+
+        conn.transaction::<_, Error, _>(|conn| {
+            let parent_id = Self::get(conn, id)?.parent_id;
+            let mut siblings: Vec<i32> = dsl::intents
+                .filter(dsl::parent_id.is(parent_id))
+                .filter(dsl::id.ne(id))
+                .order((dsl::position.asc(), dsl::id.asc()))
+                .select(dsl::id)
+                .load(conn)?;
+            let index = (position.max(0) as usize).min(siblings.len());
+            siblings.insert(index, id);
+
+            for (position, sibling_id) in siblings.into_iter().enumerate() {
+                diesel::update(dsl::intents.find(sibling_id))
+                    .set(dsl::position.eq(position as i32))
+                    .execute(conn)?;
+            }
+            Ok(id)
+        })
+    }
+
+    fn next_position(conn: &mut SqliteConnection, parent_id: Option<i32>) -> Result<i32> {
+        use crate::db::schema::intents::dsl;
+
+        let last: Option<i32> = dsl::intents
+            .filter(dsl::parent_id.is(parent_id))
+            .select(diesel::dsl::max(dsl::position))
+            .first(conn)?;
+        Ok(last.map_or(0, |position| position + 1))
+    }
+
+    /// Only accepts colors in the `#rrggbb` form.
+    pub fn set_details(
+        conn: &mut SqliteConnection,
+        id: i32,
+        data: &SetIntentDetails,
+    ) -> Result<i32> {
+        use crate::db::schema::intents::dsl::intents;
+
+        if let Some(color) = &data.color {
+            let is_hex = color.len() == 7
+                && color.starts_with('#')
+                && color[1..].chars().all(|c| c.is_ascii_hexdigit());
+            if !is_hex {
+                return Err(BmcError::InvalidColor.into());
+            }
+        }
+        diesel::update(intents.find(id)).set(data).execute(conn)?;
+        Ok(id)
+    }
+
+    /// Ids of the intents the given one is nested under, closest parent first.
+    pub fn get_ancestor_ids(conn: &mut SqliteConnection, id: i32) -> Result<Vec<i32>> {
+        let mut ancestors = vec![];
+        let mut parent_id = Self::get(conn, id)?.parent_id;
+        while let Some(current_id) = parent_id {
+            // Parents may have been deleted before the trigger detached their children.
+            let Ok(parent) = Self::get(conn, current_id) else {
+                break;
+            };
+            ancestors.push(current_id);
+            parent_id = parent.parent_id;
+        }
+        Ok(ancestors)
+    }
+
+    /// Top level intents with their sub-intents nested as children, each level sorted
+    /// pinned first, then by position.
+    pub fn get_list(conn: &mut SqliteConnection) -> Result<Vec<IntentNode>> {
+        let intents = Self::get_flat_list(conn)?;
+
+        let mut children: HashMap<Option<i32>, Vec<Intent>> = HashMap::new();
+        let ids: Vec<i32> = intents.iter().map(|intent| intent.id).collect();
+        for intent in intents {
+            // Intents whose parent is gone are shown at the top level.
+            let parent_id = intent.parent_id.filter(|id| ids.contains(id));
+            children.entry(parent_id).or_default().push(intent);
+        }
+        Ok(Self::build_nodes(&mut children, None))
+    }
+
+    /// All intents without nesting, pinned ones first, then by position.
+    pub fn get_flat_list(conn: &mut SqliteConnection) -> Result<Vec<Intent>> {
         use crate::db::schema::intents::dsl;
 
-        let intents: Vec<Intent> = dsl::intents.load(conn)?;
+        let intents: Vec<Intent> = dsl::intents
+            .order((dsl::pinned.desc(), dsl::position.asc(), dsl::id.asc()))
+            .load(conn)?;
         Ok(intents)
     }
 
+    /// Ids of all intents nested under the given one, at any depth.
+    pub fn get_descendant_ids(conn: &mut SqliteConnection, id: i32) -> Result<Vec<i32>> {
+        let intents = Self::get_flat_list(conn)?;
+
+        let mut descendants = vec![];
+        let mut parents = vec![id];
+        while let Some(parent_id) = parents.pop() {
+            for intent in &intents {
+                if intent.parent_id == Some(parent_id) {
+                    descendants.push(intent.id);
+                    parents.push(intent.id);
+                }
+            }
+        }
+        Ok(descendants)
+    }
+
+    fn build_nodes(
+        children: &mut HashMap<Option<i32>, Vec<Intent>>,
+        parent_id: Option<i32>,
+    ) -> Vec<IntentNode> {
+        let intents = children.remove(&parent_id).unwrap_or_default();
+        intents
+            .into_iter()
+            .map(|intent| {
+                let children = Self::build_nodes(children, Some(intent.id));
+                IntentNode { intent, children }
+            })
+            .collect()
+    }
+
     pub fn archive(conn: &mut SqliteConnection, id: i32) -> Result<i32> {
         use crate::db::schema::intents::dsl::{archived_at, intents};
 
@@ -60,6 +223,28 @@ impl IntentBmc {
         Ok(id)
     }
 
+    /// Archives an intent along with all of its sub-intents, returns ids of the intents
+    /// that got archived. Sub-intents which were archived before are left untouched.
+    pub fn archive_with_descendants(conn: &mut SqliteConnection, id: i32) -> Result<Vec<i32>> {
+        use crate::db::schema::intents::dsl;
+
+        conn.transaction::<_, Error, _>(|conn| {
+            let mut ids = vec![id];
+            ids.extend(Self::get_descendant_ids(conn, id)?);
+
+            let ids: Vec<i32> = dsl::intents
+                .filter(dsl::id.eq_any(ids))
+                .filter(dsl::archived_at.is_null())
+                .select(dsl::id)
+                .load(conn)?;
+            let now = chrono::Utc::now().timestamp();
+            diesel::update(dsl::intents.filter(dsl::id.eq_any(&ids)))
+                .set(dsl::archived_at.eq(now))
+                .execute(conn)?;
+            Ok(ids)
+        })
+    }
+
     pub fn unarchive(conn: &mut SqliteConnection, id: i32) -> Result<i32> {
         use crate::db::schema::intents::dsl::{archived_at, intents};
 
@@ -69,6 +254,102 @@ impl IntentBmc {
         Ok(id)
     }
 
+    pub fn set_target(conn: &mut SqliteConnection, id: i32, data: &SetIntentTarget) -> Result<i32> {
+        use crate::db::schema::intents::dsl::intents;
+
+        if [data.target, data.budget]
+            .iter()
+            .flatten()
+            .any(|minutes| *minutes <= 0)
+        {
+            return Err(BmcError::InvalidDuration.into());
+        }
+        diesel::update(intents.find(id)).set(data).execute(conn)?;
+        Ok(id)
+    }
+
+    /// Progress of every intent which has a target or a budget set. Weekly targets
+    /// count the current ISO week, days are resolved using the given day boundary.
+    pub fn get_target_progress(
+        conn: &mut SqliteConnection,
+        boundary: &DayBoundary,
+    ) -> Result<Vec<IntentTargetProgress>> {
+        use crate::db::schema::intents::dsl;
+
+        let intents: Vec<Intent> = dsl::intents
+            .filter(dsl::target.is_not_null().or(dsl::budget.is_not_null()))
+            .load(conn)?;
+        if intents.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let (monday, sunday) = DayBoundary::week_of(boundary.today());
+        let mut focused_by_intent = |from, to| {
+            let options = GetStatsOptions {
+                group_by: StatsGroupBy::Intent,
+                from,
+                to,
+                include_manual: None,
+            };
+            StatsBmc::get(conn, &options, boundary).map(|stats| {
+                stats
+                    .entries
+                    .into_iter()
+                    .filter_map(|entry| Some((entry.id?, entry.total_duration)))
+                    .collect::<HashMap<i32, i64>>()
+            })
+        };
+        let weekly = focused_by_intent(Some(monday), Some(sunday))?;
+        let total = focused_by_intent(None, None)?;
+
+        Ok(intents
+            .into_iter()
+            .map(|intent| {
+                let focused = match intent.target_period {
+                    IntentTargetPeriod::Weekly => &weekly,
+                    IntentTargetPeriod::Total => &total,
+                };
+                IntentTargetProgress {
+                    intent_id: intent.id,
+                    target_period: intent.target_period,
+                    focused: focused.get(&intent.id).copied().unwrap_or(0),
+                    target: intent.target,
+                    budget: intent.budget,
+                }
+            })
+            .collect())
+    }
+
+    /// Target progress of the intent and its parents after a session was logged, each paired
+    /// with the progress as it was before. A session only counts towards the current period
+    /// of a weekly target if it started within the current week.
+    pub fn get_session_target_progress(
+        conn: &mut SqliteConnection,
+        boundary: &DayBoundary,
+        intent_id: i32,
+        started_at: i64,
+        duration: i64,
+    ) -> Result<Vec<(IntentTargetProgress, IntentTargetProgress)>> {
+        let mut ids = Self::get_ancestor_ids(conn, intent_id)?;
+        ids.push(intent_id);
+        let (monday, sunday) = DayBoundary::week_of(boundary.today());
+        let day = boundary.day_of(started_at);
+        let in_current_week = monday <= day && day <= sunday;
+
+        Ok(Self::get_target_progress(conn, boundary)?
+            .into_iter()
+            .filter(|progress| ids.contains(&progress.intent_id))
+            .map(|progress| {
+                let counted = match progress.target_period {
+                    IntentTargetPeriod::Weekly => in_current_week,
+                    IntentTargetPeriod::Total => true,
+                };
+                let before = progress.before(if counted { duration } else { 0 });
+                (before, progress)
+            })
+            .collect())
+    }
+
     pub fn get_tags(conn: &mut SqliteConnection, intent_id: i32) -> Result<Vec<Tag>> {
         use crate::db::schema::intent_tags::dsl as intent_tags_dsl;
         use crate::db::schema::tags::dsl as tags_dsl;
@@ -85,10 +366,12 @@ impl IntentBmc {
 
 #[cfg(test)]
 mod intent_bmc_tests {
+    use diesel::result::Error::NotFound;
+
     use crate::{
-        bmc::{IntentTagBmc, TagBmc},
+        bmc::{IntentTagBmc, SessionBmc, TagBmc},
         db::Db,
-        models::{CreateIntentTag, CreateTag},
+        models::{CreateIntentTag, CreateManualSession, CreateTag},
         prelude::Error,
     };
 
@@ -100,6 +383,7 @@ mod intent_bmc_tests {
 
         let data = CreateIntent {
             label: "foo".to_string(),
+            parent_id: None,
         };
         let id = IntentBmc::create(&mut conn, &data).unwrap();
 
@@ -112,6 +396,7 @@ mod intent_bmc_tests {
 
         let data = CreateIntent {
             label: "foo".to_string(),
+            parent_id: None,
         };
         let id = IntentBmc::create(&mut conn, &data).unwrap();
         let data = UpdateIntent {
@@ -131,6 +416,7 @@ mod intent_bmc_tests {
 
         let data = CreateIntent {
             label: "foo".to_string(),
+            parent_id: None,
         };
         let id = IntentBmc::create(&mut conn, &data).unwrap();
         let intent = IntentBmc::get(&mut conn, id).unwrap();
@@ -147,6 +433,7 @@ mod intent_bmc_tests {
 
         let data = CreateIntent {
             label: "foo".to_string(),
+            parent_id: None,
         };
         for _ in 0..10 {
             IntentBmc::create(&mut conn, &data).unwrap();
@@ -162,6 +449,7 @@ mod intent_bmc_tests {
 
         let data = CreateIntent {
             label: "foo".to_string(),
+            parent_id: None,
         };
         let id = IntentBmc::create(&mut conn, &data).unwrap();
 
@@ -177,6 +465,7 @@ mod intent_bmc_tests {
 
         let data = CreateIntent {
             label: "foo".to_string(),
+            parent_id: None,
         };
         let id = IntentBmc::create(&mut conn, &data).unwrap();
 
@@ -192,6 +481,7 @@ mod intent_bmc_tests {
 
         let data = CreateIntent {
             label: "foo".to_string(),
+            parent_id: None,
         };
         let id = IntentBmc::create(&mut conn, &data).unwrap();
 
@@ -213,6 +503,7 @@ mod intent_bmc_tests {
         // Create an intent
         let intent_data = CreateIntent {
             label: "test intent".to_string(),
+            parent_id: None,
         };
         let intent_id = IntentBmc::create(&mut conn, &intent_data).unwrap();
 
@@ -264,4 +555,293 @@ mod intent_bmc_tests {
         ];
         assert_eq!(tags, expected_tags);
     }
+
+    fn create_child(conn: &mut SqliteConnection, label: &str, parent_id: Option<i32>) -> i32 {
+        let data = CreateIntent {
+            label: label.to_string(),
+            parent_id,
+        };
+        IntentBmc::create(conn, &data).unwrap()
+    }
+
+    #[test]
+    fn test_get_list_as_tree() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let client = create_child(&mut conn, "client", None);
+        let project = create_child(&mut conn, "project", Some(client));
+        create_child(&mut conn, "workstream", Some(project));
+        create_child(&mut conn, "other", None);
+
+        let tree = IntentBmc::get_list(&mut conn).unwrap();
+        assert_eq!(tree.len(), 2);
+        assert_eq!(tree[0].intent.label, "client");
+        assert_eq!(tree[0].children[0].intent.label, "project");
+        assert_eq!(tree[0].children[0].children[0].intent.label, "workstream");
+        assert!(tree[1].children.is_empty());
+
+        // Deleting a parent moves its children to the top level.
+        IntentBmc::delete(&mut conn, client).unwrap();
+        let tree = IntentBmc::get_list(&mut conn).unwrap();
+        assert_eq!(tree.len(), 2);
+        assert_eq!(IntentBmc::get(&mut conn, project).unwrap().parent_id, None);
+    }
+
+    #[test]
+    fn test_create_intent_with_missing_parent() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let data = CreateIntent {
+            label: "foo".to_string(),
+            parent_id: Some(42),
+        };
+        let result = IntentBmc::create(&mut conn, &data);
+        assert!(matches!(result, Err(Error::DieselError(NotFound))));
+    }
+
+    #[test]
+    fn test_set_parent_prevents_cycles() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let a = create_child(&mut conn, "a", None);
+        let b = create_child(&mut conn, "b", Some(a));
+        let c = create_child(&mut conn, "c", Some(b));
+
+        let result = IntentBmc::set_parent(&mut conn, a, Some(c));
+        assert!(matches!(
+            result,
+            Err(Error::BmcError(BmcError::IntentCycle))
+        ));
+        let result = IntentBmc::set_parent(&mut conn, a, Some(a));
+        assert!(matches!(
+            result,
+            Err(Error::BmcError(BmcError::IntentCycle))
+        ));
+
+        IntentBmc::set_parent(&mut conn, c, Some(a)).unwrap();
+        IntentBmc::set_parent(&mut conn, b, None).unwrap();
+        assert_eq!(IntentBmc::get(&mut conn, c).unwrap().parent_id, Some(a));
+        assert_eq!(IntentBmc::get(&mut conn, b).unwrap().parent_id, None);
+    }
+
+    #[test]
+    fn test_archive_with_descendants() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let a = create_child(&mut conn, "a", None);
+        let b = create_child(&mut conn, "b", Some(a));
+        let c = create_child(&mut conn, "c", Some(b));
+        let other = create_child(&mut conn, "other", None);
+        IntentBmc::archive(&mut conn, c).unwrap();
+        let archived_at = IntentBmc::get(&mut conn, c).unwrap().archived_at;
+
+        let mut ids = IntentBmc::archive_with_descendants(&mut conn, a).unwrap();
+        ids.sort();
+        assert_eq!(ids, vec![a, b]);
+        assert!(IntentBmc::get(&mut conn, b).unwrap().archived_at.is_some());
+        assert_eq!(
+            IntentBmc::get(&mut conn, c).unwrap().archived_at,
+            archived_at
+        );
+        assert!(IntentBmc::get(&mut conn, other)
+            .unwrap()
+            .archived_at
+            .is_none());
+    }
+
+    #[test]
+    fn test_get_ancestor_ids() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let a = create_child(&mut conn, "a", None);
+        let b = create_child(&mut conn, "b", Some(a));
+        let c = create_child(&mut conn, "c", Some(b));
+
+        assert_eq!(
+            IntentBmc::get_ancestor_ids(&mut conn, c).unwrap(),
+            vec![b, a]
+        );
+        assert!(IntentBmc::get_ancestor_ids(&mut conn, a)
+            .unwrap()
+            .is_empty());
+    }
+
+    fn target(
+        period: IntentTargetPeriod,
+        target: Option<i64>,
+        budget: Option<i64>,
+    ) -> SetIntentTarget {
+        SetIntentTarget {
+            target_period: period,
+            target,
+            budget,
+        }
+    }
+
+    fn log_session(conn: &mut SqliteConnection, intent_id: i32, finished_at: i64, minutes: i64) {
+        let data = CreateManualSession {
+            intent_id,
+            started_at: finished_at - minutes * 60,
+            finished_at,
+        };
+        SessionBmc::create_manual(conn, &data).unwrap();
+    }
+
+    #[test]
+    fn test_set_target() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let id = create_child(&mut conn, "foo", None);
+
+        let data = target(IntentTargetPeriod::Total, Some(600), Some(900));
+        IntentBmc::set_target(&mut conn, id, &data).unwrap();
+        let intent = IntentBmc::get(&mut conn, id).unwrap();
+        assert_eq!(intent.target_period, IntentTargetPeriod::Total);
+        assert_eq!(intent.target, Some(600));
+        assert_eq!(intent.budget, Some(900));
+
+        let data = target(IntentTargetPeriod::Weekly, None, Some(0));
+        let result = IntentBmc::set_target(&mut conn, id, &data);
+        assert!(matches!(
+            result,
+            Err(Error::BmcError(BmcError::InvalidDuration))
+        ));
+
+        let data = target(IntentTargetPeriod::Weekly, None, None);
+        IntentBmc::set_target(&mut conn, id, &data).unwrap();
+        let intent = IntentBmc::get(&mut conn, id).unwrap();
+        assert_eq!(intent.target, None);
+        assert_eq!(intent.budget, None);
+    }
+
+    #[test]
+    fn test_get_target_progress() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let boundary = DayBoundary::new(Some("UTC"), 0);
+        let course = create_child(&mut conn, "course", None);
+        let lecture = create_child(&mut conn, "lecture", Some(course));
+        create_child(&mut conn, "untracked", None);
+        let data = target(IntentTargetPeriod::Weekly, Some(30), None);
+        IntentBmc::set_target(&mut conn, course, &data).unwrap();
+        let data = target(IntentTargetPeriod::Total, None, Some(60));
+        IntentBmc::set_target(&mut conn, lecture, &data).unwrap();
+
+        let now = chrono::Utc::now().timestamp();
+        // Sessions of sub-intents count towards their parents.
+        log_session(&mut conn, lecture, now, 20);
+        // Long before the current week, only counts towards the total.
+        log_session(&mut conn, lecture, 1688947200, 50);
+
+        let progress = IntentBmc::get_target_progress(&mut conn, &boundary).unwrap();
+        assert_eq!(progress.len(), 2);
+
+        let course = progress.iter().find(|p| p.intent_id == course).unwrap();
+        assert_eq!(course.focused, 20 * 60);
+        assert!(!course.is_target_reached());
+        assert!(!course.is_budget_exceeded());
+
+        let lecture = progress.iter().find(|p| p.intent_id == lecture).unwrap();
+        assert_eq!(lecture.focused, 70 * 60);
+        assert!(lecture.is_budget_exceeded());
+        assert!(!lecture.before(20 * 60).is_budget_exceeded());
+    }
+
+    #[test]
+    fn test_get_session_target_progress() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let boundary = DayBoundary::new(Some("UTC"), 0);
+        let course = create_child(&mut conn, "course", None);
+        let lecture = create_child(&mut conn, "lecture", Some(course));
+        let data = target(IntentTargetPeriod::Weekly, Some(30), None);
+        IntentBmc::set_target(&mut conn, course, &data).unwrap();
+        let data = target(IntentTargetPeriod::Total, Some(60), None);
+        IntentBmc::set_target(&mut conn, lecture, &data).unwrap();
+
+        let now = chrono::Utc::now().timestamp();
+        log_session(&mut conn, lecture, now, 20);
+        // Logged for a day long before the current week.
+        let started_at = 1688947200;
+        log_session(&mut conn, lecture, started_at + 50 * 60, 50);
+
+        let progress =
+            IntentBmc::get_session_target_progress(&mut conn, &boundary, lecture, started_at, 3000)
+                .unwrap();
+        assert_eq!(progress.len(), 2);
+        // The weekly target wasn't affected by the session.
+        let (before, after) = progress
+            .iter()
+            .find(|(_, p)| p.intent_id == course)
+            .unwrap();
+        assert_eq!(before.focused, 20 * 60);
+        assert_eq!(after.focused, 20 * 60);
+        // The total target was reached by it.
+        let (before, after) = progress
+            .iter()
+            .find(|(_, p)| p.intent_id == lecture)
+            .unwrap();
+        assert_eq!(before.focused, 20 * 60);
+        assert!(!before.is_target_reached() && after.is_target_reached());
+    }
+
+    #[test]
+    fn test_move_to_reorders_siblings() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let a = create_child(&mut conn, "a", None);
+        let b = create_child(&mut conn, "b", None);
+        let c = create_child(&mut conn, "c", None);
+        // Nested intents are ordered separately.
+        let child = create_child(&mut conn, "child", Some(a));
+        assert_eq!(IntentBmc::get(&mut conn, child).unwrap().position, 0);
+
+        IntentBmc::move_to(&mut conn, c, 0).unwrap();
+        let labels: Vec<_> = IntentBmc::get_list(&mut conn)
+            .unwrap()
+            .into_iter()
+            .map(|node| node.intent.label)
+            .collect();
+        assert_eq!(labels, vec!["c", "a", "b"]);
+
+        IntentBmc::move_to(&mut conn, c, 42).unwrap();
+        assert_eq!(IntentBmc::get(&mut conn, c).unwrap().position, 2);
+
+        // Pinned intents come first regardless of their position.
+        let data = UpdateIntent {
+            label: None,
+            pinned: Some(true),
+        };
+        IntentBmc::update(&mut conn, b, &data).unwrap();
+        let tree = IntentBmc::get_list(&mut conn).unwrap();
+        assert_eq!(tree[0].intent.id, b);
+        assert_eq!(tree[1].intent.id, a);
+
+        // Moving under another parent puts the intent last.
+        IntentBmc::set_parent(&mut conn, c, Some(a)).unwrap();
+        assert_eq!(IntentBmc::get(&mut conn, c).unwrap().position, 1);
+    }
+
+    #[test]
+    fn test_set_details() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let id = create_child(&mut conn, "foo", None);
+
+        let data = SetIntentDetails {
+            color: Some("#1a2B3c".to_string()),
+            icon: Some("📚".to_string()),
+            description: Some("**Reading** list".to_string()),
+        };
+        IntentBmc::set_details(&mut conn, id, &data).unwrap();
+        let intent = IntentBmc::get(&mut conn, id).unwrap();
+        assert_eq!(intent.color.as_deref(), Some("#1a2B3c"));
+        assert_eq!(intent.icon.as_deref(), Some("📚"));
+        assert_eq!(intent.description.as_deref(), Some("**Reading** list"));
+
+        let data = SetIntentDetails {
+            color: Some("red".to_string()),
+            ..Default::default()
+        };
+        let result = IntentBmc::set_details(&mut conn, id, &data);
+        assert!(matches!(
+            result,
+            Err(Error::BmcError(BmcError::InvalidColor))
+        ));
+
+        IntentBmc::set_details(&mut conn, id, &SetIntentDetails::default()).unwrap();
+        let intent = IntentBmc::get(&mut conn, id).unwrap();
+        assert_eq!(intent.color, None);
+        assert_eq!(intent.description, None);
+    }
 }
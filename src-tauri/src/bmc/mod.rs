@@ -1,15 +1,25 @@
+mod error;
 mod intent;
 mod intent_tag;
+mod rest_day;
 mod script;
+mod search;
 mod session;
+mod session_pause;
+mod stats;
 mod tag;
 mod task;
 mod theme;
 
+pub use error::*;
 pub use intent::*;
 pub use intent_tag::*;
+pub use rest_day::*;
 pub use script::*;
+pub use search::*;
 pub use session::*;
+pub use session_pause::*;
+pub use stats::*;
 pub use tag::*;
 pub use task::*;
 pub use theme::*;
@@ -22,7 +32,7 @@ use crate::prelude::Result;
 pub struct BaseBmc {}
 
 impl BaseBmc {
-    fn get_last_insert_id(conn: &mut SqliteConnection) -> Result<i32> {
+    pub(crate) fn get_last_insert_id(conn: &mut SqliteConnection) -> Result<i32> {
         let id: i32 = diesel::select(last_insert_rowid()).first(conn)?;
         Ok(id)
     }
@@ -40,6 +50,7 @@ mod test_helpers {
     pub fn create_dummy_intent(conn: &mut SqliteConnection) -> i32 {
         let data = CreateIntent {
             label: "foo".to_string(),
+            parent_id: None,
         };
         IntentBmc::create(conn, &data).unwrap()
     }
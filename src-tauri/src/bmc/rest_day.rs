@@ -0,0 +1,85 @@
+use diesel::prelude::*;
+use diesel::SqliteConnection;
+
+use crate::models::CreateRestDay;
+use crate::models::RestDay;
+use crate::prelude::Result;
+
+use super::BaseBmc;
+
+pub struct RestDayBmc {}
+
+impl RestDayBmc {
+    pub fn create(conn: &mut SqliteConnection, data: &CreateRestDay) -> Result<i32> {
+        use crate::db::schema::rest_days;
+
+        diesel::insert_into(rest_days::table)
+            .values(data)
+            .execute(conn)?;
+        BaseBmc::get_last_insert_id(conn)
+    }
+
+    pub fn delete(conn: &mut SqliteConnection, id: i32) -> Result<i32> {
+        use crate::db::schema::rest_days::dsl;
+
+        diesel::delete(dsl::rest_days.find(id)).execute(conn)?;
+        Ok(id)
+    }
+
+    pub fn get_list(conn: &mut SqliteConnection) -> Result<Vec<RestDay>> {
+        use crate::db::schema::rest_days::dsl;
+
+        let rest_days: Vec<RestDay> = dsl::rest_days.order(dsl::day.asc()).load(conn)?;
+        Ok(rest_days)
+    }
+}
+
+#[cfg(test)]
+mod rest_day_bmc_tests {
+    use chrono::NaiveDate;
+
+    use crate::{db::Db, prelude::Error};
+
+    use super::*;
+
+    fn date(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2023, 7, d).unwrap()
+    }
+
+    #[test]
+    fn test_create_rest_day() {
+        let mut conn = Db::establish_test_connection().unwrap();
+
+        let data = CreateRestDay { day: date(10) };
+        let id = RestDayBmc::create(&mut conn, &data).unwrap();
+
+        assert_eq!(id, 1);
+    }
+
+    #[test]
+    fn test_fail_create_duplicate_rest_day() {
+        let mut conn = Db::establish_test_connection().unwrap();
+
+        let data = CreateRestDay { day: date(10) };
+        RestDayBmc::create(&mut conn, &data).unwrap();
+        let result = RestDayBmc::create(&mut conn, &data);
+
+        assert!(matches!(result, Err(Error::DieselError(_))));
+    }
+
+    #[test]
+    fn test_get_list_and_delete_rest_days() {
+        let mut conn = Db::establish_test_connection().unwrap();
+
+        for day in [12, 10, 11] {
+            let data = CreateRestDay { day: date(day) };
+            RestDayBmc::create(&mut conn, &data).unwrap();
+        }
+        RestDayBmc::delete(&mut conn, 3).unwrap();
+        let rest_days = RestDayBmc::get_list(&mut conn).unwrap();
+
+        assert_eq!(rest_days.len(), 2);
+        assert_eq!(rest_days[0].day, date(10));
+        assert_eq!(rest_days[1].day, date(12));
+    }
+}
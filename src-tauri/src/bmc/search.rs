@@ -0,0 +1,253 @@
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Text};
+use diesel::SqliteConnection;
+use serde::Deserialize;
+use ts_rs::TS;
+
+use crate::models::{SearchHit, SearchHitKind, SearchIndexRow};
+use crate::prelude::Result;
+
+const DEFAULT_SEARCH_LIMIT: i64 = 50;
+/// Control characters delimiting matches in raw snippets, they can't come from user text
+/// that went through escaping, unlike `<mark>`.
+const MATCH_START: char = '\u{2}';
+const MATCH_END: char = '\u{3}';
+
+#[derive(TS, Deserialize, Debug, Clone)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct SearchOptions {
+    /// Words to look for, every word has to match the start of a word in the text.
+    pub query: String,
+    /// Kinds of hits to include, defaults to all of them.
+    pub kinds: Option<Vec<SearchHitKind>>,
+    #[ts(type = "number | null")]
+    pub limit: Option<i64>,
+}
+
+pub struct SearchBmc {}
+
+impl SearchBmc {
+    /// Searches the full-text index kept up to date by triggers, best matches come first.
+    pub fn search(conn: &mut SqliteConnection, options: &SearchOptions) -> Result<Vec<SearchHit>> {
+        let Some(match_query) = Self::to_match_query(&options.query) else {
+            return Ok(vec![]);
+        };
+
+        let mut query = format!(
+            "SELECT kind, item_id,
+                snippet(search_index, 2, '{MATCH_START}', '{MATCH_END}', '…', 12) AS snippet,
+                bm25(search_index) AS rank
+            FROM search_index
+            WHERE search_index MATCH ?"
+        );
+        if let Some(kinds) = &options.kinds {
+            // Kinds come from a fixed set of values, so they can be inlined.
+            let kinds: Vec<_> = kinds
+                .iter()
+                .map(|kind| format!("'{}'", kind.as_index_kind()))
+                .collect();
+            query += &format!(" AND kind IN ({})", kinds.join(", "));
+        }
+        query += " ORDER BY rank LIMIT ?";
+
+        let rows: Vec<SearchIndexRow> = diesel::sql_query(query)
+            .bind::<Text, _>(match_query)
+            .bind::<BigInt, _>(options.limit.unwrap_or(DEFAULT_SEARCH_LIMIT))
+            .load(conn)?;
+
+        let hits = rows
+            .into_iter()
+            .filter_map(|row| {
+                Some(SearchHit {
+                    kind: SearchHitKind::from_index_kind(&row.kind)?,
+                    id: row.item_id,
+                    snippet: Self::highlight(&row.snippet),
+                    rank: row.rank,
+                })
+            })
+            .collect();
+        Ok(hits)
+    }
+
+    /// Escapes the snippet for HTML, then wraps the matches in `<mark>` tags.
+    fn highlight(snippet: &str) -> String {
+        let mut html = String::with_capacity(snippet.len());
+        for c in snippet.chars() {
+            match c {
+                MATCH_START => html.push_str("<mark>"),
+                MATCH_END => html.push_str("</mark>"),
+                '&' => html.push_str("&amp;"),
+                '<' => html.push_str("&lt;"),
+                '>' => html.push_str("&gt;"),
+                '"' => html.push_str("&quot;"),
+                '\'' => html.push_str("&#39;"),
+                c => html.push(c),
+            }
+        }
+        html
+    }
+
+    /// Turns user input into an FTS5 query matching every word as a prefix. Words are quoted
+    /// so FTS5 operators and punctuation in the input are searched for literally.
+    fn to_match_query(query: &str) -> Option<String> {
+        let terms: Vec<_> = query
+            .split_whitespace()
+            .map(|word| word.replace('"', ""))
+            .filter(|word| !word.is_empty())
+            .map(|word| format!("\"{}\"*", word))
+            .collect();
+        if terms.is_empty() {
+            return None;
+        }
+        Some(terms.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod search_bmc_tests {
+    use crate::{
+        bmc::{IntentBmc, SessionBmc, TagBmc, TaskBmc},
+        db::Db,
+        models::{
+            CreateIntent, CreateSession, CreateTag, CreateTask, TaskPriority, UpdateSession,
+            UpdateTask,
+        },
+        timer::SessionType,
+    };
+
+    use super::*;
+
+    fn search(conn: &mut SqliteConnection, query: &str) -> Vec<SearchHit> {
+        let options = SearchOptions {
+            query: query.to_string(),
+            kinds: None,
+            limit: None,
+        };
+        SearchBmc::search(conn, &options).unwrap()
+    }
+
+    #[test]
+    fn test_to_match_query() {
+        assert_eq!(
+            SearchBmc::to_match_query("migration bug"),
+            Some("\"migration\"* \"bug\"*".to_string())
+        );
+        assert_eq!(
+            SearchBmc::to_match_query("\"foo\" OR -bar"),
+            Some("\"foo\"* \"OR\"* \"-bar\"*".to_string())
+        );
+        assert_eq!(SearchBmc::to_match_query("  \"\" "), None);
+    }
+
+    #[test]
+    fn test_highlight() {
+        assert_eq!(
+            SearchBmc::highlight("a <b> & \u{2}\"c\"\u{3}'s"),
+            "a &lt;b&gt; &amp; <mark>&quot;c&quot;</mark>&#39;s"
+        );
+    }
+
+    #[test]
+    fn test_search_escapes_snippets() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        IntentBmc::create(
+            &mut conn,
+            &CreateIntent {
+                label: "<img src=x> docs".to_string(),
+                parent_id: None,
+            },
+        )
+        .unwrap();
+
+        let hits = search(&mut conn, "docs");
+        assert_eq!(hits[0].snippet, "&lt;img src=x&gt; <mark>docs</mark>");
+    }
+
+    #[test]
+    fn test_search_across_tables() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let intent_id = IntentBmc::create(
+            &mut conn,
+            &CreateIntent {
+                label: "Database migrations".to_string(),
+                parent_id: None,
+            },
+        )
+        .unwrap();
+        TagBmc::create(
+            &mut conn,
+            &CreateTag {
+                label: "migration".to_string(),
+            },
+        )
+        .unwrap();
+        let task_id = TaskBmc::create(
+            &mut conn,
+            &CreateTask {
+                body: "Write the migration for rest days".to_string(),
+                intent_id,
+                priority: TaskPriority::Normal,
+                due_date: None,
+                parent_task_id: None,
+            },
+        )
+        .unwrap();
+        let data = CreateSession {
+            duration: 1500,
+            started_at: 1688976000,
+            intent_id,
+            session_type: SessionType::Focus,
+            planned_duration: None,
+        };
+        let session_id = SessionBmc::create(&mut conn, &data).unwrap();
+        SessionBmc::update(
+            &mut conn,
+            session_id,
+            &UpdateSession {
+                summary: Some("Found the migration bug in the timestamps".to_string()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let hits = search(&mut conn, "migration");
+        assert_eq!(hits.len(), 4);
+
+        let hits = search(&mut conn, "migration bug");
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].kind, SearchHitKind::Session);
+        assert_eq!(hits[0].id, session_id);
+        assert!(hits[0].snippet.contains("<mark>migration</mark>"));
+
+        let options = SearchOptions {
+            query: "migr".to_string(),
+            kinds: Some(vec![SearchHitKind::Task, SearchHitKind::Tag]),
+            limit: Some(1),
+        };
+        let hits = SearchBmc::search(&mut conn, &options).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert!(matches!(
+            hits[0].kind,
+            SearchHitKind::Task | SearchHitKind::Tag
+        ));
+
+        // The index follows updates and deletes.
+        TaskBmc::update(
+            &mut conn,
+            task_id,
+            &UpdateTask {
+                body: Some("Plan the sprint".to_string()),
+                priority: None,
+                intent_id: None,
+            },
+        )
+        .unwrap();
+        assert_eq!(search(&mut conn, "sprint")[0].id, task_id);
+        assert_eq!(search(&mut conn, "migration").len(), 3);
+
+        TaskBmc::delete(&mut conn, task_id).unwrap();
+        SessionBmc::delete(&mut conn, session_id).unwrap();
+        assert!(search(&mut conn, "sprint").is_empty());
+        assert_eq!(search(&mut conn, "migration").len(), 2);
+    }
+}
@@ -1,49 +1,170 @@
+use chrono::NaiveDate;
 use diesel::prelude::*;
 use diesel::SqliteConnection;
 use serde::Deserialize;
 use ts_rs::TS;
 
+use crate::day::DayBoundary;
+use crate::models::CreateManualSession;
 use crate::models::CreateSession;
 use crate::models::Session;
+use crate::models::SessionDetails;
 use crate::models::UpdateSession;
-use crate::prelude::Result;
+use crate::prelude::{Error, Result};
+use crate::timer::SessionType;
 
-use super::BaseBmc;
+use super::{BaseBmc, BmcError, SessionPauseBmc};
 
 #[derive(TS, Deserialize)]
 #[ts(export, export_to = "../src/bindings/")]
 pub struct GetSessionsOptions {
     pub intent_id: Option<i32>,
+    /// Session types to include, defaults to focus sessions only.
+    pub session_types: Option<Vec<SessionType>>,
+    /// Whether to include manually logged sessions, defaults to true.
+    pub include_manual: Option<bool>,
+    /// Only include sessions started within the given day, has to be turned into a range
+    /// with [`GetSessionsOptions::resolve_day`] before listing.
+    #[ts(type = "string | null")]
+    pub day: Option<NaiveDate>,
+    #[ts(type = "number | null")]
+    pub started_from: Option<i64>,
+    #[ts(type = "number | null")]
+    pub started_to: Option<i64>,
     pub offset: Option<i32>,
     pub limit: Option<i32>,
 }
 
+impl GetSessionsOptions {
+    /// Replaces `day` with the range of timestamps the day spans according to the boundary.
+    /// Rejects a day combined with an explicit range.
+    pub fn resolve_day(&mut self, boundary: &DayBoundary) -> Result<()> {
+        let Some(day) = self.day.take() else {
+            return Ok(());
+        };
+        if self.started_from.is_some() || self.started_to.is_some() {
+            return Err(BmcError::InvalidTimeRange.into());
+        }
+        let (from, to) = boundary.range_of(day);
+        self.started_from = Some(from);
+        self.started_to = Some(to);
+        Ok(())
+    }
+}
+
 pub struct SessionBmc {}
 
 impl SessionBmc {
     pub fn create(conn: &mut SqliteConnection, data: &CreateSession) -> Result<i32> {
+        let finished_at = chrono::Utc::now().timestamp();
+        Self::create_finished(conn, data, finished_at)
+    }
+
+    /// Inserts a session which finished at a known time, e.g. one restored after a restart.
+    pub fn create_finished(
+        conn: &mut SqliteConnection,
+        data: &CreateSession,
+        finished_at: i64,
+    ) -> Result<i32> {
+        use crate::db::schema::sessions;
+
+        diesel::insert_into(sessions::table)
+            .values((data, sessions::finished_at.eq(finished_at)))
+            .execute(conn)?;
+        BaseBmc::get_last_insert_id(conn)
+    }
+
+    /// Inserts a session taken from another app's history, it counts as logged by hand.
+    /// Overlaps are left to the caller to check.
+    pub fn create_imported(
+        conn: &mut SqliteConnection,
+        data: &CreateSession,
+        finished_at: i64,
+    ) -> Result<i32> {
         use crate::db::schema::sessions;
 
         diesel::insert_into(sessions::table)
-            .values(data)
+            .values((
+                data,
+                sessions::finished_at.eq(finished_at),
+                sessions::is_manual.eq(true),
+            ))
             .execute(conn)?;
         BaseBmc::get_last_insert_id(conn)
     }
 
+    /// Logs a focus session that was not tracked by the timer.
+    /// Rejects empty ranges and ranges overlapping any existing session.
+    pub fn create_manual(conn: &mut SqliteConnection, data: &CreateManualSession) -> Result<i32> {
+        use crate::db::schema::sessions::dsl;
+
+        if data.finished_at <= data.started_at {
+            return Err(BmcError::InvalidTimeRange.into());
+        }
+
+        conn.transaction::<_, Error, _>(|conn| {
+            Self::check_overlap(conn, data.started_at, data.finished_at, None)?;
+
+            diesel::insert_into(dsl::sessions)
+                .values((
+                    dsl::duration.eq((data.finished_at - data.started_at) as i32),
+                    dsl::started_at.eq(data.started_at),
+                    dsl::finished_at.eq(data.finished_at),
+                    dsl::intent_id.eq(data.intent_id),
+                    dsl::session_type.eq(SessionType::Focus),
+                    dsl::is_manual.eq(true),
+                ))
+                .execute(conn)?;
+            BaseBmc::get_last_insert_id(conn)
+        })
+    }
+
+    /// Updates a session, validating the resulting time range and duration
+    /// whenever any of them is changed. Pauses are fitted into the new range.
     pub fn update(conn: &mut SqliteConnection, id: i32, data: &UpdateSession) -> Result<i32> {
         use crate::db::schema::sessions::dsl;
 
-        diesel::update(dsl::sessions.find(id))
-            .set(data)
-            .execute(conn)?;
+        conn.transaction::<_, Error, _>(|conn| {
+            let changes_time =
+                data.duration.is_some() || data.started_at.is_some() || data.finished_at.is_some();
+
+            if changes_time {
+                let session: Session = dsl::sessions.find(id).first(conn)?;
+                let started_at = data.started_at.unwrap_or(session.started_at);
+                let finished_at = data.finished_at.unwrap_or(session.finished_at);
+                let duration = data.duration.unwrap_or(session.duration) as i64;
+
+                if finished_at <= started_at {
+                    return Err(BmcError::InvalidTimeRange.into());
+                }
+                if duration <= 0 || duration > finished_at - started_at {
+                    return Err(BmcError::InvalidDuration.into());
+                }
+                Self::check_overlap(conn, started_at, finished_at, Some(id))?;
+                SessionPauseBmc::clamp_to_range(conn, id, started_at, finished_at)?;
+            }
+
+            diesel::update(dsl::sessions.find(id))
+                .set(data)
+                .execute(conn)?;
+            Ok(id)
+        })
+    }
+
+    /// Deletes a session, its pauses are removed by the foreign key cascade.
+    pub fn delete(conn: &mut SqliteConnection, id: i32) -> Result<i32> {
+        use crate::db::schema::sessions::dsl;
+
+        diesel::delete(dsl::sessions.find(id)).execute(conn)?;
         Ok(id)
     }
 
-    pub fn get(conn: &mut SqliteConnection, id: i32) -> Result<Session> {
+    pub fn get(conn: &mut SqliteConnection, id: i32) -> Result<SessionDetails> {
         use crate::db::schema::sessions::dsl;
 
         let session: Session = dsl::sessions.find(id).first(conn)?;
-        Ok(session)
+        let pauses = SessionPauseBmc::get_list(conn, id)?;
+        Ok(SessionDetails { session, pauses })
     }
 
     pub fn get_list(
@@ -53,13 +174,34 @@ impl SessionBmc {
         use crate::db::schema::sessions::dsl;
 
         let mut query = dsl::sessions.into_boxed();
+        let mut session_types = vec![SessionType::Focus];
 
         if let Some(options) = options {
+            if options.day.is_some() {
+                return Err(BmcError::UnresolvedDay.into());
+            }
+
             // Filter out sessions based on the `intent_id` field
             if let Some(intent_id) = options.intent_id {
                 query = query.filter(dsl::intent_id.eq(intent_id));
             }
 
+            if let Some(types) = options.session_types {
+                session_types = types;
+            }
+
+            if options.include_manual == Some(false) {
+                query = query.filter(dsl::is_manual.eq(false));
+            }
+
+            if let Some(started_from) = options.started_from {
+                query = query.filter(dsl::started_at.ge(started_from));
+            }
+
+            if let Some(started_to) = options.started_to {
+                query = query.filter(dsl::started_at.lt(started_to));
+            }
+
             // Apply pagination based on the `offset` and `limit` fields
             if let Some(offset) = options.offset {
                 query = query.offset(offset as i64);
@@ -69,10 +211,76 @@ impl SessionBmc {
                 query = query.limit(limit as i64);
             }
         };
+        query = query.filter(dsl::session_type.eq_any(session_types));
 
         let sessions: Vec<Session> = query.load(conn)?;
         Ok(sessions)
     }
+
+    /// Focus sessions overlapping the `[from, to)` timestamp range, together with their pauses.
+    pub fn get_overlapping(
+        conn: &mut SqliteConnection,
+        from: i64,
+        to: i64,
+    ) -> Result<Vec<SessionDetails>> {
+        use crate::db::schema::sessions::dsl;
+
+        let sessions: Vec<Session> = dsl::sessions
+            .filter(dsl::session_type.eq(SessionType::Focus))
+            .filter(dsl::started_at.lt(to))
+            .filter(dsl::finished_at.gt(from))
+            .order(dsl::started_at.asc())
+            .load(conn)?;
+        let ids: Vec<i32> = sessions.iter().map(|session| session.id).collect();
+        let pauses = SessionPauseBmc::get_list_by_sessions(conn, &ids)?;
+
+        let details = sessions
+            .into_iter()
+            .map(|session| SessionDetails {
+                pauses: pauses
+                    .iter()
+                    .filter(|pause| pause.session_id == session.id)
+                    .cloned()
+                    .collect(),
+                session,
+            })
+            .collect();
+        Ok(details)
+    }
+
+    /// Whether any session other than `exclude_id` overlaps the `[started_at, finished_at)`
+    /// range, adjacent sessions don't overlap.
+    pub fn overlaps(
+        conn: &mut SqliteConnection,
+        started_at: i64,
+        finished_at: i64,
+        exclude_id: Option<i32>,
+    ) -> Result<bool> {
+        use crate::db::schema::sessions::dsl;
+
+        let mut query = dsl::sessions
+            .filter(dsl::started_at.lt(finished_at))
+            .filter(dsl::finished_at.gt(started_at))
+            .into_boxed();
+        if let Some(id) = exclude_id {
+            query = query.filter(dsl::id.ne(id));
+        }
+
+        let overlapping: i64 = query.count().get_result(conn)?;
+        Ok(overlapping > 0)
+    }
+
+    fn check_overlap(
+        conn: &mut SqliteConnection,
+        started_at: i64,
+        finished_at: i64,
+        exclude_id: Option<i32>,
+    ) -> Result<()> {
+        if Self::overlaps(conn, started_at, finished_at, exclude_id)? {
+            return Err(BmcError::OverlappingSession.into());
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -80,8 +288,7 @@ mod session_bmc_tests {
     use crate::{
         bmc::{test_helpers::create_dummy_intent, IntentBmc},
         db::Db,
-        models::CreateIntent,
-        prelude::Error,
+        models::{CreateIntent, CreateSessionPause},
     };
 
     use super::*;
@@ -93,6 +300,7 @@ mod session_bmc_tests {
         // Create related intent.
         let data = CreateIntent {
             label: "foo".to_string(),
+            parent_id: None,
         };
         let intent_id = IntentBmc::create(&mut conn, &data).unwrap();
 
@@ -101,6 +309,8 @@ mod session_bmc_tests {
             duration: 1500,
             started_at,
             intent_id,
+            session_type: SessionType::Focus,
+            planned_duration: None,
         };
         let id = SessionBmc::create(&mut conn, &data).unwrap();
 
@@ -116,6 +326,8 @@ mod session_bmc_tests {
             duration: 1500,
             started_at,
             intent_id: 1,
+            session_type: SessionType::Focus,
+            planned_duration: None,
         };
         let result = SessionBmc::create(&mut conn, &data);
 
@@ -129,6 +341,7 @@ mod session_bmc_tests {
         // create related intent
         let data = CreateIntent {
             label: "foo".to_string(),
+            parent_id: None,
         };
         let intent_id = IntentBmc::create(&mut conn, &data).unwrap();
 
@@ -137,9 +350,11 @@ mod session_bmc_tests {
             duration: 1500,
             started_at,
             intent_id,
+            session_type: SessionType::Focus,
+            planned_duration: None,
         };
         let id = SessionBmc::create(&mut conn, &data).unwrap();
-        let session = SessionBmc::get(&mut conn, id).unwrap();
+        let session = SessionBmc::get(&mut conn, id).unwrap().session;
 
         assert_eq!(session.id, 1);
         assert_eq!(session.started_at, started_at);
@@ -155,6 +370,7 @@ mod session_bmc_tests {
         // Create related intent.
         let data = CreateIntent {
             label: "foo".to_string(),
+            parent_id: None,
         };
         let intent_id = IntentBmc::create(&mut conn, &data).unwrap();
 
@@ -164,6 +380,8 @@ mod session_bmc_tests {
                 duration: 1500,
                 started_at,
                 intent_id,
+                session_type: SessionType::Focus,
+                planned_duration: None,
             };
             SessionBmc::create(&mut conn, &data).unwrap();
         }
@@ -179,6 +397,7 @@ mod session_bmc_tests {
         // Create related intent.
         let data = CreateIntent {
             label: "foo".to_string(),
+            parent_id: None,
         };
         let intent_id = IntentBmc::create(&mut conn, &data).unwrap();
         let intent_id2 = IntentBmc::create(&mut conn, &data).unwrap();
@@ -189,6 +408,8 @@ mod session_bmc_tests {
                 duration: 1500,
                 started_at,
                 intent_id,
+                session_type: SessionType::Focus,
+                planned_duration: None,
             };
             SessionBmc::create(&mut conn, &data).unwrap();
         }
@@ -198,12 +419,19 @@ mod session_bmc_tests {
                 duration: 1500,
                 started_at,
                 intent_id: intent_id2,
+                session_type: SessionType::Focus,
+                planned_duration: None,
             };
             SessionBmc::create(&mut conn, &data).unwrap();
         }
 
         let options = GetSessionsOptions {
             intent_id: Some(intent_id),
+            session_types: None,
+            include_manual: None,
+            day: None,
+            started_from: None,
+            started_to: None,
             offset: None,
             limit: None,
         };
@@ -219,6 +447,7 @@ mod session_bmc_tests {
         // Create related intent.
         let data = CreateIntent {
             label: "foo".to_string(),
+            parent_id: None,
         };
         let intent_id = IntentBmc::create(&mut conn, &data).unwrap();
 
@@ -228,12 +457,19 @@ mod session_bmc_tests {
                 duration: 1500,
                 started_at,
                 intent_id,
+                session_type: SessionType::Focus,
+                planned_duration: None,
             };
             SessionBmc::create(&mut conn, &data).unwrap();
         }
 
         let options = GetSessionsOptions {
             intent_id: Some(intent_id),
+            session_types: None,
+            include_manual: None,
+            day: None,
+            started_from: None,
+            started_to: None,
             offset: Some(4),
             limit: Some(4),
         };
@@ -253,15 +489,416 @@ mod session_bmc_tests {
             duration: 1500,
             started_at: 0,
             intent_id,
+            session_type: SessionType::Focus,
+            planned_duration: None,
         };
         let session_id = SessionBmc::create(&mut conn, &data).unwrap();
         // Update session
         let data = UpdateSession {
             summary: Some("foo".to_string()),
+            ..Default::default()
         };
         SessionBmc::update(&mut conn, session_id, &data).unwrap();
         // Query and test update
-        let session = SessionBmc::get(&mut conn, session_id).unwrap();
+        let session = SessionBmc::get(&mut conn, session_id).unwrap().session;
         assert_eq!(session.summary.unwrap(), "foo".to_string());
     }
+
+    #[test]
+    fn test_get_session_with_pauses() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        // Create intent.
+        let intent_id = create_dummy_intent(&mut conn);
+        // Create session.
+        let data = CreateSession {
+            duration: 1500,
+            started_at: 0,
+            intent_id,
+            session_type: SessionType::Focus,
+            planned_duration: None,
+        };
+        let session_id = SessionBmc::create(&mut conn, &data).unwrap();
+        // Create pauses.
+        for started_at in [300, 900] {
+            let data = CreateSessionPause {
+                session_id,
+                started_at,
+                finished_at: started_at + 60,
+            };
+            SessionPauseBmc::create(&mut conn, &data).unwrap();
+        }
+        // Query and test pauses
+        let details = SessionBmc::get(&mut conn, session_id).unwrap();
+        assert_eq!(details.session.id, session_id);
+        assert_eq!(details.pauses.len(), 2);
+        assert_eq!(details.pauses[0].finished_at, 360);
+    }
+
+    #[test]
+    fn test_get_list_of_sessions_defaults_to_focus() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let intent_id = create_dummy_intent(&mut conn);
+
+        for session_type in [
+            SessionType::Focus,
+            SessionType::Break,
+            SessionType::LongBreak,
+        ] {
+            let data = CreateSession {
+                duration: 300,
+                started_at: 0,
+                intent_id,
+                session_type,
+                planned_duration: None,
+            };
+            SessionBmc::create(&mut conn, &data).unwrap();
+        }
+        let sessions = SessionBmc::get_list(&mut conn, None).unwrap();
+
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].session_type, SessionType::Focus);
+    }
+
+    #[test]
+    fn test_get_list_of_sessions_by_session_types() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let intent_id = create_dummy_intent(&mut conn);
+
+        for session_type in [
+            SessionType::Focus,
+            SessionType::Break,
+            SessionType::Break,
+            SessionType::LongBreak,
+        ] {
+            let data = CreateSession {
+                duration: 300,
+                started_at: 0,
+                intent_id,
+                session_type,
+                planned_duration: None,
+            };
+            SessionBmc::create(&mut conn, &data).unwrap();
+        }
+
+        let options = GetSessionsOptions {
+            intent_id: None,
+            session_types: Some(vec![SessionType::Break, SessionType::LongBreak]),
+            include_manual: None,
+            day: None,
+            started_from: None,
+            started_to: None,
+            offset: None,
+            limit: None,
+        };
+        let sessions = SessionBmc::get_list(&mut conn, Some(options)).unwrap();
+
+        assert_eq!(sessions.len(), 3);
+        assert!(sessions
+            .iter()
+            .all(|session| session.session_type != SessionType::Focus));
+    }
+
+    #[test]
+    fn test_get_session_with_planned_duration() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let intent_id = create_dummy_intent(&mut conn);
+
+        // Session planned for 25 minutes but extended by 10.
+        let data = CreateSession {
+            duration: 2100,
+            started_at: 0,
+            intent_id,
+            session_type: SessionType::Focus,
+            planned_duration: Some(1500),
+        };
+        let id = SessionBmc::create(&mut conn, &data).unwrap();
+        let session = SessionBmc::get(&mut conn, id).unwrap().session;
+
+        assert_eq!(session.duration, 2100);
+        assert_eq!(session.planned_duration, Some(1500));
+    }
+
+    #[test]
+    fn test_create_manual_session() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let intent_id = create_dummy_intent(&mut conn);
+
+        let data = CreateManualSession {
+            intent_id,
+            started_at: 3600,
+            finished_at: 5400,
+        };
+        let id = SessionBmc::create_manual(&mut conn, &data).unwrap();
+        let session = SessionBmc::get(&mut conn, id).unwrap().session;
+
+        assert!(session.is_manual);
+        assert_eq!(session.duration, 1800);
+        assert_eq!(session.finished_at, 5400);
+        assert_eq!(session.session_type, SessionType::Focus);
+    }
+
+    #[test]
+    fn test_fail_create_manual_session_invalid_time_range() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let intent_id = create_dummy_intent(&mut conn);
+
+        let data = CreateManualSession {
+            intent_id,
+            started_at: 5400,
+            finished_at: 3600,
+        };
+        let result = SessionBmc::create_manual(&mut conn, &data);
+
+        assert!(matches!(
+            result,
+            Err(Error::BmcError(BmcError::InvalidTimeRange))
+        ));
+    }
+
+    #[test]
+    fn test_fail_create_manual_session_overlapping_session() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let intent_id = create_dummy_intent(&mut conn);
+
+        let data = CreateManualSession {
+            intent_id,
+            started_at: 3600,
+            finished_at: 5400,
+        };
+        SessionBmc::create_manual(&mut conn, &data).unwrap();
+
+        let data = CreateManualSession {
+            intent_id,
+            started_at: 5000,
+            finished_at: 6000,
+        };
+        let result = SessionBmc::create_manual(&mut conn, &data);
+        assert!(matches!(
+            result,
+            Err(Error::BmcError(BmcError::OverlappingSession))
+        ));
+
+        // Adjacent sessions do not overlap.
+        let data = CreateManualSession {
+            intent_id,
+            started_at: 5400,
+            finished_at: 6000,
+        };
+        assert!(SessionBmc::create_manual(&mut conn, &data).is_ok());
+    }
+
+    #[test]
+    fn test_get_list_of_sessions_without_manual() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let intent_id = create_dummy_intent(&mut conn);
+
+        let data = CreateManualSession {
+            intent_id,
+            started_at: 3600,
+            finished_at: 5400,
+        };
+        SessionBmc::create_manual(&mut conn, &data).unwrap();
+        let data = CreateSession {
+            duration: 1500,
+            started_at: 7200,
+            intent_id,
+            session_type: SessionType::Focus,
+            planned_duration: None,
+        };
+        SessionBmc::create(&mut conn, &data).unwrap();
+
+        let sessions = SessionBmc::get_list(&mut conn, None).unwrap();
+        assert_eq!(sessions.len(), 2);
+
+        let options = GetSessionsOptions {
+            intent_id: None,
+            session_types: None,
+            include_manual: Some(false),
+            day: None,
+            started_from: None,
+            started_to: None,
+            offset: None,
+            limit: None,
+        };
+        let sessions = SessionBmc::get_list(&mut conn, Some(options)).unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert!(!sessions[0].is_manual);
+    }
+
+    #[test]
+    fn test_get_list_of_sessions_by_day() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        // 2023-07-10 03:00 and 05:00 UTC, days start at 04:00.
+        create_timed_session(&mut conn, 1688958000);
+        let id = create_timed_session(&mut conn, 1688965200);
+        let boundary = DayBoundary::new(Some("UTC"), 4);
+        let options = || GetSessionsOptions {
+            intent_id: None,
+            session_types: None,
+            include_manual: None,
+            day: NaiveDate::from_ymd_opt(2023, 7, 10),
+            started_from: None,
+            started_to: None,
+            offset: None,
+            limit: None,
+        };
+
+        // The day has to be resolved before listing.
+        let result = SessionBmc::get_list(&mut conn, Some(options()));
+        assert!(matches!(
+            result,
+            Err(Error::BmcError(BmcError::UnresolvedDay))
+        ));
+
+        let mut resolved = options();
+        resolved.resolve_day(&boundary).unwrap();
+        assert_eq!(resolved.started_from, Some(1688961600));
+        assert_eq!(resolved.started_to, Some(1688961600 + 24 * 60 * 60));
+        let sessions = SessionBmc::get_list(&mut conn, Some(resolved)).unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].id, id);
+
+        // A day doesn't combine with an explicit range.
+        let mut combined = GetSessionsOptions {
+            started_from: Some(0),
+            ..options()
+        };
+        let result = combined.resolve_day(&boundary);
+        assert!(matches!(
+            result,
+            Err(Error::BmcError(BmcError::InvalidTimeRange))
+        ));
+    }
+
+    fn create_timed_session(conn: &mut SqliteConnection, started_at: i64) -> i32 {
+        let intent_id = create_dummy_intent(conn);
+        let data = CreateManualSession {
+            intent_id,
+            started_at,
+            finished_at: started_at + 1500,
+        };
+        SessionBmc::create_manual(conn, &data).unwrap()
+    }
+
+    #[test]
+    fn test_update_session_time_range_and_intent() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let session_id = create_timed_session(&mut conn, 0);
+        let intent_id = create_dummy_intent(&mut conn);
+
+        let data = UpdateSession {
+            duration: Some(1200),
+            started_at: Some(600),
+            finished_at: Some(1800),
+            intent_id: Some(intent_id),
+            ..Default::default()
+        };
+        SessionBmc::update(&mut conn, session_id, &data).unwrap();
+        let session = SessionBmc::get(&mut conn, session_id).unwrap().session;
+
+        assert_eq!(session.duration, 1200);
+        assert_eq!(session.started_at, 600);
+        assert_eq!(session.finished_at, 1800);
+        assert_eq!(session.intent_id, intent_id);
+    }
+
+    #[test]
+    fn test_fail_update_session_invalid_time_range() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let session_id = create_timed_session(&mut conn, 0);
+
+        let data = UpdateSession {
+            finished_at: Some(0),
+            ..Default::default()
+        };
+        let result = SessionBmc::update(&mut conn, session_id, &data);
+
+        assert!(matches!(
+            result,
+            Err(Error::BmcError(BmcError::InvalidTimeRange))
+        ));
+    }
+
+    #[test]
+    fn test_fail_update_session_duration_longer_than_wall_time() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let session_id = create_timed_session(&mut conn, 0);
+
+        let data = UpdateSession {
+            duration: Some(1501),
+            ..Default::default()
+        };
+        let result = SessionBmc::update(&mut conn, session_id, &data);
+
+        assert!(matches!(
+            result,
+            Err(Error::BmcError(BmcError::InvalidDuration))
+        ));
+    }
+
+    #[test]
+    fn test_fail_update_session_overlapping_session() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let session_id = create_timed_session(&mut conn, 0);
+        create_timed_session(&mut conn, 3600);
+
+        let data = UpdateSession {
+            finished_at: Some(4000),
+            ..Default::default()
+        };
+        let result = SessionBmc::update(&mut conn, session_id, &data);
+
+        assert!(matches!(
+            result,
+            Err(Error::BmcError(BmcError::OverlappingSession))
+        ));
+    }
+
+    #[test]
+    fn test_update_session_time_range_clamps_pauses() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let session_id = create_timed_session(&mut conn, 0);
+        for (started_at, finished_at) in [(100, 200), (550, 700), (1000, 1100), (1400, 1450)] {
+            let data = CreateSessionPause {
+                session_id,
+                started_at,
+                finished_at,
+            };
+            SessionPauseBmc::create(&mut conn, &data).unwrap();
+        }
+
+        let data = UpdateSession {
+            duration: Some(300),
+            started_at: Some(600),
+            finished_at: Some(1050),
+            ..Default::default()
+        };
+        SessionBmc::update(&mut conn, session_id, &data).unwrap();
+        let pauses = SessionPauseBmc::get_list(&mut conn, session_id).unwrap();
+
+        let ranges: Vec<(i64, i64)> = pauses
+            .iter()
+            .map(|pause| (pause.started_at, pause.finished_at))
+            .collect();
+        assert_eq!(ranges, vec![(600, 700), (1000, 1050)]);
+    }
+
+    #[test]
+    fn test_delete_session_with_pauses() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let session_id = create_timed_session(&mut conn, 0);
+        let data = CreateSessionPause {
+            session_id,
+            started_at: 300,
+            finished_at: 360,
+        };
+        SessionPauseBmc::create(&mut conn, &data).unwrap();
+
+        SessionBmc::delete(&mut conn, session_id).unwrap();
+
+        assert!(SessionBmc::get(&mut conn, session_id).is_err());
+        assert!(SessionPauseBmc::get_list(&mut conn, session_id)
+            .unwrap()
+            .is_empty());
+    }
 }
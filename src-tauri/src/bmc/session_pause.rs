@@ -0,0 +1,151 @@
+use diesel::prelude::*;
+use diesel::SqliteConnection;
+
+use crate::models::CreateSessionPause;
+use crate::models::SessionPause;
+use crate::prelude::Result;
+
+use super::BaseBmc;
+
+pub struct SessionPauseBmc {}
+
+impl SessionPauseBmc {
+    pub fn create(conn: &mut SqliteConnection, data: &CreateSessionPause) -> Result<i32> {
+        use crate::db::schema::session_pauses;
+
+        diesel::insert_into(session_pauses::table)
+            .values(data)
+            .execute(conn)?;
+        BaseBmc::get_last_insert_id(conn)
+    }
+
+    pub fn get_list(conn: &mut SqliteConnection, session_id: i32) -> Result<Vec<SessionPause>> {
+        use crate::db::schema::session_pauses::dsl;
+
+        let pauses: Vec<SessionPause> = dsl::session_pauses
+            .filter(dsl::session_id.eq(session_id))
+            .order(dsl::started_at.asc())
+            .load(conn)?;
+        Ok(pauses)
+    }
+
+    /// Pauses of all the given sessions.
+    pub fn get_list_by_sessions(
+        conn: &mut SqliteConnection,
+        session_ids: &[i32],
+    ) -> Result<Vec<SessionPause>> {
+        use crate::db::schema::session_pauses::dsl;
+
+        let pauses: Vec<SessionPause> = dsl::session_pauses
+            .filter(dsl::session_id.eq_any(session_ids))
+            .order(dsl::started_at.asc())
+            .load(conn)?;
+        Ok(pauses)
+    }
+
+    /// Fits the pauses of a session into its `[started_at, finished_at]` range,
+    /// pauses lying entirely outside of it are deleted.
+    pub fn clamp_to_range(
+        conn: &mut SqliteConnection,
+        session_id: i32,
+        started_at: i64,
+        finished_at: i64,
+    ) -> Result<()> {
+        use crate::db::schema::session_pauses::dsl;
+
+        let pauses = dsl::session_pauses.filter(dsl::session_id.eq(session_id));
+        diesel::delete(
+            pauses.filter(
+                dsl::finished_at
+                    .le(started_at)
+                    .or(dsl::started_at.ge(finished_at)),
+            ),
+        )
+        .execute(conn)?;
+        diesel::update(pauses.filter(dsl::started_at.lt(started_at)))
+            .set(dsl::started_at.eq(started_at))
+            .execute(conn)?;
+        diesel::update(pauses.filter(dsl::finished_at.gt(finished_at)))
+            .set(dsl::finished_at.eq(finished_at))
+            .execute(conn)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod session_pause_bmc_tests {
+    use crate::{
+        bmc::{test_helpers::create_dummy_intent, SessionBmc},
+        db::Db,
+        models::CreateSession,
+        prelude::Error,
+        timer::SessionType,
+    };
+
+    use super::*;
+
+    fn create_dummy_session(conn: &mut SqliteConnection) -> i32 {
+        let intent_id = create_dummy_intent(conn);
+        let data = CreateSession {
+            duration: 1500,
+            started_at: 0,
+            intent_id,
+            session_type: SessionType::Focus,
+            planned_duration: None,
+        };
+        SessionBmc::create(conn, &data).unwrap()
+    }
+
+    #[test]
+    fn test_create_session_pause() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let session_id = create_dummy_session(&mut conn);
+
+        let data = CreateSessionPause {
+            session_id,
+            started_at: 300,
+            finished_at: 360,
+        };
+        let id = SessionPauseBmc::create(&mut conn, &data).unwrap();
+
+        assert_eq!(id, 1);
+    }
+
+    #[test]
+    fn test_fail_create_session_pause_non_existent_session_row() {
+        let mut conn = Db::establish_test_connection().unwrap();
+
+        let data = CreateSessionPause {
+            session_id: 1,
+            started_at: 300,
+            finished_at: 360,
+        };
+        let result = SessionPauseBmc::create(&mut conn, &data);
+
+        assert!(matches!(result, Err(Error::DieselError(_))));
+    }
+
+    #[test]
+    fn test_get_list_of_session_pauses() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let session_id = create_dummy_session(&mut conn);
+        let other_session_id = create_dummy_session(&mut conn);
+
+        // Insert out of order to check sorting.
+        for (session_id, started_at) in
+            [(session_id, 600), (session_id, 300), (other_session_id, 0)]
+        {
+            let data = CreateSessionPause {
+                session_id,
+                started_at,
+                finished_at: started_at + 60,
+            };
+            SessionPauseBmc::create(&mut conn, &data).unwrap();
+        }
+        let pauses = SessionPauseBmc::get_list(&mut conn, session_id).unwrap();
+
+        assert_eq!(pauses.len(), 2);
+        assert_eq!(pauses[0].started_at, 300);
+        assert_eq!(pauses[1].started_at, 600);
+    }
+}
@@ -0,0 +1,691 @@
+use std::collections::{BTreeMap, HashSet};
+
+use chrono::{Datelike, NaiveDate};
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Bool};
+use diesel::SqliteConnection;
+use serde::Deserialize;
+use ts_rs::TS;
+
+use crate::day::DayBoundary;
+use crate::models::{
+    GoalProgress, HeatmapDay, HourlyDistribution, SessionDetails, Stats, StatsEntry, StatsTotals,
+    Streaks,
+};
+use crate::prelude::{Minutes, Result};
+
+use super::{RestDayBmc, SessionBmc};
+
+#[derive(TS, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[ts(export, export_to = "../src/bindings/")]
+pub enum StatsGroupBy {
+    Day,
+    /// ISO week, e.g. `2023-W28`.
+    Week,
+    Month,
+    /// Sessions of sub-intents count towards their parents too.
+    Intent,
+    /// Sessions count towards every tag of their intent.
+    Tag,
+}
+
+#[derive(TS, Deserialize, Debug, Clone)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct GetStatsOptions {
+    pub group_by: StatsGroupBy,
+    /// First day of the range, inclusive.
+    #[ts(type = "string | null")]
+    pub from: Option<NaiveDate>,
+    /// Last day of the range, inclusive.
+    #[ts(type = "string | null")]
+    pub to: Option<NaiveDate>,
+    /// Whether to include manually logged sessions, defaults to true.
+    pub include_manual: Option<bool>,
+}
+
+diesel::sql_function!(
+    /// Date period a timestamp falls into, registered per query with the day boundary.
+    fn stats_period(timestamp: BigInt) -> Text;
+);
+
+pub struct StatsBmc {}
+
+impl StatsBmc {
+    /// Aggregates focus sessions, days are resolved using the given day boundary.
+    pub fn get(
+        conn: &mut SqliteConnection,
+        options: &GetStatsOptions,
+        boundary: &DayBoundary,
+    ) -> Result<Stats> {
+        let group_by = options.group_by;
+        let period_boundary = boundary.clone();
+        stats_period::register_impl(conn, move |timestamp: i64| {
+            let day = period_boundary.day_of(timestamp);
+            match group_by {
+                StatsGroupBy::Week => {
+                    let week = day.iso_week();
+                    format!("{}-W{:02}", week.year(), week.week())
+                }
+                StatsGroupBy::Month => day.format("%Y-%m").to_string(),
+                _ => day.format("%Y-%m-%d").to_string(),
+            }
+        })?;
+
+        let started_from = options.from.map_or(i64::MIN, |day| boundary.start_of(day));
+        let started_to = options.to.map_or(i64::MAX, |day| boundary.range_of(day).1);
+        let include_manual = options.include_manual.unwrap_or(true);
+
+        let filter = "WHERE s.session_type = 'Focus'
+            AND s.started_at >= ? AND s.started_at < ?
+            AND (? OR s.is_manual = 0)";
+
+        // Pairs every intent with itself and all of its ancestors,
+        // so sessions of sub-intents count towards their parents.
+        let intent_ancestors = "WITH RECURSIVE intent_ancestors (intent_id, ancestor_id) AS (
+                SELECT id, id FROM intents
+                UNION
+                SELECT a.intent_id, i.parent_id FROM intent_ancestors a
+                INNER JOIN intents i ON i.id = a.ancestor_id
+                WHERE i.parent_id IS NOT NULL
+            )";
+
+        let (with, key, id, color, join) = match group_by {
+            StatsGroupBy::Day | StatsGroupBy::Week | StatsGroupBy::Month => {
+                ("", "stats_period(s.started_at)", "NULL", "NULL", "")
+            }
+            StatsGroupBy::Intent => (
+                intent_ancestors,
+                "i.label",
+                "i.id",
+                "i.color",
+                "INNER JOIN intent_ancestors a ON a.intent_id = s.intent_id
+                INNER JOIN intents i ON i.id = a.ancestor_id",
+            ),
+            StatsGroupBy::Tag => (
+                "",
+                "t.label",
+                "t.id",
+                "NULL",
+                "INNER JOIN intent_tags it ON it.intent_id = s.intent_id
+                INNER JOIN tags t ON t.id = it.tag_id",
+            ),
+        };
+        let query = format!(
+            "{with} SELECT {key} AS key, {id} AS id, {color} AS color,
+                SUM(s.duration) AS total_duration,
+                COUNT(*) AS sessions_count,
+                AVG(s.duration) AS average_duration
+            FROM sessions s {join} {filter}
+            GROUP BY {key}, {id}
+            ORDER BY {key}"
+        );
+        let entries: Vec<StatsEntry> = diesel::sql_query(query)
+            .bind::<BigInt, _>(started_from)
+            .bind::<BigInt, _>(started_to)
+            .bind::<Bool, _>(include_manual)
+            .load(conn)?;
+
+        let query = format!(
+            "SELECT COALESCE(SUM(s.duration), 0) AS total_duration,
+                COUNT(*) AS sessions_count,
+                COALESCE(AVG(s.duration), 0.0) AS average_duration
+            FROM sessions s {filter}"
+        );
+        let totals: StatsTotals = diesel::sql_query(query)
+            .bind::<BigInt, _>(started_from)
+            .bind::<BigInt, _>(started_to)
+            .bind::<Bool, _>(include_manual)
+            .get_result(conn)?;
+
+        Ok(Stats {
+            total_duration: totals.total_duration,
+            sessions_count: totals.sessions_count,
+            average_duration: totals.average_duration,
+            entries,
+        })
+    }
+
+    /// Computes the current and longest streak of focus days up to today.
+    /// Rest days and rest weekdays without focus neither extend nor break a streak,
+    /// neither does today until it is over.
+    pub fn get_streaks(
+        conn: &mut SqliteConnection,
+        boundary: &DayBoundary,
+        rest_weekdays: &[u32],
+    ) -> Result<Streaks> {
+        let options = GetStatsOptions {
+            group_by: StatsGroupBy::Day,
+            from: None,
+            to: None,
+            include_manual: None,
+        };
+        let focus_days: HashSet<NaiveDate> = Self::get(conn, &options, boundary)?
+            .entries
+            .iter()
+            .filter_map(|entry| entry.key.parse().ok())
+            .collect();
+        let rest_days: HashSet<NaiveDate> = RestDayBmc::get_list(conn)?
+            .into_iter()
+            .map(|rest_day| rest_day.day)
+            .collect();
+
+        let today = boundary.today();
+        let mut streaks = Streaks {
+            current: 0,
+            longest: 0,
+        };
+        let Some(mut day) = focus_days.iter().min().copied() else {
+            return Ok(streaks);
+        };
+
+        while day <= today {
+            if focus_days.contains(&day) {
+                streaks.current += 1;
+                streaks.longest = streaks.longest.max(streaks.current);
+            } else {
+                let is_rest_day = rest_days.contains(&day)
+                    || rest_weekdays.contains(&day.weekday().num_days_from_monday());
+                if !is_rest_day && day != today {
+                    streaks.current = 0;
+                }
+            }
+            day = day.succ_opt().unwrap();
+        }
+        Ok(streaks)
+    }
+
+    /// Focused time of every day in the inclusive `[from, to]` range, days without focus included.
+    pub fn get_heatmap(
+        conn: &mut SqliteConnection,
+        from: NaiveDate,
+        to: NaiveDate,
+        boundary: &DayBoundary,
+    ) -> Result<Vec<HeatmapDay>> {
+        let (range_start, range_end) = (boundary.start_of(from), boundary.range_of(to).1);
+        let sessions = SessionBmc::get_overlapping(conn, range_start, range_end)?;
+
+        let mut days: BTreeMap<NaiveDate, f64> = BTreeMap::new();
+        let mut day = from;
+        while day <= to {
+            days.insert(day, 0.0);
+            day = day.succ_opt().unwrap();
+        }
+        split_focus_time(
+            &sessions,
+            (range_start, range_end),
+            |timestamp| boundary.next_day_start(timestamp),
+            |timestamp, seconds| {
+                if let Some(total) = days.get_mut(&boundary.day_of(timestamp)) {
+                    *total += seconds;
+                }
+            },
+        );
+
+        let heatmap = days
+            .into_iter()
+            .map(|(day, duration)| HeatmapDay {
+                day,
+                duration: duration.round() as i64,
+            })
+            .collect();
+        Ok(heatmap)
+    }
+
+    /// Focused time by local weekday and hour within the optional inclusive day range.
+    pub fn get_hourly_distribution(
+        conn: &mut SqliteConnection,
+        from: Option<NaiveDate>,
+        to: Option<NaiveDate>,
+        boundary: &DayBoundary,
+    ) -> Result<HourlyDistribution> {
+        let range_start = from.map_or(i64::MIN, |day| boundary.start_of(day));
+        let range_end = to.map_or(i64::MAX, |day| boundary.range_of(day).1);
+        let sessions = SessionBmc::get_overlapping(conn, range_start, range_end)?;
+
+        let mut hours = vec![vec![0.0; 24]; 7];
+        split_focus_time(
+            &sessions,
+            (range_start, range_end),
+            |timestamp| boundary.next_hour_start(timestamp),
+            |timestamp, seconds| {
+                let (weekday, hour) = boundary.weekday_hour_of(timestamp);
+                hours[weekday as usize][hour as usize] += seconds;
+            },
+        );
+
+        let hours = hours
+            .into_iter()
+            .map(|day| day.into_iter().map(|s| s.round() as i64).collect())
+            .collect();
+        Ok(HourlyDistribution { hours })
+    }
+
+    pub fn get_goal_progress(
+        conn: &mut SqliteConnection,
+        boundary: &DayBoundary,
+        daily_goal: Minutes,
+        weekly_goal: Minutes,
+    ) -> Result<GoalProgress> {
+        let today = boundary.today();
+        let (monday, sunday) = DayBoundary::week_of(today);
+        let mut focused_between = |from: NaiveDate, to: NaiveDate| {
+            let options = GetStatsOptions {
+                group_by: StatsGroupBy::Day,
+                from: Some(from),
+                to: Some(to),
+                include_manual: None,
+            };
+            Self::get(conn, &options, boundary).map(|stats| stats.total_duration)
+        };
+
+        Ok(GoalProgress {
+            daily_focused: focused_between(today, today)?,
+            daily_goal,
+            weekly_focused: focused_between(monday, sunday)?,
+            weekly_goal,
+        })
+    }
+}
+
+/// Spreads the focused time of each session evenly over its wall clock span minus pauses,
+/// so that a session crossing a boundary is split between both sides of it.
+/// Pieces are cut at `range` and at every timestamp returned by `next_boundary`.
+fn split_focus_time(
+    sessions: &[SessionDetails],
+    range: (i64, i64),
+    next_boundary: impl Fn(i64) -> i64,
+    mut add: impl FnMut(i64, f64),
+) {
+    for SessionDetails { session, pauses } in sessions {
+        let mut active = vec![];
+        let mut cursor = session.started_at;
+        for pause in pauses {
+            if pause.started_at > cursor {
+                active.push((cursor, pause.started_at.min(session.finished_at)));
+            }
+            cursor = cursor.max(pause.finished_at);
+        }
+        if cursor < session.finished_at {
+            active.push((cursor, session.finished_at));
+        }
+
+        let active_total: i64 = active.iter().map(|(start, end)| end - start).sum();
+        if active_total <= 0 {
+            continue;
+        }
+        // Recorded duration may differ from the active wall clock time, e.g. when the
+        // session ended while paused.
+        let ratio = session.duration as f64 / active_total as f64;
+
+        for (start, end) in active {
+            let mut timestamp = start.max(range.0);
+            let end = end.min(range.1);
+            while timestamp < end {
+                let piece_end = next_boundary(timestamp).min(end);
+                add(timestamp, (piece_end - timestamp) as f64 * ratio);
+                timestamp = piece_end;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod stats_bmc_tests {
+    use crate::{
+        bmc::{
+            test_helpers::create_dummy_tag, IntentBmc, IntentTagBmc, SessionBmc, SessionPauseBmc,
+        },
+        db::Db,
+        models::{
+            CreateIntent, CreateIntentTag, CreateManualSession, CreateRestDay, CreateSession,
+            CreateSessionPause, SetIntentDetails, UpdateSession,
+        },
+    };
+
+    use crate::timer::SessionType;
+
+    use super::*;
+
+    // 2023-07-10 00:00:00 UTC
+    const DAY: i64 = 1688947200;
+    const HOUR: i64 = 3600;
+
+    fn create_intent(conn: &mut SqliteConnection, label: &str) -> i32 {
+        let data = CreateIntent {
+            label: label.to_string(),
+            parent_id: None,
+        };
+        IntentBmc::create(conn, &data).unwrap()
+    }
+
+    fn log_session(conn: &mut SqliteConnection, intent_id: i32, started_at: i64, minutes: i64) {
+        let data = CreateManualSession {
+            intent_id,
+            started_at,
+            finished_at: started_at + minutes * 60,
+        };
+        SessionBmc::create_manual(conn, &data).unwrap();
+    }
+
+    fn options(group_by: StatsGroupBy) -> GetStatsOptions {
+        GetStatsOptions {
+            group_by,
+            from: None,
+            to: None,
+            include_manual: None,
+        }
+    }
+
+    fn utc(day_start_hour: u32) -> DayBoundary {
+        DayBoundary::new(Some("UTC"), day_start_hour)
+    }
+
+    #[test]
+    fn test_get_stats_by_day() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let intent_id = create_intent(&mut conn, "foo");
+        log_session(&mut conn, intent_id, DAY + 9 * HOUR, 30);
+        log_session(&mut conn, intent_id, DAY + 10 * HOUR, 60);
+        log_session(&mut conn, intent_id, DAY + 33 * HOUR, 45);
+
+        let stats = StatsBmc::get(&mut conn, &options(StatsGroupBy::Day), &utc(0)).unwrap();
+
+        assert_eq!(stats.total_duration, 135 * 60);
+        assert_eq!(stats.sessions_count, 3);
+        assert_eq!(stats.average_duration, 45.0 * 60.0);
+        assert_eq!(stats.entries.len(), 2);
+        assert_eq!(stats.entries[0].key, "2023-07-10");
+        assert_eq!(stats.entries[0].total_duration, 90 * 60);
+        assert_eq!(stats.entries[0].sessions_count, 2);
+        assert_eq!(stats.entries[1].key, "2023-07-11");
+        assert_eq!(stats.entries[1].id, None);
+    }
+
+    #[test]
+    fn test_get_stats_by_day_honors_day_start_hour() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let intent_id = create_intent(&mut conn, "foo");
+        // 01:00 on the next day still counts towards the previous one.
+        log_session(&mut conn, intent_id, DAY + 25 * HOUR, 30);
+
+        let stats = StatsBmc::get(&mut conn, &options(StatsGroupBy::Day), &utc(4)).unwrap();
+
+        assert_eq!(stats.entries.len(), 1);
+        assert_eq!(stats.entries[0].key, "2023-07-10");
+    }
+
+    #[test]
+    fn test_get_stats_by_week_and_month() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let intent_id = create_intent(&mut conn, "foo");
+        log_session(&mut conn, intent_id, DAY, 30);
+        log_session(&mut conn, intent_id, DAY + 7 * 24 * HOUR, 30);
+        log_session(&mut conn, intent_id, DAY + 30 * 24 * HOUR, 30);
+
+        let stats = StatsBmc::get(&mut conn, &options(StatsGroupBy::Week), &utc(0)).unwrap();
+        let keys: Vec<_> = stats.entries.iter().map(|e| e.key.as_str()).collect();
+        assert_eq!(keys, ["2023-W28", "2023-W29", "2023-W32"]);
+
+        let stats = StatsBmc::get(&mut conn, &options(StatsGroupBy::Month), &utc(0)).unwrap();
+        let keys: Vec<_> = stats.entries.iter().map(|e| e.key.as_str()).collect();
+        assert_eq!(keys, ["2023-07", "2023-08"]);
+    }
+
+    #[test]
+    fn test_get_stats_by_intent() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let foo_id = create_intent(&mut conn, "foo");
+        let bar_id = create_intent(&mut conn, "bar");
+        log_session(&mut conn, foo_id, DAY, 30);
+        log_session(&mut conn, bar_id, DAY + HOUR, 20);
+        log_session(&mut conn, bar_id, DAY + 2 * HOUR, 40);
+
+        let stats = StatsBmc::get(&mut conn, &options(StatsGroupBy::Intent), &utc(0)).unwrap();
+
+        assert_eq!(stats.entries.len(), 2);
+        assert_eq!(stats.entries[0].key, "bar");
+        assert_eq!(stats.entries[0].id, Some(bar_id));
+        assert_eq!(stats.entries[0].total_duration, 60 * 60);
+        assert_eq!(stats.entries[0].average_duration, 30.0 * 60.0);
+        assert_eq!(stats.entries[1].key, "foo");
+    }
+
+    #[test]
+    fn test_get_stats_by_intent_rolls_up_children() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let client_id = create_intent(&mut conn, "client");
+        let project_id = create_intent(&mut conn, "project");
+        let workstream_id = create_intent(&mut conn, "workstream");
+        IntentBmc::set_parent(&mut conn, project_id, Some(client_id)).unwrap();
+        IntentBmc::set_parent(&mut conn, workstream_id, Some(project_id)).unwrap();
+        let details = SetIntentDetails {
+            color: Some("#ff8800".to_string()),
+            ..Default::default()
+        };
+        IntentBmc::set_details(&mut conn, client_id, &details).unwrap();
+        log_session(&mut conn, client_id, DAY, 10);
+        log_session(&mut conn, project_id, DAY + HOUR, 20);
+        log_session(&mut conn, workstream_id, DAY + 2 * HOUR, 30);
+
+        let stats = StatsBmc::get(&mut conn, &options(StatsGroupBy::Intent), &utc(0)).unwrap();
+
+        let totals: Vec<_> = stats
+            .entries
+            .iter()
+            .map(|entry| (entry.key.as_str(), entry.total_duration / 60))
+            .collect();
+        assert_eq!(
+            totals,
+            vec![("client", 60), ("project", 50), ("workstream", 30)]
+        );
+        assert_eq!(stats.entries[0].sessions_count, 3);
+        assert_eq!(stats.entries[0].color.as_deref(), Some("#ff8800"));
+        assert_eq!(stats.entries[1].color, None);
+        // Totals still count every session once.
+        assert_eq!(stats.total_duration, 60 * 60);
+    }
+
+    #[test]
+    fn test_get_stats_by_tag() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let intent_id = create_intent(&mut conn, "foo");
+        let untagged_id = create_intent(&mut conn, "bar");
+        let tag_id = create_dummy_tag(&mut conn);
+        let data = CreateIntentTag { intent_id, tag_id };
+        IntentTagBmc::create(&mut conn, &data).unwrap();
+        log_session(&mut conn, intent_id, DAY, 30);
+        log_session(&mut conn, untagged_id, DAY + HOUR, 30);
+
+        let stats = StatsBmc::get(&mut conn, &options(StatsGroupBy::Tag), &utc(0)).unwrap();
+
+        assert_eq!(stats.total_duration, 60 * 60);
+        assert_eq!(stats.entries.len(), 1);
+        assert_eq!(stats.entries[0].id, Some(tag_id));
+        assert_eq!(stats.entries[0].total_duration, 30 * 60);
+    }
+
+    #[test]
+    fn test_get_stats_within_date_range() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let intent_id = create_intent(&mut conn, "foo");
+        for day in 0..5 {
+            log_session(&mut conn, intent_id, DAY + day * 24 * HOUR, 30);
+        }
+
+        let options = GetStatsOptions {
+            from: NaiveDate::from_ymd_opt(2023, 7, 11),
+            to: NaiveDate::from_ymd_opt(2023, 7, 13),
+            ..options(StatsGroupBy::Day)
+        };
+        let stats = StatsBmc::get(&mut conn, &options, &utc(0)).unwrap();
+
+        assert_eq!(stats.sessions_count, 3);
+        assert_eq!(stats.entries[0].key, "2023-07-11");
+        assert_eq!(stats.entries[2].key, "2023-07-13");
+    }
+
+    #[test]
+    fn test_get_stats_without_manual_sessions() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let intent_id = create_intent(&mut conn, "foo");
+        log_session(&mut conn, intent_id, DAY, 30);
+
+        let options = GetStatsOptions {
+            include_manual: Some(false),
+            ..options(StatsGroupBy::Day)
+        };
+        let stats = StatsBmc::get(&mut conn, &options, &utc(0)).unwrap();
+
+        assert_eq!(stats.total_duration, 0);
+        assert_eq!(stats.sessions_count, 0);
+        assert!(stats.entries.is_empty());
+    }
+
+    fn days_ago(boundary: &DayBoundary, days: i64) -> i64 {
+        let day = boundary.today() - chrono::Duration::days(days);
+        boundary.start_of(day) + HOUR
+    }
+
+    #[test]
+    fn test_get_streaks() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let intent_id = create_intent(&mut conn, "foo");
+        let boundary = utc(0);
+        // Longest streak of 3 days, then a gap and the current streak of 2 days.
+        for days in [9, 8, 7, 2, 1] {
+            log_session(&mut conn, intent_id, days_ago(&boundary, days), 30);
+        }
+
+        let streaks = StatsBmc::get_streaks(&mut conn, &boundary, &[]).unwrap();
+
+        assert_eq!(
+            streaks,
+            Streaks {
+                current: 2,
+                longest: 3
+            }
+        );
+    }
+
+    #[test]
+    fn test_get_streaks_skips_rest_days() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let intent_id = create_intent(&mut conn, "foo");
+        let boundary = utc(0);
+        for days in [4, 3, 1, 0] {
+            log_session(&mut conn, intent_id, days_ago(&boundary, days), 30);
+        }
+        let data = CreateRestDay {
+            day: boundary.today() - chrono::Duration::days(2),
+        };
+        RestDayBmc::create(&mut conn, &data).unwrap();
+
+        let streaks = StatsBmc::get_streaks(&mut conn, &boundary, &[]).unwrap();
+
+        assert_eq!(
+            streaks,
+            Streaks {
+                current: 4,
+                longest: 4
+            }
+        );
+    }
+
+    #[test]
+    fn test_get_streaks_skips_rest_weekdays() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let intent_id = create_intent(&mut conn, "foo");
+        let boundary = utc(0);
+        log_session(&mut conn, intent_id, days_ago(&boundary, 2), 30);
+        let rest_weekday = (boundary.today() - chrono::Duration::days(1))
+            .weekday()
+            .num_days_from_monday();
+
+        let streaks = StatsBmc::get_streaks(&mut conn, &boundary, &[rest_weekday]).unwrap();
+        assert_eq!(streaks.current, 1);
+
+        let streaks = StatsBmc::get_streaks(&mut conn, &boundary, &[]).unwrap();
+        assert_eq!(streaks.current, 0);
+        assert_eq!(streaks.longest, 1);
+    }
+
+    #[test]
+    fn test_get_goal_progress() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let intent_id = create_intent(&mut conn, "foo");
+        let boundary = utc(0);
+        log_session(&mut conn, intent_id, days_ago(&boundary, 0), 30);
+
+        let progress = StatsBmc::get_goal_progress(&mut conn, &boundary, 30, 600).unwrap();
+
+        assert_eq!(progress.daily_focused, 30 * 60);
+        assert!(progress.weekly_focused >= 30 * 60);
+        assert!(progress.is_daily_goal_reached());
+    }
+
+    #[test]
+    fn test_get_heatmap_splits_sessions_across_days() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let intent_id = create_intent(&mut conn, "foo");
+        // 23:30 to 00:30, half an hour on each day.
+        log_session(&mut conn, intent_id, DAY + 23 * HOUR + 1800, 60);
+
+        let from = NaiveDate::from_ymd_opt(2023, 7, 9).unwrap();
+        let to = NaiveDate::from_ymd_opt(2023, 7, 12).unwrap();
+        let heatmap = StatsBmc::get_heatmap(&mut conn, from, to, &utc(0)).unwrap();
+        let durations: Vec<_> = heatmap.iter().map(|day| day.duration).collect();
+
+        assert_eq!(heatmap.len(), 4);
+        assert_eq!(heatmap[0].day, from);
+        assert_eq!(durations, [0, 1800, 1800, 0]);
+    }
+
+    #[test]
+    fn test_get_heatmap_skips_pauses() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let intent_id = create_intent(&mut conn, "foo");
+        // 23:00 to 01:00 with the whole hour before midnight paused.
+        let data = CreateSession {
+            duration: 3600,
+            started_at: DAY + 23 * HOUR,
+            intent_id,
+            session_type: SessionType::Focus,
+            planned_duration: None,
+        };
+        let session_id = SessionBmc::create(&mut conn, &data).unwrap();
+        let data = UpdateSession {
+            finished_at: Some(DAY + 25 * HOUR),
+            ..Default::default()
+        };
+        SessionBmc::update(&mut conn, session_id, &data).unwrap();
+        let data = CreateSessionPause {
+            session_id,
+            started_at: DAY + 23 * HOUR,
+            finished_at: DAY + 24 * HOUR,
+        };
+        SessionPauseBmc::create(&mut conn, &data).unwrap();
+
+        let day = NaiveDate::from_ymd_opt(2023, 7, 10).unwrap();
+        let heatmap =
+            StatsBmc::get_heatmap(&mut conn, day, day.succ_opt().unwrap(), &utc(0)).unwrap();
+
+        assert_eq!(heatmap[0].duration, 0);
+        assert_eq!(heatmap[1].duration, 3600);
+    }
+
+    #[test]
+    fn test_get_hourly_distribution() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let intent_id = create_intent(&mut conn, "foo");
+        // Monday 09:30 to 10:15.
+        log_session(&mut conn, intent_id, DAY + 9 * HOUR + 1800, 45);
+
+        let distribution =
+            StatsBmc::get_hourly_distribution(&mut conn, None, None, &utc(0)).unwrap();
+
+        assert_eq!(distribution.hours.len(), 7);
+        assert_eq!(distribution.hours[0][9], 1800);
+        assert_eq!(distribution.hours[0][10], 900);
+        assert_eq!(distribution.hours.iter().flatten().sum::<i64>(), 2700);
+    }
+}
@@ -1,13 +1,38 @@
+use std::collections::HashMap;
+
+use chrono::{Duration, NaiveDate};
 use diesel::prelude::*;
-use diesel::sql_types::Timestamp;
 use diesel::SqliteConnection;
 use serde::Deserialize;
 use ts_rs::TS;
 
-use crate::models::{CreateTask, Task, UpdateTask};
-use crate::prelude::Result;
+use crate::models::{
+    CreateTask, CreateTaskSession, EstimationReport, Task, TaskEstimate, TaskProgress,
+    TaskRecurrence, UpdateTask,
+};
+use crate::prelude::{Error, Minutes, Result};
+
+use super::{BaseBmc, BmcError};
+
+#[derive(TS, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[ts(export, export_to = "../src/bindings/")]
+pub enum TaskSortBy {
+    /// Order set by the user, the default.
+    Position,
+    /// Highest priority first.
+    Priority,
+    /// Earliest due date first, tasks without one come last.
+    DueDate,
+    CreatedAt,
+}
 
-use super::BaseBmc;
+#[derive(TS, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[ts(export, export_to = "../src/bindings/")]
+pub enum TaskDueFilter {
+    /// Uncompleted tasks due before today.
+    Overdue,
+    DueToday,
+}
 
 #[derive(TS, Deserialize)]
 #[ts(export, export_to = "../src/bindings/")]
@@ -16,34 +41,228 @@ pub struct GetTasksOptions {
     pub offset: Option<i32>,
     pub limit: Option<i32>,
     pub completed: Option<bool>,
+    pub sort_by: Option<TaskSortBy>,
+    pub due: Option<TaskDueFilter>,
+    /// Day the due filter is relative to, required along with it.
+    #[ts(type = "string | null")]
+    pub today: Option<NaiveDate>,
+}
+
+/// Tasks affected by completing a task.
+#[derive(Debug, Default, PartialEq)]
+pub struct CompletedTasks {
+    /// Tasks that got completed, parents completed along with their last subtask included.
+    pub completed_ids: Vec<i32>,
+    /// Next instances generated for completed recurring tasks, along with their subtasks.
+    pub created_ids: Vec<i32>,
+}
+
+impl CompletedTasks {
+    fn extend(&mut self, other: CompletedTasks) {
+        self.completed_ids.extend(other.completed_ids);
+        self.created_ids.extend(other.created_ids);
+    }
 }
 
 pub struct TaskBmc {}
 
 impl TaskBmc {
+    /// Subtasks must belong to the same intent as their parent task.
     pub fn create(conn: &mut SqliteConnection, data: &CreateTask) -> Result<i32> {
         use crate::db::schema::tasks;
 
+        if let Some(parent_task_id) = data.parent_task_id {
+            // Makes sure the parent exists, there's no foreign key on `parent_task_id`.
+            if Self::get(conn, parent_task_id)?.intent_id != data.intent_id {
+                return Err(BmcError::InvalidParentTask.into());
+            }
+        }
+        let created_at = chrono::Utc::now().timestamp();
+        let position = Self::next_position(conn, data.intent_id, data.parent_task_id)?;
         diesel::insert_into(tasks::table)
-            .values(data)
+            .values((
+                data,
+                tasks::created_at.eq(created_at),
+                tasks::position.eq(position),
+            ))
             .execute(conn)?;
         BaseBmc::get_last_insert_id(conn)
     }
 
+    /// Changing the intent moves the task as with `move_to_intent`.
     pub fn update(conn: &mut SqliteConnection, id: i32, data: &UpdateTask) -> Result<i32> {
         use crate::db::schema::tasks::dsl;
 
-        diesel::update(dsl::tasks.find(id))
-            .set(data)
-            .execute(conn)?;
-        Ok(id)
+        conn.transaction::<_, Error, _>(|conn| {
+            if let Some(intent_id) = data.intent_id {
+                Self::move_to_intent(conn, id, intent_id)?;
+            }
+            diesel::update(dsl::tasks.find(id))
+                .set(data)
+                .execute(conn)?;
+            Ok(id)
+        })
     }
 
-    pub fn delete(conn: &mut SqliteConnection, id: i32) -> Result<i32> {
+    /// Moves a task along with all of its subtasks to another intent. A subtask moved on its
+    /// own becomes a top level task, as its parent stays behind. The task is placed last.
+    pub fn move_to_intent(conn: &mut SqliteConnection, id: i32, intent_id: i32) -> Result<i32> {
         use crate::db::schema::tasks::dsl;
 
-        diesel::delete(dsl::tasks.filter(dsl::id.eq(id))).execute(conn)?;
-        Ok(id)
+        conn.transaction::<_, Error, _>(|conn| {
+            let task = Self::get(conn, id)?;
+            if task.intent_id == intent_id {
+                return Ok(id);
+            }
+            let position = Self::next_position(conn, intent_id, None)?;
+            diesel::update(dsl::tasks.find(id))
+                .set((
+                    dsl::intent_id.eq(intent_id),
+                    dsl::parent_task_id.eq(None::<i32>),
+                    dsl::position.eq(position),
+                ))
+                .execute(conn)?;
+            let descendant_ids = Self::get_descendant_ids(conn, id)?;
+            diesel::update(dsl::tasks.filter(dsl::id.eq_any(descendant_ids)))
+                .set(dsl::intent_id.eq(intent_id))
+                .execute(conn)?;
+            Ok(id)
+        })
+    }
+
+    /// Copies a task along with all of its subtasks to the given intent, the copies start
+    /// out uncompleted. Returns id of the copied task.
+    pub fn copy_to_intent(conn: &mut SqliteConnection, id: i32, intent_id: i32) -> Result<i32> {
+        conn.transaction::<_, Error, _>(|conn| Self::copy_tree(conn, id, intent_id, None))
+    }
+
+    /// Moves the tasks to the given intent in a single transaction. Subtasks whose parent
+    /// is moved as well go along with it, whatever the order of `ids`. Returns ids of every
+    /// moved task, subtasks included.
+    pub fn move_many_to_intent(
+        conn: &mut SqliteConnection,
+        ids: &[i32],
+        intent_id: i32,
+    ) -> Result<Vec<i32>> {
+        conn.transaction::<_, Error, _>(|conn| {
+            let mut moved_ids = vec![];
+            for id in Self::without_nested(conn, ids)? {
+                if Self::get(conn, id)?.intent_id == intent_id {
+                    continue;
+                }
+                Self::move_to_intent(conn, id, intent_id)?;
+                moved_ids.push(id);
+                moved_ids.extend(Self::get_descendant_ids(conn, id)?);
+            }
+            Ok(moved_ids)
+        })
+    }
+
+    /// Completes the tasks in a single transaction, as `complete` does for each of them.
+    pub fn complete_many(
+        conn: &mut SqliteConnection,
+        ids: &[i32],
+        today: NaiveDate,
+    ) -> Result<CompletedTasks> {
+        conn.transaction::<_, Error, _>(|conn| {
+            let mut completed = CompletedTasks::default();
+            for id in ids {
+                completed.extend(Self::complete(conn, *id, today)?);
+            }
+            Ok(completed)
+        })
+    }
+
+    /// Reopens the tasks in a single transaction, returns ids of every task that got
+    /// reopened, including their parents.
+    pub fn uncomplete_many(conn: &mut SqliteConnection, ids: &[i32]) -> Result<Vec<i32>> {
+        conn.transaction::<_, Error, _>(|conn| {
+            let mut reopened = vec![];
+            for id in ids {
+                reopened.extend(Self::uncomplete(conn, *id)?);
+            }
+            Ok(reopened)
+        })
+    }
+
+    /// Deletes the tasks along with their subtasks in a single transaction, returns ids of
+    /// every deleted task, subtasks included.
+    pub fn delete_many(conn: &mut SqliteConnection, ids: &[i32]) -> Result<Vec<i32>> {
+        conn.transaction::<_, Error, _>(|conn| {
+            let mut deleted_ids = vec![];
+            for id in Self::without_nested(conn, ids)? {
+                deleted_ids.extend(Self::delete(conn, id)?);
+            }
+            Ok(deleted_ids)
+        })
+    }
+
+    /// Deletes tasks completed more than `days` days ago, optionally only those of the given
+    /// intent. Tasks with open subtasks are kept, so are instances of recurring series, they
+    /// are the series' history. Returns ids of the deleted tasks, subtasks included.
+    pub fn clear_completed(
+        conn: &mut SqliteConnection,
+        days: i64,
+        intent_id: Option<i32>,
+    ) -> Result<Vec<i32>> {
+        use crate::db::schema::tasks::dsl;
+
+        if days < 0 {
+            return Err(BmcError::InvalidDuration.into());
+        }
+        let finished_before = chrono::Utc::now().timestamp() - days * 24 * 60 * 60;
+        conn.transaction::<_, Error, _>(|conn| {
+            let mut query = dsl::tasks
+                .filter(dsl::completed.eq(true))
+                .filter(dsl::finished_at.lt(finished_before))
+                .filter(dsl::series_id.is_null())
+                .select(dsl::id)
+                .into_boxed();
+            if let Some(intent_id) = intent_id {
+                query = query.filter(dsl::intent_id.eq(intent_id));
+            }
+            let candidate_ids: Vec<i32> = query.load(conn)?;
+
+            let mut ids = vec![];
+            for id in candidate_ids {
+                let descendant_ids = Self::get_descendant_ids(conn, id)?;
+                let is_kept: bool = diesel::select(diesel::dsl::exists(
+                    dsl::tasks
+                        .filter(dsl::id.eq_any(descendant_ids))
+                        .filter(dsl::completed.eq(false).or(dsl::series_id.is_not_null())),
+                ))
+                .get_result(conn)?;
+                if !is_kept {
+                    ids.push(id);
+                }
+            }
+            Self::delete_many(conn, &ids)
+        })
+    }
+
+    /// Drops the ids of tasks which are subtasks of another task in `ids`, at any depth.
+    fn without_nested(conn: &mut SqliteConnection, ids: &[i32]) -> Result<Vec<i32>> {
+        let mut roots = vec![];
+        for id in ids {
+            let ancestor_ids = Self::get_ancestor_ids(conn, *id)?;
+            if !roots.contains(id) && !ancestor_ids.iter().any(|id| ids.contains(id)) {
+                roots.push(*id);
+            }
+        }
+        Ok(roots)
+    }
+
+    /// Subtasks are deleted along with their parent, returns ids of every deleted task,
+    /// the parent first.
+    pub fn delete(conn: &mut SqliteConnection, id: i32) -> Result<Vec<i32>> {
+        use crate::db::schema::tasks::dsl;
+
+        conn.transaction::<_, Error, _>(|conn| {
+            let mut ids = vec![id];
+            ids.extend(Self::get_descendant_ids(conn, id)?);
+            diesel::delete(dsl::tasks.filter(dsl::id.eq_any(&ids))).execute(conn)?;
+            Ok(ids)
+        })
     }
 
     pub fn get(conn: &mut SqliteConnection, id: i32) -> Result<Task> {
@@ -60,6 +279,7 @@ impl TaskBmc {
         use crate::db::schema::tasks::dsl;
 
         let mut query = dsl::tasks.into_boxed();
+        let mut sort_by = TaskSortBy::Position;
 
         if let Some(options) = options {
             if let Some(intent_id) = options.intent_id {
@@ -68,6 +288,18 @@ impl TaskBmc {
             if let Some(completed) = options.completed {
                 query = query.filter(dsl::completed.eq(completed));
             }
+            if let Some(due) = options.due {
+                let today = options.today.ok_or(BmcError::MissingToday)?;
+                query = match due {
+                    TaskDueFilter::Overdue => query
+                        .filter(dsl::due_date.lt(today))
+                        .filter(dsl::completed.eq(false)),
+                    TaskDueFilter::DueToday => query.filter(dsl::due_date.eq(today)),
+                };
+            }
+            if let Some(options_sort_by) = options.sort_by {
+                sort_by = options_sort_by;
+            }
             if let Some(offset) = options.offset {
                 query = query.offset(offset as i64);
             }
@@ -76,43 +308,551 @@ impl TaskBmc {
             }
         }
 
-        let tasks: Vec<Task> = query.load(conn)?;
+        query = match sort_by {
+            TaskSortBy::Position => query.order(dsl::position.asc()),
+            TaskSortBy::Priority => query
+                .order(dsl::priority.desc())
+                .then_order_by(dsl::position.asc()),
+            TaskSortBy::DueDate => query
+                .order(dsl::due_date.is_null())
+                .then_order_by(dsl::due_date.asc())
+                .then_order_by(dsl::position.asc()),
+            TaskSortBy::CreatedAt => query.order(dsl::created_at.asc()),
+        };
+        let tasks: Vec<Task> = query.then_order_by(dsl::id.asc()).load(conn)?;
         Ok(tasks)
     }
 
-    pub fn complete(conn: &mut SqliteConnection, id: i32) -> Result<i32> {
-        let now = chrono::Utc::now().naive_utc();
-        let query = "UPDATE tasks SET completed = ?, finished_at = ? WHERE id = ?";
+    /// Moves a task to the given position among the tasks sharing its intent and parent task,
+    /// positions past the end move it last.
+    pub fn move_to(conn: &mut SqliteConnection, id: i32, position: i32) -> Result<i32> {
+        use crate::db::schema::tasks::dsl;
+
+        conn.transaction::<_, Error, _>(|conn| {
+            let task = Self::get(conn, id)?;
+            let mut siblings: Vec<i32> = dsl::tasks
+                .filter(dsl::intent_id.eq(task.intent_id))
+                .filter(dsl::parent_task_id.is(task.parent_task_id))
+                .filter(dsl::id.ne(id))
+                .order((dsl::position.asc(), dsl::id.asc()))
+                .select(dsl::id)
+                .load(conn)?;
+            let index = (position.max(0) as usize).min(siblings.len());
+            siblings.insert(index, id);
+
+            for (position, sibling_id) in siblings.into_iter().enumerate() {
+                diesel::update(dsl::tasks.find(sibling_id))
+                    .set(dsl::position.eq(position as i32))
+                    .execute(conn)?;
+            }
+            Ok(id)
+        })
+    }
+
+    /// `None` removes the due date.
+    pub fn set_due_date(
+        conn: &mut SqliteConnection,
+        id: i32,
+        due_date: Option<NaiveDate>,
+    ) -> Result<i32> {
+        use crate::db::schema::tasks::dsl;
 
-        diesel::sql_query(query)
-            .bind::<diesel::sql_types::Bool, _>(true)
-            .bind::<diesel::sql_types::Nullable<Timestamp>, _>(now)
-            .bind::<diesel::sql_types::Integer, _>(id)
+        diesel::update(dsl::tasks.find(id))
+            .set(dsl::due_date.eq(due_date))
             .execute(conn)?;
         Ok(id)
     }
 
-    pub fn uncomplete(conn: &mut SqliteConnection, id: i32) -> Result<i32> {
-        let query = "UPDATE tasks SET completed = ?, finished_at = ? WHERE id = ?";
+    /// `None` stops the task from recurring, its past instances are kept.
+    pub fn set_recurrence(
+        conn: &mut SqliteConnection,
+        id: i32,
+        recurrence: Option<TaskRecurrence>,
+    ) -> Result<i32> {
+        use crate::db::schema::tasks::dsl;
+
+        if recurrence
+            .as_ref()
+            .map_or(false, |recurrence| !recurrence.is_valid())
+        {
+            return Err(BmcError::InvalidRecurrence.into());
+        }
+        diesel::update(dsl::tasks.find(id))
+            .set(dsl::recurrence.eq(recurrence))
+            .execute(conn)?;
+        Ok(id)
+    }
+
+    /// Generates the next instance of every open recurring task whose due date has passed,
+    /// due on the first day of the rule from `today` on. The missed instances are left open.
+    /// Returns ids of the generated tasks.
+    pub fn roll_over(conn: &mut SqliteConnection, today: NaiveDate) -> Result<Vec<i32>> {
+        use crate::db::schema::tasks::dsl;
+
+        conn.transaction::<_, Error, _>(|conn| {
+            let tasks: Vec<Task> = dsl::tasks
+                .filter(dsl::recurrence.is_not_null())
+                .filter(dsl::completed.eq(false))
+                .filter(dsl::due_date.lt(today))
+                .order(dsl::id.asc())
+                .load(conn)?;
+            let mut created_ids = vec![];
+            for task in &tasks {
+                let recurrence = task.recurrence.as_ref().unwrap();
+                let due_date = recurrence.next_after(task.due_date.unwrap(), today);
+                created_ids.extend(Self::create_next_instance(conn, task, due_date)?);
+            }
+            Ok(created_ids)
+        })
+    }
+
+    /// Completed instances of the recurring series the task belongs to, latest first.
+    pub fn get_history(conn: &mut SqliteConnection, id: i32) -> Result<Vec<Task>> {
+        use crate::db::schema::tasks::dsl;
+
+        let Some(series_id) = Self::get(conn, id)?.series_id else {
+            return Ok(vec![]);
+        };
+        let tasks: Vec<Task> = dsl::tasks
+            .filter(dsl::series_id.eq(series_id))
+            .filter(dsl::completed.eq(true))
+            .order((dsl::finished_at.desc(), dsl::id.desc()))
+            .load(conn)?;
+        Ok(tasks)
+    }
+
+    /// Completes the parent tasks as well once all of their subtasks are completed.
+    /// Completing a recurring task generates its next instance, due after the current one
+    /// but never on `today` or earlier.
+    pub fn complete(
+        conn: &mut SqliteConnection,
+        id: i32,
+        today: NaiveDate,
+    ) -> Result<CompletedTasks> {
+        conn.transaction::<_, Error, _>(|conn| {
+            let mut completed = Self::complete_open(conn, &[id], today)?;
+            completed.extend(Self::complete_finished_parents(conn, id, today)?);
+            Ok(completed)
+        })
+    }
+
+    /// Completes a task along with all of its subtasks. Subtasks which were completed
+    /// before are left untouched.
+    pub fn complete_with_subtasks(
+        conn: &mut SqliteConnection,
+        id: i32,
+        today: NaiveDate,
+    ) -> Result<CompletedTasks> {
+        conn.transaction::<_, Error, _>(|conn| {
+            let mut ids = vec![id];
+            ids.extend(Self::get_descendant_ids(conn, id)?);
+            let mut completed = Self::complete_open(conn, &ids, today)?;
+            completed.extend(Self::complete_finished_parents(conn, id, today)?);
+            Ok(completed)
+        })
+    }
+
+    /// Reopens the completed parent tasks as well, so that reopening a subtask never leaves
+    /// it open under a completed parent. A parent may still be completed on its own while
+    /// some of its subtasks are open, these are left untouched. Returns ids of every task
+    /// that got reopened.
+    pub fn uncomplete(conn: &mut SqliteConnection, id: i32) -> Result<Vec<i32>> {
+        use crate::db::schema::tasks::dsl;
+
+        conn.transaction::<_, Error, _>(|conn| {
+            let mut ids = Self::get_ancestor_ids(conn, id)?;
+            ids.push(id);
+            let reopened: Vec<i32> = dsl::tasks
+                .filter(dsl::id.eq_any(&ids))
+                .filter(dsl::completed.eq(true))
+                .select(dsl::id)
+                .load(conn)?;
+            Self::set_completed(conn, &ids, false)?;
+            Ok(reopened)
+        })
+    }
+
+    /// Ids of all subtasks of the given task, at any depth.
+    pub fn get_descendant_ids(conn: &mut SqliteConnection, id: i32) -> Result<Vec<i32>> {
+        use crate::db::schema::tasks::dsl;
+
+        let mut descendants = vec![];
+        let mut parents = vec![id];
+        while let Some(parent_id) = parents.pop() {
+            let children: Vec<i32> = dsl::tasks
+                .filter(dsl::parent_task_id.eq(parent_id))
+                .select(dsl::id)
+                .load(conn)?;
+            parents.extend(&children);
+            descendants.extend(children);
+        }
+        Ok(descendants)
+    }
+
+    /// Ids of the tasks the given one is a subtask of, closest parent first.
+    pub fn get_ancestor_ids(conn: &mut SqliteConnection, id: i32) -> Result<Vec<i32>> {
+        let mut ancestors = vec![];
+        let mut parent_id = Self::get(conn, id)?.parent_task_id;
+        while let Some(current_id) = parent_id {
+            let Ok(parent) = Self::get(conn, current_id) else {
+                break;
+            };
+            ancestors.push(current_id);
+            parent_id = parent.parent_task_id;
+        }
+        Ok(ancestors)
+    }
+
+    /// Subtask progress of every task of the intent which has subtasks.
+    pub fn get_progress(conn: &mut SqliteConnection, intent_id: i32) -> Result<Vec<TaskProgress>> {
+        use crate::db::schema::tasks::dsl;
+
+        let tasks: Vec<Task> = dsl::tasks
+            .filter(dsl::intent_id.eq(intent_id))
+            .order((dsl::position.asc(), dsl::id.asc()))
+            .load(conn)?;
+
+        let mut children: HashMap<i32, Vec<&Task>> = HashMap::new();
+        for task in &tasks {
+            if let Some(parent_task_id) = task.parent_task_id {
+                children.entry(parent_task_id).or_default().push(task);
+            }
+        }
+
+        let progress = tasks
+            .iter()
+            .filter(|task| children.contains_key(&task.id))
+            .map(|task| {
+                let mut progress = TaskProgress {
+                    task_id: task.id,
+                    completed: 0,
+                    total: 0,
+                };
+                let mut stack = children[&task.id].clone();
+                while let Some(subtask) = stack.pop() {
+                    progress.total += 1;
+                    progress.completed += subtask.completed as i32;
+                    stack.extend(children.get(&subtask.id).into_iter().flatten());
+                }
+                progress
+            })
+            .collect();
+        Ok(progress)
+    }
+
+    /// `None` removes the estimate.
+    pub fn set_estimate(
+        conn: &mut SqliteConnection,
+        id: i32,
+        estimated_pomodoros: Option<i32>,
+    ) -> Result<i32> {
+        use crate::db::schema::tasks::dsl;
 
-        diesel::sql_query(query)
-            .bind::<diesel::sql_types::Bool, _>(false)
-            .bind::<diesel::sql_types::Nullable<Timestamp>, Option<chrono::NaiveDateTime>>(None)
-            .bind::<diesel::sql_types::Integer, _>(id)
+        if estimated_pomodoros.map_or(false, |pomodoros| pomodoros <= 0) {
+            return Err(BmcError::InvalidEstimate.into());
+        }
+        diesel::update(dsl::tasks.find(id))
+            .set(dsl::estimated_pomodoros.eq(estimated_pomodoros))
             .execute(conn)?;
         Ok(id)
     }
+
+    /// Attributes a focus session to the task, linking the same session twice is a no-op.
+    pub fn link_session(conn: &mut SqliteConnection, id: i32, session_id: i32) -> Result<()> {
+        use crate::db::schema::task_sessions;
+
+        let data = CreateTaskSession {
+            task_id: id,
+            session_id,
+        };
+        diesel::insert_or_ignore_into(task_sessions::table)
+            .values(&data)
+            .execute(conn)?;
+        Ok(())
+    }
+
+    /// `focus_duration` is the length of a pomodoro the estimate is converted to minutes with.
+    pub fn get_estimate(
+        conn: &mut SqliteConnection,
+        id: i32,
+        focus_duration: Minutes,
+    ) -> Result<TaskEstimate> {
+        let task = Self::get(conn, id)?;
+        let actuals = Self::get_actuals(conn, &[id])?;
+        Ok(Self::to_estimate(&task, &actuals, focus_duration))
+    }
+
+    /// Compares estimates with the actual focus sessions of every completed task which
+    /// was estimated, optionally only those of the given intent.
+    pub fn get_estimation_report(
+        conn: &mut SqliteConnection,
+        intent_id: Option<i32>,
+        focus_duration: Minutes,
+    ) -> Result<EstimationReport> {
+        use crate::db::schema::tasks::dsl;
+
+        let mut query = dsl::tasks
+            .filter(dsl::completed.eq(true))
+            .filter(dsl::estimated_pomodoros.is_not_null())
+            .into_boxed();
+        if let Some(intent_id) = intent_id {
+            query = query.filter(dsl::intent_id.eq(intent_id));
+        }
+        let tasks: Vec<Task> = query
+            .order((dsl::finished_at.asc(), dsl::id.asc()))
+            .load(conn)?;
+        let ids: Vec<i32> = tasks.iter().map(|task| task.id).collect();
+        let actuals = Self::get_actuals(conn, &ids)?;
+
+        let mut report = EstimationReport {
+            ratio: None,
+            on_estimate: 0,
+            underestimated: 0,
+            overestimated: 0,
+            tasks: vec![],
+        };
+        let (mut estimated, mut actual) = (0, 0);
+        for task in &tasks {
+            let estimate = Self::to_estimate(task, &actuals, focus_duration);
+            let estimated_pomodoros = estimate.estimated_pomodoros.unwrap_or_default();
+            match estimate.actual_pomodoros.cmp(&estimated_pomodoros) {
+                std::cmp::Ordering::Equal => report.on_estimate += 1,
+                std::cmp::Ordering::Greater => report.underestimated += 1,
+                std::cmp::Ordering::Less => report.overestimated += 1,
+            }
+            estimated += estimated_pomodoros;
+            actual += estimate.actual_pomodoros;
+            report.tasks.push(estimate);
+        }
+        if estimated > 0 {
+            report.ratio = Some(actual as f64 / estimated as f64);
+        }
+        Ok(report)
+    }
+
+    /// Number of linked sessions and their total duration in seconds, by task id.
+    fn get_actuals(conn: &mut SqliteConnection, ids: &[i32]) -> Result<HashMap<i32, (i64, i64)>> {
+        use crate::db::schema::{sessions, task_sessions};
+
+        let rows: Vec<(i32, i64, Option<i64>)> = task_sessions::table
+            .inner_join(sessions::table)
+            .filter(task_sessions::task_id.eq_any(ids))
+            .group_by(task_sessions::task_id)
+            .select((
+                task_sessions::task_id,
+                diesel::dsl::count(sessions::id),
+                diesel::dsl::sum(sessions::duration),
+            ))
+            .load(conn)?;
+        Ok(rows
+            .into_iter()
+            .map(|(task_id, count, duration)| (task_id, (count, duration.unwrap_or_default())))
+            .collect())
+    }
+
+    fn to_estimate(
+        task: &Task,
+        actuals: &HashMap<i32, (i64, i64)>,
+        focus_duration: Minutes,
+    ) -> TaskEstimate {
+        let (count, duration) = actuals.get(&task.id).copied().unwrap_or_default();
+        TaskEstimate {
+            task_id: task.id,
+            estimated_pomodoros: task.estimated_pomodoros,
+            actual_pomodoros: count as i32,
+            estimated_minutes: task
+                .estimated_pomodoros
+                .map(|pomodoros| pomodoros as Minutes * focus_duration),
+            actual_minutes: duration / 60,
+        }
+    }
+
+    /// Completes the tasks among `ids` which are still open and generates the next instances
+    /// of the recurring ones.
+    fn complete_open(
+        conn: &mut SqliteConnection,
+        ids: &[i32],
+        today: NaiveDate,
+    ) -> Result<CompletedTasks> {
+        use crate::db::schema::tasks::dsl;
+
+        let tasks: Vec<Task> = dsl::tasks
+            .filter(dsl::id.eq_any(ids))
+            .filter(dsl::completed.eq(false))
+            .order(dsl::id.asc())
+            .load(conn)?;
+        let mut completed = CompletedTasks::default();
+        for task in &tasks {
+            completed.completed_ids.push(task.id);
+            if let Some(recurrence) = &task.recurrence {
+                let due_date = recurrence
+                    .next_after(task.due_date.unwrap_or(today), today + Duration::days(1));
+                completed
+                    .created_ids
+                    .extend(Self::create_next_instance(conn, task, due_date)?);
+            }
+        }
+        Self::set_completed(conn, &completed.completed_ids, true)?;
+        Ok(completed)
+    }
+
+    fn set_completed(conn: &mut SqliteConnection, ids: &[i32], completed: bool) -> Result<()> {
+        use crate::db::schema::tasks::dsl;
+
+        let finished_at = completed.then(|| chrono::Utc::now().timestamp());
+        diesel::update(dsl::tasks.filter(dsl::id.eq_any(ids)))
+            .set((
+                dsl::completed.eq(completed),
+                dsl::finished_at.eq(finished_at),
+            ))
+            .execute(conn)?;
+        Ok(())
+    }
+
+    /// Position after the last task sharing the given intent and parent task.
+    fn next_position(
+        conn: &mut SqliteConnection,
+        intent_id: i32,
+        parent_task_id: Option<i32>,
+    ) -> Result<i32> {
+        use crate::db::schema::tasks::dsl;
+
+        let last: Option<i32> = dsl::tasks
+            .filter(dsl::intent_id.eq(intent_id))
+            .filter(dsl::parent_task_id.is(parent_task_id))
+            .select(diesel::dsl::max(dsl::position))
+            .first(conn)?;
+        Ok(last.map_or(0, |position| position + 1))
+    }
+
+    fn copy_tree(
+        conn: &mut SqliteConnection,
+        id: i32,
+        intent_id: i32,
+        parent_task_id: Option<i32>,
+    ) -> Result<i32> {
+        use crate::db::schema::tasks::dsl;
+
+        let task = Self::get(conn, id)?;
+        let data = CreateTask {
+            body: task.body,
+            intent_id,
+            priority: task.priority,
+            due_date: task.due_date,
+            parent_task_id,
+        };
+        let copy_id = Self::create(conn, &data)?;
+        diesel::update(dsl::tasks.find(copy_id))
+            .set(dsl::estimated_pomodoros.eq(task.estimated_pomodoros))
+            .execute(conn)?;
+
+        let subtask_ids: Vec<i32> = dsl::tasks
+            .filter(dsl::parent_task_id.eq(id))
+            .order((dsl::position.asc(), dsl::id.asc()))
+            .select(dsl::id)
+            .load(conn)?;
+        for subtask_id in subtask_ids {
+            Self::copy_tree(conn, subtask_id, intent_id, Some(copy_id))?;
+        }
+        Ok(copy_id)
+    }
+
+    /// Copies a recurring task into a new instance due on the given day, the recurrence
+    /// moves over to the new instance so that a series only ever has one upcoming task.
+    /// Subtasks are carried over like a checklist, uncompleted and without due dates.
+    /// Returns ids of the new instance and its subtasks.
+    fn create_next_instance(
+        conn: &mut SqliteConnection,
+        task: &Task,
+        due_date: NaiveDate,
+    ) -> Result<Vec<i32>> {
+        use crate::db::schema::tasks::dsl;
+
+        let data = CreateTask {
+            body: task.body.clone(),
+            intent_id: task.intent_id,
+            priority: task.priority,
+            due_date: Some(due_date),
+            parent_task_id: task.parent_task_id,
+        };
+        let next_id = Self::create(conn, &data)?;
+        let series_id = task.series_id.unwrap_or(task.id);
+        diesel::update(dsl::tasks.find(next_id))
+            .set((
+                dsl::estimated_pomodoros.eq(task.estimated_pomodoros),
+                dsl::recurrence.eq(&task.recurrence),
+                dsl::series_id.eq(series_id),
+            ))
+            .execute(conn)?;
+        diesel::update(dsl::tasks.find(task.id))
+            .set((
+                dsl::recurrence.eq(None::<TaskRecurrence>),
+                dsl::series_id.eq(series_id),
+            ))
+            .execute(conn)?;
+
+        let subtask_ids: Vec<i32> = dsl::tasks
+            .filter(dsl::parent_task_id.eq(task.id))
+            .order((dsl::position.asc(), dsl::id.asc()))
+            .select(dsl::id)
+            .load(conn)?;
+        for subtask_id in subtask_ids {
+            Self::copy_tree(conn, subtask_id, task.intent_id, Some(next_id))?;
+        }
+        let mut ids = vec![next_id];
+        ids.extend(Self::get_descendant_ids(conn, next_id)?);
+        diesel::update(dsl::tasks.filter(dsl::id.eq_any(&ids[1..])))
+            .set(dsl::due_date.eq(None::<NaiveDate>))
+            .execute(conn)?;
+        Ok(ids)
+    }
+
+    /// Walks up from the given task and completes every parent whose subtasks are all done.
+    fn complete_finished_parents(
+        conn: &mut SqliteConnection,
+        id: i32,
+        today: NaiveDate,
+    ) -> Result<CompletedTasks> {
+        use crate::db::schema::tasks::dsl;
+
+        let mut completed = CompletedTasks::default();
+        let mut parent_id = Self::get(conn, id)?.parent_task_id;
+        while let Some(current_id) = parent_id {
+            let has_open_subtasks: bool = diesel::select(diesel::dsl::exists(
+                dsl::tasks
+                    .filter(dsl::parent_task_id.eq(current_id))
+                    .filter(dsl::completed.eq(false)),
+            ))
+            .get_result(conn)?;
+            let Ok(parent) = Self::get(conn, current_id) else {
+                break;
+            };
+            if has_open_subtasks {
+                break;
+            }
+            completed.extend(Self::complete_open(conn, &[current_id], today)?);
+            parent_id = parent.parent_task_id;
+        }
+        Ok(completed)
+    }
 }
 
 #[cfg(test)]
 mod task_bmc_tests {
-    use crate::{bmc::IntentBmc, db::Db, models::CreateIntent, prelude::Error};
+    use crate::{
+        bmc::{IntentBmc, SessionBmc},
+        db::Db,
+        models::{CreateIntent, CreateSession, TaskPriority},
+        prelude::Error,
+        timer::SessionType,
+    };
 
     use super::*;
 
     fn create_dummy_intent(conn: &mut SqliteConnection) -> i32 {
         let data = CreateIntent {
             label: "foo".to_string(),
+            parent_id: None,
         };
         IntentBmc::create(conn, &data).unwrap()
     }
@@ -126,6 +866,9 @@ mod task_bmc_tests {
         let data = CreateTask {
             body: "supercalifragilisticexpialidocious".to_string(),
             intent_id,
+            priority: TaskPriority::Normal,
+            due_date: None,
+            parent_task_id: None,
         };
         let id = TaskBmc::create(&mut conn, &data).unwrap();
         // Query task.
@@ -147,11 +890,16 @@ mod task_bmc_tests {
         let data = CreateTask {
             body: "supercalifragilisticexpialidocious".to_string(),
             intent_id,
+            priority: TaskPriority::Normal,
+            due_date: None,
+            parent_task_id: None,
         };
         let id = TaskBmc::create(&mut conn, &data).unwrap();
         // Update task.
         let data = UpdateTask {
             body: Some("goofball".to_string()),
+            priority: None,
+            intent_id: None,
         };
         let id = TaskBmc::update(&mut conn, id, &data).unwrap();
         // Query task.
@@ -169,11 +917,14 @@ mod task_bmc_tests {
         let data = CreateTask {
             body: "supercalifragilisticexpialidocious".to_string(),
             intent_id,
+            priority: TaskPriority::Normal,
+            due_date: None,
+            parent_task_id: None,
         };
         let id = TaskBmc::create(&mut conn, &data).unwrap();
         assert_eq!(id, 1);
         // Delete task.
-        let id = TaskBmc::delete(&mut conn, id).unwrap();
+        assert_eq!(TaskBmc::delete(&mut conn, id).unwrap(), vec![id]);
         // Query non existent task.
         let result = TaskBmc::get(&mut conn, id);
         // Test the result.
@@ -189,6 +940,9 @@ mod task_bmc_tests {
         let data = CreateTask {
             body: "supercalifragilisticexpialidocious".to_string(),
             intent_id,
+            priority: TaskPriority::Normal,
+            due_date: None,
+            parent_task_id: None,
         };
         let id = TaskBmc::create(&mut conn, &data).unwrap();
         let task = TaskBmc::get(&mut conn, id).unwrap();
@@ -209,6 +963,9 @@ mod task_bmc_tests {
             let data = CreateTask {
                 body: "supercalifragilisticexpialidocious".to_string(),
                 intent_id,
+                priority: TaskPriority::Normal,
+                due_date: None,
+                parent_task_id: None,
             };
             TaskBmc::create(&mut conn, &data).unwrap();
         }
@@ -229,6 +986,9 @@ mod task_bmc_tests {
             let data = CreateTask {
                 body: "wadduh cuh".to_string(),
                 intent_id,
+                priority: TaskPriority::Normal,
+                due_date: None,
+                parent_task_id: None,
             };
             TaskBmc::create(&mut conn, &data).unwrap();
         }
@@ -236,6 +996,9 @@ mod task_bmc_tests {
             let data = CreateTask {
                 body: "wadduh blood".to_string(),
                 intent_id: intent_id2,
+                priority: TaskPriority::Normal,
+                due_date: None,
+                parent_task_id: None,
             };
             TaskBmc::create(&mut conn, &data).unwrap();
         }
@@ -245,6 +1008,9 @@ mod task_bmc_tests {
             offset: None,
             limit: None,
             completed: None,
+            sort_by: None,
+            due: None,
+            today: None,
         };
         let sessions = TaskBmc::get_list(&mut conn, Some(options)).unwrap();
 
@@ -262,6 +1028,9 @@ mod task_bmc_tests {
             let data = CreateTask {
                 body: "extremely difficult task".to_string(),
                 intent_id,
+                priority: TaskPriority::Normal,
+                due_date: None,
+                parent_task_id: None,
             };
             TaskBmc::create(&mut conn, &data).unwrap();
         }
@@ -271,6 +1040,9 @@ mod task_bmc_tests {
             offset: Some(4),
             limit: Some(4),
             completed: None,
+            sort_by: None,
+            due: None,
+            today: None,
         };
         let sessions = TaskBmc::get_list(&mut conn, Some(options)).unwrap();
 
@@ -288,11 +1060,14 @@ mod task_bmc_tests {
             let data = CreateTask {
                 body: "extremely difficult task".to_string(),
                 intent_id,
+                priority: TaskPriority::Normal,
+                due_date: None,
+                parent_task_id: None,
             };
             let id = TaskBmc::create(&mut conn, &data).unwrap();
             // Complete every other task.
             if i % 2 == 0 {
-                TaskBmc::complete(&mut conn, id).unwrap();
+                TaskBmc::complete(&mut conn, id, today()).unwrap();
             }
         }
 
@@ -301,6 +1076,9 @@ mod task_bmc_tests {
             completed: Some(true),
             offset: None,
             limit: None,
+            sort_by: None,
+            due: None,
+            today: None,
         };
         let sessions = TaskBmc::get_list(&mut conn, Some(options)).unwrap();
 
@@ -319,10 +1097,13 @@ mod task_bmc_tests {
         let data = CreateTask {
             body: "to be completed".to_string(),
             intent_id,
+            priority: TaskPriority::Normal,
+            due_date: None,
+            parent_task_id: None,
         };
         let id = TaskBmc::create(&mut conn, &data).unwrap();
         // Complete the task.
-        let id = TaskBmc::complete(&mut conn, id).unwrap();
+        TaskBmc::complete(&mut conn, id, today()).unwrap();
         // Query the task.
         let task = TaskBmc::get(&mut conn, id).unwrap();
         // Test if it's actually completed.
@@ -339,19 +1120,698 @@ mod task_bmc_tests {
         let data = CreateTask {
             body: "to be completed".to_string(),
             intent_id,
+            priority: TaskPriority::Normal,
+            due_date: None,
+            parent_task_id: None,
         };
         let id = TaskBmc::create(&mut conn, &data).unwrap();
         // Complete the task.
-        let id = TaskBmc::complete(&mut conn, id).unwrap();
+        TaskBmc::complete(&mut conn, id, today()).unwrap();
         let task = TaskBmc::get(&mut conn, id).unwrap();
         // Test if it's actually completed.
         assert_eq!(task.completed, true);
         assert!(task.finished_at.is_some());
         // Reverse completion.
-        let id = TaskBmc::uncomplete(&mut conn, id).unwrap();
+        assert_eq!(TaskBmc::uncomplete(&mut conn, id).unwrap(), vec![id]);
         let task = TaskBmc::get(&mut conn, id).unwrap();
         // Test whether completion has been reversed.
         assert_eq!(task.completed, false);
         assert!(task.finished_at.is_none());
     }
+
+    fn create_task(
+        conn: &mut SqliteConnection,
+        intent_id: i32,
+        priority: TaskPriority,
+        due_date: Option<NaiveDate>,
+    ) -> i32 {
+        let data = CreateTask {
+            body: "foo".to_string(),
+            intent_id,
+            priority,
+            due_date,
+            parent_task_id: None,
+        };
+        TaskBmc::create(conn, &data).unwrap()
+    }
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2023, 7, 20).unwrap()
+    }
+
+    fn options(sort_by: Option<TaskSortBy>, due: Option<TaskDueFilter>) -> GetTasksOptions {
+        GetTasksOptions {
+            intent_id: None,
+            offset: None,
+            limit: None,
+            completed: None,
+            sort_by,
+            due,
+            today: Some(today()),
+        }
+    }
+
+    fn subtasks_of(conn: &mut SqliteConnection, id: i32) -> Vec<Task> {
+        use crate::db::schema::tasks::dsl;
+
+        dsl::tasks
+            .filter(dsl::parent_task_id.eq(id))
+            .load(conn)
+            .unwrap()
+    }
+
+    fn ids(conn: &mut SqliteConnection, options: GetTasksOptions) -> Vec<i32> {
+        TaskBmc::get_list(conn, Some(options))
+            .unwrap()
+            .into_iter()
+            .map(|task| task.id)
+            .collect()
+    }
+
+    #[test]
+    fn test_move_task() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let intent_id = create_dummy_intent(&mut conn);
+        let a = create_task(&mut conn, intent_id, TaskPriority::Normal, None);
+        let b = create_task(&mut conn, intent_id, TaskPriority::Normal, None);
+        let c = create_task(&mut conn, intent_id, TaskPriority::Normal, None);
+        assert_eq!(TaskBmc::get(&mut conn, c).unwrap().position, 2);
+
+        TaskBmc::move_to(&mut conn, c, 0).unwrap();
+        assert_eq!(ids(&mut conn, options(None, None)), vec![c, a, b]);
+
+        TaskBmc::move_to(&mut conn, c, 10).unwrap();
+        assert_eq!(ids(&mut conn, options(None, None)), vec![a, b, c]);
+    }
+
+    #[test]
+    fn test_get_tasks_sorted() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let intent_id = create_dummy_intent(&mut conn);
+        let day = |d| NaiveDate::from_ymd_opt(2023, 7, d);
+        let a = create_task(&mut conn, intent_id, TaskPriority::Low, day(25));
+        let b = create_task(&mut conn, intent_id, TaskPriority::High, None);
+        let c = create_task(&mut conn, intent_id, TaskPriority::Normal, day(21));
+
+        let by_priority = options(Some(TaskSortBy::Priority), None);
+        assert_eq!(ids(&mut conn, by_priority), vec![b, c, a]);
+        let by_due_date = options(Some(TaskSortBy::DueDate), None);
+        assert_eq!(ids(&mut conn, by_due_date), vec![c, a, b]);
+    }
+
+    #[test]
+    fn test_get_tasks_by_due_date() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let intent_id = create_dummy_intent(&mut conn);
+        let day = |d| NaiveDate::from_ymd_opt(2023, 7, d);
+        let overdue = create_task(&mut conn, intent_id, TaskPriority::Normal, day(18));
+        let done = create_task(&mut conn, intent_id, TaskPriority::Normal, day(19));
+        let due_today = create_task(&mut conn, intent_id, TaskPriority::Normal, day(20));
+        create_task(&mut conn, intent_id, TaskPriority::Normal, day(21));
+        create_task(&mut conn, intent_id, TaskPriority::Normal, None);
+        TaskBmc::complete(&mut conn, done, today()).unwrap();
+
+        let due = options(None, Some(TaskDueFilter::Overdue));
+        assert_eq!(ids(&mut conn, due), vec![overdue]);
+        let due = options(None, Some(TaskDueFilter::DueToday));
+        assert_eq!(ids(&mut conn, due), vec![due_today]);
+
+        TaskBmc::set_due_date(&mut conn, due_today, None).unwrap();
+        let due = options(None, Some(TaskDueFilter::DueToday));
+        assert!(ids(&mut conn, due).is_empty());
+    }
+
+    fn create_subtask(conn: &mut SqliteConnection, intent_id: i32, parent_task_id: i32) -> i32 {
+        let data = CreateTask {
+            body: "step".to_string(),
+            intent_id,
+            priority: TaskPriority::Normal,
+            due_date: None,
+            parent_task_id: Some(parent_task_id),
+        };
+        TaskBmc::create(conn, &data).unwrap()
+    }
+
+    fn is_completed(conn: &mut SqliteConnection, id: i32) -> bool {
+        TaskBmc::get(conn, id).unwrap().completed
+    }
+
+    #[test]
+    fn test_create_subtask_of_other_intent() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let intent_id = create_dummy_intent(&mut conn);
+        let other_intent_id = create_dummy_intent(&mut conn);
+        let parent = create_task(&mut conn, intent_id, TaskPriority::Normal, None);
+
+        let data = CreateTask {
+            body: "step".to_string(),
+            intent_id: other_intent_id,
+            priority: TaskPriority::Normal,
+            due_date: None,
+            parent_task_id: Some(parent),
+        };
+        let result = TaskBmc::create(&mut conn, &data);
+        assert!(matches!(
+            result,
+            Err(Error::BmcError(BmcError::InvalidParentTask))
+        ));
+    }
+
+    #[test]
+    fn test_complete_keeps_parents_consistent() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let intent_id = create_dummy_intent(&mut conn);
+        let root = create_task(&mut conn, intent_id, TaskPriority::Normal, None);
+        let step = create_subtask(&mut conn, intent_id, root);
+        let nested = create_subtask(&mut conn, intent_id, step);
+        let other_step = create_subtask(&mut conn, intent_id, root);
+        // Siblings are ordered separately from top level tasks.
+        assert_eq!(TaskBmc::get(&mut conn, other_step).unwrap().position, 1);
+
+        // The last open subtask completes its parents.
+        TaskBmc::complete(&mut conn, nested, today()).unwrap();
+        assert!(is_completed(&mut conn, step));
+        assert!(!is_completed(&mut conn, root));
+        TaskBmc::complete(&mut conn, other_step, today()).unwrap();
+        assert!(is_completed(&mut conn, root));
+
+        // Reopening a subtask reopens every parent.
+        let mut reopened = TaskBmc::uncomplete(&mut conn, nested).unwrap();
+        reopened.sort();
+        assert_eq!(reopened, vec![root, step, nested]);
+        assert!(!is_completed(&mut conn, step));
+        assert!(!is_completed(&mut conn, root));
+        assert!(is_completed(&mut conn, other_step));
+    }
+
+    #[test]
+    fn test_complete_with_subtasks() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let intent_id = create_dummy_intent(&mut conn);
+        let root = create_task(&mut conn, intent_id, TaskPriority::Normal, None);
+        let step = create_subtask(&mut conn, intent_id, root);
+        let nested = create_subtask(&mut conn, intent_id, step);
+        let done = create_subtask(&mut conn, intent_id, root);
+        TaskBmc::complete(&mut conn, done, today()).unwrap();
+        let finished_at = TaskBmc::get(&mut conn, done).unwrap().finished_at;
+
+        let mut ids = TaskBmc::complete_with_subtasks(&mut conn, root, today())
+            .unwrap()
+            .completed_ids;
+        ids.sort();
+        assert_eq!(ids, vec![root, step, nested]);
+        assert!(is_completed(&mut conn, nested));
+        assert_eq!(
+            TaskBmc::get(&mut conn, done).unwrap().finished_at,
+            finished_at
+        );
+    }
+
+    #[test]
+    fn test_get_progress_and_delete_subtasks() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let intent_id = create_dummy_intent(&mut conn);
+        let root = create_task(&mut conn, intent_id, TaskPriority::Normal, None);
+        create_task(&mut conn, intent_id, TaskPriority::Normal, None);
+        let steps: Vec<i32> = (0..4)
+            .map(|_| create_subtask(&mut conn, intent_id, root))
+            .collect();
+        let nested = create_subtask(&mut conn, intent_id, steps[0]);
+        TaskBmc::complete(&mut conn, steps[1], today()).unwrap();
+        TaskBmc::complete(&mut conn, steps[2], today()).unwrap();
+        // Completes `steps[0]` as well.
+        TaskBmc::complete(&mut conn, nested, today()).unwrap();
+
+        let progress = TaskBmc::get_progress(&mut conn, intent_id).unwrap();
+        assert_eq!(
+            progress,
+            vec![
+                TaskProgress {
+                    task_id: root,
+                    completed: 4,
+                    total: 5,
+                },
+                TaskProgress {
+                    task_id: steps[0],
+                    completed: 1,
+                    total: 1,
+                },
+            ]
+        );
+
+        let mut deleted = TaskBmc::delete(&mut conn, root).unwrap();
+        deleted.sort();
+        let mut expected = vec![root, nested];
+        expected.extend(&steps);
+        expected.sort();
+        assert_eq!(deleted, expected);
+        let remaining = TaskBmc::get_list(&mut conn, None).unwrap();
+        assert_eq!(remaining.len(), 1);
+    }
+
+    fn create_focus_session(conn: &mut SqliteConnection, intent_id: i32, duration: i32) -> i32 {
+        let data = CreateSession {
+            duration,
+            started_at: 1689940800,
+            intent_id,
+            session_type: SessionType::Focus,
+            planned_duration: Some(1500),
+        };
+        SessionBmc::create(conn, &data).unwrap()
+    }
+
+    #[test]
+    fn test_set_invalid_estimate() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let intent_id = create_dummy_intent(&mut conn);
+        let id = create_task(&mut conn, intent_id, TaskPriority::Normal, None);
+
+        let result = TaskBmc::set_estimate(&mut conn, id, Some(0));
+        assert!(matches!(
+            result,
+            Err(Error::BmcError(BmcError::InvalidEstimate))
+        ));
+        TaskBmc::set_estimate(&mut conn, id, Some(3)).unwrap();
+        TaskBmc::set_estimate(&mut conn, id, None).unwrap();
+        assert!(TaskBmc::get(&mut conn, id)
+            .unwrap()
+            .estimated_pomodoros
+            .is_none());
+    }
+
+    #[test]
+    fn test_get_estimate() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let intent_id = create_dummy_intent(&mut conn);
+        let id = create_task(&mut conn, intent_id, TaskPriority::Normal, None);
+        TaskBmc::set_estimate(&mut conn, id, Some(3)).unwrap();
+
+        let first = create_focus_session(&mut conn, intent_id, 1500);
+        let second = create_focus_session(&mut conn, intent_id, 1200);
+        TaskBmc::link_session(&mut conn, id, first).unwrap();
+        TaskBmc::link_session(&mut conn, id, second).unwrap();
+        // Linking the same session again doesn't count it twice.
+        TaskBmc::link_session(&mut conn, id, second).unwrap();
+
+        let estimate = TaskBmc::get_estimate(&mut conn, id, 25).unwrap();
+        assert_eq!(
+            estimate,
+            TaskEstimate {
+                task_id: id,
+                estimated_pomodoros: Some(3),
+                actual_pomodoros: 2,
+                estimated_minutes: Some(75),
+                actual_minutes: 45,
+            }
+        );
+
+        // Deleting a session removes it from the task's actuals.
+        SessionBmc::delete(&mut conn, first).unwrap();
+        let estimate = TaskBmc::get_estimate(&mut conn, id, 25).unwrap();
+        assert_eq!(estimate.actual_pomodoros, 1);
+    }
+
+    #[test]
+    fn test_get_estimation_report() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let intent_id = create_dummy_intent(&mut conn);
+        let mut create_estimated = |estimate: i32, sessions: i32, completed: bool| {
+            let id = create_task(&mut conn, intent_id, TaskPriority::Normal, None);
+            TaskBmc::set_estimate(&mut conn, id, Some(estimate)).unwrap();
+            for _ in 0..sessions {
+                let session_id = create_focus_session(&mut conn, intent_id, 1500);
+                TaskBmc::link_session(&mut conn, id, session_id).unwrap();
+            }
+            if completed {
+                TaskBmc::complete(&mut conn, id, today()).unwrap();
+            }
+            id
+        };
+        create_estimated(2, 2, true);
+        create_estimated(2, 4, true);
+        create_estimated(4, 3, true);
+        // Open tasks are not part of the report.
+        create_estimated(1, 5, false);
+        // Neither are tasks without an estimate.
+        let unestimated = create_task(&mut conn, intent_id, TaskPriority::Normal, None);
+        TaskBmc::complete(&mut conn, unestimated, today()).unwrap();
+
+        let report = TaskBmc::get_estimation_report(&mut conn, None, 25).unwrap();
+        assert_eq!(report.tasks.len(), 3);
+        assert_eq!(report.on_estimate, 1);
+        assert_eq!(report.underestimated, 1);
+        assert_eq!(report.overestimated, 1);
+        assert_eq!(report.ratio, Some(9.0 / 8.0));
+
+        let other_intent_id = create_dummy_intent(&mut conn);
+        let report = TaskBmc::get_estimation_report(&mut conn, Some(other_intent_id), 25).unwrap();
+        assert!(report.tasks.is_empty());
+        assert!(report.ratio.is_none());
+    }
+
+    #[test]
+    fn test_next_recurrence() {
+        let day = |m, d| NaiveDate::from_ymd_opt(2023, m, d).unwrap();
+        // 2023-07-21 is a Friday.
+        let friday = day(7, 21);
+        assert_eq!(TaskRecurrence::Daily.next_after(friday, friday), day(7, 22));
+        assert_eq!(
+            TaskRecurrence::Weekdays.next_after(friday, friday),
+            day(7, 24)
+        );
+        let weekly = TaskRecurrence::Weekly(vec![2, 5]);
+        assert_eq!(weekly.next_after(friday, friday), day(7, 22));
+        assert_eq!(weekly.next_after(day(7, 22), day(7, 22)), day(7, 26));
+        // Never earlier than the given day.
+        assert_eq!(TaskRecurrence::Daily.next_after(day(7, 1), friday), friday);
+        // Months without the day fall back to their last day.
+        let monthly = TaskRecurrence::Monthly(31);
+        assert_eq!(monthly.next_after(day(1, 31), day(1, 31)), day(2, 28));
+        assert_eq!(monthly.next_after(day(2, 28), day(2, 28)), day(3, 31));
+        assert_eq!(
+            TaskRecurrence::Monthly(5).next_after(day(12, 5), day(12, 5)),
+            NaiveDate::from_ymd_opt(2024, 1, 5).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_set_invalid_recurrence() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let intent_id = create_dummy_intent(&mut conn);
+        let id = create_task(&mut conn, intent_id, TaskPriority::Normal, None);
+
+        for recurrence in [
+            TaskRecurrence::Weekly(vec![]),
+            TaskRecurrence::Weekly(vec![7]),
+            TaskRecurrence::Monthly(0),
+        ] {
+            let result = TaskBmc::set_recurrence(&mut conn, id, Some(recurrence));
+            assert!(matches!(
+                result,
+                Err(Error::BmcError(BmcError::InvalidRecurrence))
+            ));
+        }
+        let recurrence = TaskRecurrence::Weekly(vec![0, 4]);
+        TaskBmc::set_recurrence(&mut conn, id, Some(recurrence.clone())).unwrap();
+        let task = TaskBmc::get(&mut conn, id).unwrap();
+        assert_eq!(task.recurrence, Some(recurrence));
+    }
+
+    #[test]
+    fn test_complete_recurring_task() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let intent_id = create_dummy_intent(&mut conn);
+        let first = create_task(&mut conn, intent_id, TaskPriority::High, Some(today()));
+        TaskBmc::set_recurrence(&mut conn, first, Some(TaskRecurrence::Daily)).unwrap();
+        TaskBmc::set_estimate(&mut conn, first, Some(2)).unwrap();
+
+        let completed = TaskBmc::complete(&mut conn, first, today()).unwrap();
+        assert_eq!(completed.completed_ids, vec![first]);
+        let open = TaskBmc::get_list(
+            &mut conn,
+            Some(GetTasksOptions {
+                completed: Some(false),
+                ..options(None, None)
+            }),
+        )
+        .unwrap();
+        assert_eq!(open.len(), 1);
+        let second = &open[0];
+        assert_eq!(completed.created_ids, vec![second.id]);
+        assert_eq!(second.body, "foo");
+        assert_eq!(second.priority, TaskPriority::High);
+        assert_eq!(second.estimated_pomodoros, Some(2));
+        assert_eq!(second.due_date, Some(today() + Duration::days(1)));
+        assert_eq!(second.recurrence, Some(TaskRecurrence::Daily));
+        assert_eq!(second.series_id, Some(first));
+        // The completed instance is kept without its recurrence.
+        let first_task = TaskBmc::get(&mut conn, first).unwrap();
+        assert!(first_task.completed);
+        assert!(first_task.recurrence.is_none());
+
+        // Completing it again doesn't generate another instance.
+        TaskBmc::complete(&mut conn, first, today()).unwrap();
+        assert_eq!(TaskBmc::get_list(&mut conn, None).unwrap().len(), 2);
+
+        let second = second.id;
+        TaskBmc::complete(&mut conn, second, today()).unwrap();
+        let history: Vec<i32> = TaskBmc::get_history(&mut conn, second)
+            .unwrap()
+            .into_iter()
+            .map(|task| task.id)
+            .collect();
+        assert_eq!(history.len(), 2);
+        assert!(history.contains(&first) && history.contains(&second));
+    }
+
+    #[test]
+    fn test_complete_recurring_task_with_subtasks() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let intent_id = create_dummy_intent(&mut conn);
+        let first = create_task(&mut conn, intent_id, TaskPriority::Normal, Some(today()));
+        let step = create_subtask(&mut conn, intent_id, first);
+        let nested = create_subtask(&mut conn, intent_id, step);
+        TaskBmc::set_recurrence(&mut conn, first, Some(TaskRecurrence::Daily)).unwrap();
+        let other_step = create_subtask(&mut conn, intent_id, first);
+        TaskBmc::complete(&mut conn, nested, today()).unwrap();
+
+        let completed = TaskBmc::complete(&mut conn, other_step, today()).unwrap();
+        // The parent got completed along with its last subtask, generating the next instance.
+        assert_eq!(completed.completed_ids, vec![other_step, first]);
+        assert_eq!(completed.created_ids.len(), 4);
+        let next = TaskBmc::get(&mut conn, completed.created_ids[0]).unwrap();
+        assert_eq!(next.series_id, Some(first));
+        // The subtasks are carried over uncompleted.
+        let subtasks = subtasks_of(&mut conn, next.id);
+        assert_eq!(subtasks.len(), 2);
+        assert!(subtasks.iter().all(|task| !task.completed));
+        assert!(subtasks[0].recurrence.is_none());
+        let nested_copy = subtasks_of(&mut conn, subtasks[0].id);
+        assert_eq!(nested_copy.len(), 1);
+        assert!(!nested_copy[0].completed);
+        // The completed instance keeps its own subtasks.
+        assert_eq!(subtasks_of(&mut conn, first)[0].id, step);
+    }
+
+    #[test]
+    fn test_get_list_due_filter_requires_today() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let result = TaskBmc::get_list(
+            &mut conn,
+            Some(GetTasksOptions {
+                today: None,
+                ..options(None, Some(TaskDueFilter::DueToday))
+            }),
+        );
+        assert!(matches!(
+            result,
+            Err(Error::BmcError(BmcError::MissingToday))
+        ));
+    }
+
+    #[test]
+    fn test_roll_over_recurring_tasks() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let intent_id = create_dummy_intent(&mut conn);
+        let day = |d| NaiveDate::from_ymd_opt(2023, 7, d);
+        let missed = create_task(&mut conn, intent_id, TaskPriority::Normal, day(17));
+        let upcoming = create_task(&mut conn, intent_id, TaskPriority::Normal, day(24));
+        let weekly = TaskRecurrence::Weekly(vec![0]);
+        TaskBmc::set_recurrence(&mut conn, missed, Some(weekly.clone())).unwrap();
+        TaskBmc::set_recurrence(&mut conn, upcoming, Some(weekly)).unwrap();
+
+        let ids = TaskBmc::roll_over(&mut conn, day(21).unwrap()).unwrap();
+        assert_eq!(ids.len(), 1);
+        let next = TaskBmc::get(&mut conn, ids[0]).unwrap();
+        assert_eq!(next.due_date, day(24));
+        assert_eq!(next.series_id, Some(missed));
+        // The missed instance stays open, it's only rolled over once.
+        assert!(!is_completed(&mut conn, missed));
+        assert!(TaskBmc::roll_over(&mut conn, day(21).unwrap())
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_update_task_intent() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let intent_id = create_dummy_intent(&mut conn);
+        let other_intent_id = create_dummy_intent(&mut conn);
+        create_task(&mut conn, other_intent_id, TaskPriority::Normal, None);
+        let root = create_task(&mut conn, intent_id, TaskPriority::Normal, None);
+        let step = create_subtask(&mut conn, intent_id, root);
+        let nested = create_subtask(&mut conn, intent_id, step);
+
+        let data = UpdateTask {
+            body: None,
+            priority: None,
+            intent_id: Some(other_intent_id),
+        };
+        TaskBmc::update(&mut conn, root, &data).unwrap();
+        let task = TaskBmc::get(&mut conn, root).unwrap();
+        assert_eq!(task.intent_id, other_intent_id);
+        assert_eq!(task.position, 1);
+        // Subtasks follow their parent.
+        assert_eq!(
+            TaskBmc::get(&mut conn, nested).unwrap().intent_id,
+            other_intent_id
+        );
+
+        // A subtask moved on its own leaves its parent behind.
+        TaskBmc::move_to_intent(&mut conn, step, intent_id).unwrap();
+        let task = TaskBmc::get(&mut conn, step).unwrap();
+        assert!(task.parent_task_id.is_none());
+        assert_eq!(
+            TaskBmc::get(&mut conn, nested).unwrap().intent_id,
+            intent_id
+        );
+        assert!(TaskBmc::get_descendant_ids(&mut conn, root)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_copy_task_to_intent() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let intent_id = create_dummy_intent(&mut conn);
+        let other_intent_id = create_dummy_intent(&mut conn);
+        let root = create_task(&mut conn, intent_id, TaskPriority::High, None);
+        let step = create_subtask(&mut conn, intent_id, root);
+        TaskBmc::complete(&mut conn, step, today()).unwrap();
+
+        let copy = TaskBmc::copy_to_intent(&mut conn, root, other_intent_id).unwrap();
+        let task = TaskBmc::get(&mut conn, copy).unwrap();
+        assert_eq!(task.intent_id, other_intent_id);
+        assert_eq!(task.priority, TaskPriority::High);
+        assert!(!task.completed);
+        let copied_steps = TaskBmc::get_descendant_ids(&mut conn, copy).unwrap();
+        assert_eq!(copied_steps.len(), 1);
+        assert!(!is_completed(&mut conn, copied_steps[0]));
+        // The original is left as it was.
+        assert!(is_completed(&mut conn, root));
+    }
+
+    #[test]
+    fn test_bulk_task_operations() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let intent_id = create_dummy_intent(&mut conn);
+        let other_intent_id = create_dummy_intent(&mut conn);
+        let root = create_task(&mut conn, intent_id, TaskPriority::Normal, None);
+        let step = create_subtask(&mut conn, intent_id, root);
+        let a = create_task(&mut conn, intent_id, TaskPriority::Normal, None);
+        let b = create_task(&mut conn, intent_id, TaskPriority::Normal, None);
+
+        let mut completed = TaskBmc::complete_many(&mut conn, &[step, a], today())
+            .unwrap()
+            .completed_ids;
+        completed.sort();
+        // The parent got completed along with its last subtask.
+        assert_eq!(completed, vec![root, step, a]);
+
+        // A single missing task rolls the whole operation back.
+        let result = TaskBmc::uncomplete_many(&mut conn, &[a, 999]);
+        assert!(result.is_err());
+        assert!(is_completed(&mut conn, a));
+        // Parents reopened along the way are reported, untouched tasks are not.
+        let mut reopened = TaskBmc::uncomplete_many(&mut conn, &[a, step, b]).unwrap();
+        reopened.sort();
+        assert_eq!(reopened, vec![root, step, a]);
+        assert!(!is_completed(&mut conn, a));
+        assert!(!is_completed(&mut conn, root));
+
+        TaskBmc::move_many_to_intent(&mut conn, &[a, b], other_intent_id).unwrap();
+        let options = GetTasksOptions {
+            intent_id: Some(other_intent_id),
+            ..options(None, None)
+        };
+        assert_eq!(ids(&mut conn, options), vec![a, b]);
+
+        let mut deleted = TaskBmc::delete_many(&mut conn, &[step, root, b]).unwrap();
+        deleted.sort();
+        assert_eq!(deleted, vec![root, step, b]);
+        let remaining = TaskBmc::get_list(&mut conn, None).unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].id, a);
+    }
+
+    #[test]
+    fn test_clear_completed_tasks() {
+        use crate::db::schema::tasks::dsl;
+
+        let mut conn = Db::establish_test_connection().unwrap();
+        let intent_id = create_dummy_intent(&mut conn);
+        let other_intent_id = create_dummy_intent(&mut conn);
+        let old = create_task(&mut conn, intent_id, TaskPriority::Normal, None);
+        let recent = create_task(&mut conn, intent_id, TaskPriority::Normal, None);
+        let open = create_task(&mut conn, intent_id, TaskPriority::Normal, None);
+        let other = create_task(&mut conn, other_intent_id, TaskPriority::Normal, None);
+        TaskBmc::complete_many(&mut conn, &[old, recent, other], today()).unwrap();
+        let week_ago = chrono::Utc::now().timestamp() - 7 * 24 * 60 * 60;
+        diesel::update(dsl::tasks.filter(dsl::id.eq_any([old, other])))
+            .set(dsl::finished_at.eq(week_ago))
+            .execute(&mut conn)
+            .unwrap();
+
+        let cleared = TaskBmc::clear_completed(&mut conn, 3, Some(intent_id)).unwrap();
+        assert_eq!(cleared, vec![old]);
+        let cleared = TaskBmc::clear_completed(&mut conn, 3, None).unwrap();
+        assert_eq!(cleared, vec![other]);
+        let remaining = ids(&mut conn, options(None, None));
+        assert_eq!(remaining, vec![recent, open]);
+    }
+
+    #[test]
+    fn test_clear_completed_keeps_open_subtasks_and_series() {
+        use crate::db::schema::tasks::dsl;
+
+        let mut conn = Db::establish_test_connection().unwrap();
+        let intent_id = create_dummy_intent(&mut conn);
+        // A parent completed on its own, its subtask is still open.
+        let parent = create_task(&mut conn, intent_id, TaskPriority::Normal, None);
+        let open_step = create_subtask(&mut conn, intent_id, parent);
+        TaskBmc::complete(&mut conn, parent, today()).unwrap();
+        // A finished instance of a recurring series.
+        let recurring = create_task(&mut conn, intent_id, TaskPriority::Normal, None);
+        TaskBmc::set_recurrence(&mut conn, recurring, Some(TaskRecurrence::Daily)).unwrap();
+        TaskBmc::complete(&mut conn, recurring, today()).unwrap();
+        // A parent which is done along with all of its subtasks.
+        let done = create_task(&mut conn, intent_id, TaskPriority::Normal, None);
+        let done_step = create_subtask(&mut conn, intent_id, done);
+        TaskBmc::complete_with_subtasks(&mut conn, done, today()).unwrap();
+
+        let week_ago = chrono::Utc::now().timestamp() - 7 * 24 * 60 * 60;
+        diesel::update(dsl::tasks.filter(dsl::completed.eq(true)))
+            .set(dsl::finished_at.eq(week_ago))
+            .execute(&mut conn)
+            .unwrap();
+
+        let mut cleared = TaskBmc::clear_completed(&mut conn, 3, None).unwrap();
+        cleared.sort();
+        assert_eq!(cleared, vec![done, done_step]);
+        assert!(!is_completed(&mut conn, open_step));
+        assert!(is_completed(&mut conn, parent));
+        assert_eq!(TaskBmc::get_history(&mut conn, recurring).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_move_many_to_intent_in_any_order() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let intent_id = create_dummy_intent(&mut conn);
+        let other_intent_id = create_dummy_intent(&mut conn);
+        let root = create_task(&mut conn, intent_id, TaskPriority::Normal, None);
+        let step = create_subtask(&mut conn, intent_id, root);
+        let nested = create_subtask(&mut conn, intent_id, step);
+
+        // Subtasks listed before their parent still move along with it.
+        let mut moved =
+            TaskBmc::move_many_to_intent(&mut conn, &[nested, step, root], other_intent_id)
+                .unwrap();
+        moved.sort();
+        assert_eq!(moved, vec![root, step, nested]);
+        let step_task = TaskBmc::get(&mut conn, step).unwrap();
+        assert_eq!(step_task.intent_id, other_intent_id);
+        assert_eq!(step_task.parent_task_id, Some(root));
+        let nested_task = TaskBmc::get(&mut conn, nested).unwrap();
+        assert_eq!(nested_task.parent_task_id, Some(step));
+    }
 }
@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::{collections::HashMap, path::PathBuf};
 
 use serde::{Deserialize, Serialize};
 use serde_with_macros::skip_serializing_none;
@@ -22,6 +22,16 @@ impl Config for QueueConfig {
     }
 }
 
+impl QueueConfig {
+    /// Rewrites intent ids of queued sessions, sessions of unknown intents are dropped.
+    pub fn remap_intents(&mut self, ids: &HashMap<i32, i32>) {
+        for template in self.templates.iter_mut() {
+            template.retain_mut(|session| session.remap_intent(ids));
+        }
+        self.templates.retain(|template| !template.is_empty());
+    }
+}
+
 impl Default for QueueConfig {
     fn default() -> Self {
         Self { templates: vec![] }
@@ -1,6 +1,6 @@
 use std::path::PathBuf;
 
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use serde_with_macros::skip_serializing_none;
 use ts_rs::TS;
 
@@ -23,6 +23,13 @@ pub struct SettingsConfig {
     pub main_minimize_to_tray: bool,
     pub main_always_on_top: bool,
     pub main_display_on_timer_complete: bool,
+    /// IANA timezone name used to group sessions into days, defaults to the system timezone.
+    pub timezone: Option<String>,
+    /// Hour at which a new day starts, so late night work counts towards the previous day.
+    pub day_start_hour: u32,
+    /// Folder with a continuously refreshed `.ics` file for calendar clients to subscribe to.
+    #[serde(default)]
+    pub ics_feed_dir: Option<String>,
 }
 
 impl Config for SettingsConfig {
@@ -45,6 +52,9 @@ impl Default for SettingsConfig {
             main_minimize_to_tray: false,
             main_always_on_top: false,
             main_display_on_timer_complete: true,
+            timezone: None,
+            day_start_hour: 0,
+            ics_feed_dir: None,
         }
     }
 }
@@ -65,4 +75,38 @@ pub struct SettingsConfigForUpdate {
     pub main_minimize_to_tray: Option<bool>,
     pub main_always_on_top: Option<bool>,
     pub main_display_on_timer_complete: Option<bool>,
+    /// `null` resets the timezone back to the system one, a missing field keeps it as is.
+    #[serde(default, deserialize_with = "deserialize_some")]
+    #[ts(type = "string | null")]
+    pub timezone: Option<Option<String>>,
+    pub day_start_hour: Option<u32>,
+}
+
+/// Wraps present values, `null` included, so that they can be told apart from missing fields.
+fn deserialize_some<'de, T, D>(deserializer: D) -> std::result::Result<Option<T>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    T::deserialize(deserializer).map(Some)
+}
+
+#[cfg(test)]
+mod settings_tests {
+    use super::*;
+
+    #[test]
+    fn test_timezone_update() {
+        let update: SettingsConfigForUpdate = serde_json::from_str("{}").unwrap();
+        assert_eq!(update.timezone, None);
+        let update: SettingsConfigForUpdate =
+            serde_json::from_str(r#"{"timezone":"Europe/Berlin"}"#).unwrap();
+        assert_eq!(update.timezone, Some(Some("Europe/Berlin".to_string())));
+
+        // Resetting serializes `null`, which overrides the configured timezone on merge.
+        let update: SettingsConfigForUpdate = serde_json::from_str(r#"{"timezone":null}"#).unwrap();
+        assert_eq!(update.timezone, Some(None));
+        let value = serde_json::to_value(&update).unwrap();
+        assert_eq!(value, serde_json::json!({ "timezone": null }));
+    }
 }
@@ -8,6 +8,18 @@ use crate::prelude::Minutes;
 
 use super::Config;
 
+/// Decides what happens once a focus session reaches its planned length.
+#[derive(Serialize, Deserialize, TS, Debug, Clone, PartialEq)]
+#[ts(export, export_to = "../src/bindings/")]
+pub enum FocusMode {
+    /// Switch over to a break right away.
+    Fixed,
+    /// Keep counting into overtime until the user ends the session.
+    Overtime,
+    /// Like `Overtime`, with the break length derived from the focus time spent.
+    Flowtime,
+}
+
 #[derive(Serialize, Deserialize, TS, Debug, Clone)]
 #[ts(export, export_to = "../src/bindings/")]
 pub struct TimerConfig {
@@ -26,6 +38,23 @@ pub struct TimerConfig {
     pub auto_start_focus: bool,
     pub auto_start_breaks: bool,
     pub session_summary: bool,
+
+    pub focus_mode: FocusMode,
+
+    /// Minutes of focus per minute of break under `FocusMode::Flowtime`.
+    #[ts(type = "number")]
+    pub flowtime_break_ratio: i64,
+
+    /// Daily focus goal, `0` disables it.
+    #[ts(type = "number")]
+    pub daily_goal: Minutes,
+
+    /// Weekly focus goal, `0` disables it.
+    #[ts(type = "number")]
+    pub weekly_goal: Minutes,
+
+    /// Weekdays which do not break the focus streak, `0` being Monday.
+    pub rest_weekdays: Vec<u32>,
 }
 
 impl Config for TimerConfig {
@@ -44,6 +73,11 @@ impl Default for TimerConfig {
             auto_start_focus: false,
             auto_start_breaks: false,
             session_summary: true,
+            focus_mode: FocusMode::Fixed,
+            flowtime_break_ratio: 5,
+            daily_goal: 0,
+            weekly_goal: 0,
+            rest_weekdays: vec![],
         }
     }
 }
@@ -67,4 +101,17 @@ pub struct TimerConfigForUpdate {
     pub auto_start_focus: Option<bool>,
     pub auto_start_breaks: Option<bool>,
     pub session_summary: Option<bool>,
+
+    pub focus_mode: Option<FocusMode>,
+
+    #[ts(type = "number")]
+    pub flowtime_break_ratio: Option<i64>,
+
+    #[ts(type = "number")]
+    pub daily_goal: Option<i64>,
+
+    #[ts(type = "number")]
+    pub weekly_goal: Option<i64>,
+
+    pub rest_weekdays: Option<Vec<u32>>,
 }
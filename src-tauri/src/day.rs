@@ -0,0 +1,169 @@
+//! Maps UTC timestamps onto the calendar days they count towards.
+
+use chrono::{Datelike, Duration, Local, NaiveDate, NaiveDateTime, TimeZone, Timelike, Utc};
+use chrono_tz::Tz;
+
+use crate::config::SettingsConfig;
+
+/// Day boundary defined by the configured timezone and the hour at which a day starts.
+#[derive(Debug, Clone)]
+pub struct DayBoundary {
+    timezone: Option<Tz>,
+    day_start_hour: u32,
+}
+
+impl DayBoundary {
+    /// Falls back to the system timezone when `timezone` is missing or not a valid IANA name.
+    pub fn new(timezone: Option<&str>, day_start_hour: u32) -> Self {
+        Self {
+            timezone: timezone.and_then(|timezone| timezone.parse().ok()),
+            day_start_hour: day_start_hour.min(23),
+        }
+    }
+
+    pub fn from_settings(config: &SettingsConfig) -> Self {
+        Self::new(config.timezone.as_deref(), config.day_start_hour)
+    }
+
+    /// Day that the given timestamp counts towards.
+    pub fn day_of(&self, timestamp: i64) -> NaiveDate {
+        let local = self.to_local(timestamp);
+        (local - Duration::hours(self.day_start_hour as i64)).date()
+    }
+
+    /// Day that the current moment counts towards.
+    pub fn today(&self) -> NaiveDate {
+        self.day_of(Utc::now().timestamp())
+    }
+
+    /// Monday and Sunday of the ISO week the given day belongs to.
+    pub fn week_of(day: NaiveDate) -> (NaiveDate, NaiveDate) {
+        let monday = day - Duration::days(day.weekday().num_days_from_monday() as i64);
+        (monday, monday + Duration::days(6))
+    }
+
+    /// Timestamp at which the given day starts.
+    pub fn start_of(&self, day: NaiveDate) -> i64 {
+        let local = day.and_hms_opt(self.day_start_hour, 0, 0).unwrap();
+        self.to_utc(local)
+    }
+
+    /// Half open `[start, end)` timestamp range covering the given day.
+    pub fn range_of(&self, day: NaiveDate) -> (i64, i64) {
+        (self.start_of(day), self.start_of(day + Duration::days(1)))
+    }
+
+    /// Timestamp at which the day following the given timestamp's day starts.
+    pub fn next_day_start(&self, timestamp: i64) -> i64 {
+        let next = self.start_of(self.day_of(timestamp) + Duration::days(1));
+        // Guard against DST transitions resolving to the same instant.
+        next.max(timestamp + 1)
+    }
+
+    /// Timestamp at which the next local wall clock hour starts.
+    pub fn next_hour_start(&self, timestamp: i64) -> i64 {
+        let local = self.to_local(timestamp);
+        let hour_start = local.date().and_hms_opt(local.hour(), 0, 0).unwrap();
+        let next = self.to_utc(hour_start + Duration::hours(1));
+        next.max(timestamp + 1)
+    }
+
+    /// Local weekday, `0` being Monday, and hour of the given timestamp.
+    pub fn weekday_hour_of(&self, timestamp: i64) -> (u32, u32) {
+        let local = self.to_local(timestamp);
+        (local.weekday().num_days_from_monday(), local.hour())
+    }
+
+    /// Local wall clock time of the given timestamp.
+    pub fn to_local(&self, timestamp: i64) -> NaiveDateTime {
+        let utc = Utc.timestamp_opt(timestamp, 0).unwrap();
+        match self.timezone {
+            Some(tz) => utc.with_timezone(&tz).naive_local(),
+            None => utc.with_timezone(&Local).naive_local(),
+        }
+    }
+
+    /// Timestamp of the given local wall clock time.
+    pub fn to_utc(&self, local: NaiveDateTime) -> i64 {
+        // Local times skipped by a DST transition are pushed forward by an hour.
+        let resolve = |local: NaiveDateTime| match self.timezone {
+            Some(tz) => tz
+                .from_local_datetime(&local)
+                .earliest()
+                .map(|t| t.timestamp()),
+            None => Local
+                .from_local_datetime(&local)
+                .earliest()
+                .map(|t| t.timestamp()),
+        };
+        resolve(local)
+            .or_else(|| resolve(local + Duration::hours(1)))
+            .unwrap_or_else(|| local.timestamp())
+    }
+}
+
+#[cfg(test)]
+mod day_boundary_tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_day_of_in_timezone() {
+        let boundary = DayBoundary::new(Some("Europe/Warsaw"), 0);
+
+        // 2023-07-10 22:30 UTC is already 2023-07-11 in Warsaw (UTC+2).
+        assert_eq!(boundary.day_of(1689028200), date(2023, 7, 11));
+    }
+
+    #[test]
+    fn test_day_of_before_day_start_hour() {
+        let boundary = DayBoundary::new(Some("UTC"), 4);
+
+        // 2023-07-11 02:00 UTC counts towards the previous day.
+        assert_eq!(boundary.day_of(1689040800), date(2023, 7, 10));
+        // 2023-07-11 04:00 UTC starts a new day.
+        assert_eq!(boundary.day_of(1689048000), date(2023, 7, 11));
+    }
+
+    #[test]
+    fn test_range_of_day() {
+        let boundary = DayBoundary::new(Some("Europe/Warsaw"), 4);
+        let (start, end) = boundary.range_of(date(2023, 7, 11));
+
+        // 2023-07-11 04:00 in Warsaw is 02:00 UTC.
+        assert_eq!(start, 1689040800);
+        assert_eq!(end - start, 24 * 60 * 60);
+    }
+
+    #[test]
+    fn test_week_of_day() {
+        // 2023-07-13 is a Thursday.
+        let (monday, sunday) = DayBoundary::week_of(date(2023, 7, 13));
+
+        assert_eq!(monday, date(2023, 7, 10));
+        assert_eq!(sunday, date(2023, 7, 16));
+    }
+
+    #[test]
+    fn test_next_boundaries() {
+        let boundary = DayBoundary::new(Some("Asia/Kolkata"), 4);
+        // 2023-07-10 20:15 UTC is 2023-07-11 01:45 in Kolkata (UTC+5:30).
+        let timestamp = 1689020100;
+
+        // Next day starts at 2023-07-11 04:00 local, 2023-07-10 22:30 UTC.
+        assert_eq!(boundary.next_day_start(timestamp), 1689028200);
+        // Next hour starts at 02:00 local, 20:30 UTC.
+        assert_eq!(boundary.next_hour_start(timestamp), 1689021000);
+        assert_eq!(boundary.weekday_hour_of(timestamp), (1, 1));
+    }
+
+    #[test]
+    fn test_invalid_timezone_falls_back_to_local() {
+        let boundary = DayBoundary::new(Some("Not/AZone"), 0);
+
+        assert!(boundary.timezone.is_none());
+    }
+}
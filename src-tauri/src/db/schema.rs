@@ -15,6 +15,21 @@ diesel::table! {
         pinned -> Bool,
         created_at -> BigInt,
         archived_at -> Nullable<BigInt>,
+        parent_id -> Nullable<Integer>,
+        target_period -> Text,
+        target -> Nullable<BigInt>,
+        budget -> Nullable<BigInt>,
+        color -> Nullable<Text>,
+        icon -> Nullable<Text>,
+        description -> Nullable<Text>,
+        position -> Integer,
+    }
+}
+
+diesel::table! {
+    rest_days (id) {
+        id -> Integer,
+        day -> Date,
     }
 }
 
@@ -33,6 +48,15 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    session_pauses (id) {
+        id -> Integer,
+        session_id -> Integer,
+        started_at -> BigInt,
+        finished_at -> BigInt,
+    }
+}
+
 diesel::table! {
     sessions (id) {
         id -> Integer,
@@ -41,6 +65,9 @@ diesel::table! {
         started_at -> BigInt,
         finished_at -> BigInt,
         intent_id -> Integer,
+        session_type -> Text,
+        planned_duration -> Nullable<Integer>,
+        is_manual -> Bool,
     }
 }
 
@@ -59,6 +86,21 @@ diesel::table! {
         created_at -> BigInt,
         finished_at -> Nullable<BigInt>,
         intent_id -> Integer,
+        position -> Integer,
+        priority -> Integer,
+        due_date -> Nullable<Date>,
+        parent_task_id -> Nullable<Integer>,
+        estimated_pomodoros -> Nullable<Integer>,
+        recurrence -> Nullable<Text>,
+        series_id -> Nullable<Integer>,
+    }
+}
+
+diesel::table! {
+    task_sessions (id) {
+        id -> Integer,
+        task_id -> Integer,
+        session_id -> Integer,
     }
 }
 
@@ -77,15 +119,21 @@ diesel::table! {
 
 diesel::joinable!(intent_tags -> intents (intent_id));
 diesel::joinable!(intent_tags -> tags (tag_id));
+diesel::joinable!(session_pauses -> sessions (session_id));
 diesel::joinable!(sessions -> intents (intent_id));
+diesel::joinable!(task_sessions -> sessions (session_id));
+diesel::joinable!(task_sessions -> tasks (task_id));
 diesel::joinable!(tasks -> intents (intent_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
     intent_tags,
     intents,
+    rest_days,
     scripts,
+    session_pauses,
     sessions,
     tags,
+    task_sessions,
     tasks,
     themes,
 );
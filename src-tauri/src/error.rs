@@ -1,7 +1,7 @@
 //! This is the main (and only for now) application Error type.
 //! It's using 'thiserror' as it reduces boilerplate error code while providing rich error typing.
 
-use crate::timer::TimerError;
+use crate::{bmc::BmcError, export::ExportError, import::ImportError, timer::TimerError};
 
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
@@ -19,6 +19,15 @@ pub enum Error {
 
     #[error(transparent)]
     TimerError(#[from] TimerError),
+
+    #[error(transparent)]
+    BmcError(#[from] BmcError),
+
+    #[error(transparent)]
+    ExportError(#[from] ExportError),
+
+    #[error(transparent)]
+    ImportError(#[from] ImportError),
 }
 
 impl serde::Serialize for Error {
@@ -0,0 +1,526 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel::SqliteConnection;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    bmc::BaseBmc,
+    config::{ConfigManager, QueueConfig, SettingsConfig, TimerConfig},
+    models::{
+        Intent, IntentTag, RestDay, Script, Session, SessionPause, Tag, Task, TaskSession, Theme,
+    },
+    prelude::{Error, Result},
+};
+
+use super::ExportError;
+
+/// Version of the backup format, bump it whenever the layout of `Backup` changes in a way
+/// older backups can't be read with. Fields added later are `#[serde(default)]`, so that
+/// backups written before they existed still restore under the same version.
+pub const BACKUP_VERSION: u32 = 1;
+
+/// The JSON configs managed by `ConfigManager` which are stored along the database.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BackupConfigs {
+    pub timer: TimerConfig,
+    pub settings: SettingsConfig,
+    pub queue: QueueConfig,
+}
+
+/// Complete snapshot of the app's data, every database table and config file.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Backup {
+    pub version: u32,
+    pub created_at: i64,
+    pub intents: Vec<Intent>,
+    pub tags: Vec<Tag>,
+    pub intent_tags: Vec<IntentTag>,
+    pub sessions: Vec<Session>,
+    pub session_pauses: Vec<SessionPause>,
+    pub tasks: Vec<Task>,
+    #[serde(default)]
+    pub task_sessions: Vec<TaskSession>,
+    pub scripts: Vec<Script>,
+    pub themes: Vec<Theme>,
+    pub rest_days: Vec<RestDay>,
+    #[serde(flatten)]
+    pub configs: BackupConfigs,
+}
+
+/// Collects every table of the database into a backup.
+pub fn create_backup(conn: &mut SqliteConnection, configs: BackupConfigs) -> Result<Backup> {
+    use crate::db::schema::{
+        intent_tags, intents, rest_days, scripts, session_pauses, sessions, tags, task_sessions,
+        tasks, themes,
+    };
+
+    Ok(Backup {
+        version: BACKUP_VERSION,
+        created_at: Utc::now().timestamp(),
+        intents: intents::table.order(intents::id.asc()).load(conn)?,
+        tags: tags::table.order(tags::id.asc()).load(conn)?,
+        intent_tags: intent_tags::table.order(intent_tags::id.asc()).load(conn)?,
+        sessions: sessions::table.order(sessions::id.asc()).load(conn)?,
+        session_pauses: session_pauses::table
+            .order(session_pauses::id.asc())
+            .load(conn)?,
+        tasks: tasks::table.order(tasks::id.asc()).load(conn)?,
+        task_sessions: task_sessions::table
+            .order(task_sessions::id.asc())
+            .load(conn)?,
+        scripts: scripts::table.order(scripts::id.asc()).load(conn)?,
+        themes: themes::table.order(themes::id.asc()).load(conn)?,
+        rest_days: rest_days::table.order(rest_days::id.asc()).load(conn)?,
+        configs,
+    })
+}
+
+/// Writes backup to disk as pretty printed JSON.
+pub fn write_backup(path: &PathBuf, backup: &Backup) -> Result<()> {
+    ConfigManager::write_json(path, backup)
+}
+
+/// Reads backup from disk, the version is checked before the rest of the archive is parsed.
+pub fn read_backup(path: &PathBuf) -> Result<Backup> {
+    let value = ConfigManager::read_json(path)?;
+    let version = value.get("version").and_then(|version| version.as_u64());
+    if version != Some(BACKUP_VERSION as u64) {
+        return Err(ExportError::UnsupportedBackupVersion.into());
+    }
+    let backup = serde_json::from_value(value)?;
+    Ok(backup)
+}
+
+/// Replaces all data in the database with the backup's content in a single transaction.
+/// Rows get new ids, references between tables and the theme ids in the settings
+/// are rewritten accordingly. Returns the configs which should be saved by the caller.
+pub fn restore_backup(conn: &mut SqliteConnection, backup: &Backup) -> Result<BackupConfigs> {
+    use crate::db::schema::{
+        intent_tags, intents, rest_days, scripts, session_pauses, sessions, tags, task_sessions,
+        tasks, themes,
+    };
+
+    if backup.version != BACKUP_VERSION {
+        return Err(ExportError::UnsupportedBackupVersion.into());
+    }
+
+    conn.transaction::<_, Error, _>(|conn| {
+        diesel::delete(intent_tags::table).execute(conn)?;
+        diesel::delete(task_sessions::table).execute(conn)?;
+        diesel::delete(session_pauses::table).execute(conn)?;
+        diesel::delete(sessions::table).execute(conn)?;
+        diesel::delete(tasks::table).execute(conn)?;
+        diesel::delete(intents::table).execute(conn)?;
+        diesel::delete(tags::table).execute(conn)?;
+        diesel::delete(scripts::table).execute(conn)?;
+        diesel::delete(themes::table).execute(conn)?;
+        diesel::delete(rest_days::table).execute(conn)?;
+
+        let mut intent_ids = HashMap::new();
+        for intent in &backup.intents {
+            diesel::insert_into(intents::table)
+                .values((
+                    intents::label.eq(&intent.label),
+                    intents::pinned.eq(intent.pinned),
+                    intents::created_at.eq(intent.created_at),
+                    intents::archived_at.eq(intent.archived_at),
+                    intents::target_period.eq(intent.target_period),
+                    intents::target.eq(intent.target),
+                    intents::budget.eq(intent.budget),
+                    intents::color.eq(&intent.color),
+                    intents::icon.eq(&intent.icon),
+                    intents::description.eq(&intent.description),
+                    intents::position.eq(intent.position),
+                ))
+                .execute(conn)?;
+            intent_ids.insert(intent.id, BaseBmc::get_last_insert_id(conn)?);
+        }
+        // Parents may come after their children, so they are linked once all ids are known.
+        for intent in &backup.intents {
+            let Some(parent_id) = intent.parent_id else {
+                continue;
+            };
+            diesel::update(intents::table.find(intent_ids[&intent.id]))
+                .set(intents::parent_id.eq(intent_ids.get(&parent_id)))
+                .execute(conn)?;
+        }
+
+        let mut tag_ids = HashMap::new();
+        for tag in &backup.tags {
+            diesel::insert_into(tags::table)
+                .values(tags::label.eq(&tag.label))
+                .execute(conn)?;
+            tag_ids.insert(tag.id, BaseBmc::get_last_insert_id(conn)?);
+        }
+
+        for intent_tag in &backup.intent_tags {
+            diesel::insert_into(intent_tags::table)
+                .values((
+                    intent_tags::intent_id.eq(remap(&intent_ids, intent_tag.intent_id)?),
+                    intent_tags::tag_id.eq(remap(&tag_ids, intent_tag.tag_id)?),
+                ))
+                .execute(conn)?;
+        }
+
+        let mut session_ids = HashMap::new();
+        for session in &backup.sessions {
+            diesel::insert_into(sessions::table)
+                .values((
+                    sessions::duration.eq(session.duration),
+                    sessions::summary.eq(&session.summary),
+                    sessions::started_at.eq(session.started_at),
+                    sessions::finished_at.eq(session.finished_at),
+                    sessions::intent_id.eq(remap(&intent_ids, session.intent_id)?),
+                    sessions::session_type.eq(&session.session_type),
+                    sessions::planned_duration.eq(session.planned_duration),
+                    sessions::is_manual.eq(session.is_manual),
+                ))
+                .execute(conn)?;
+            session_ids.insert(session.id, BaseBmc::get_last_insert_id(conn)?);
+        }
+
+        for pause in &backup.session_pauses {
+            diesel::insert_into(session_pauses::table)
+                .values((
+                    session_pauses::session_id.eq(remap(&session_ids, pause.session_id)?),
+                    session_pauses::started_at.eq(pause.started_at),
+                    session_pauses::finished_at.eq(pause.finished_at),
+                ))
+                .execute(conn)?;
+        }
+
+        let mut task_ids = HashMap::new();
+        for task in &backup.tasks {
+            diesel::insert_into(tasks::table)
+                .values((
+                    tasks::body.eq(&task.body),
+                    tasks::completed.eq(task.completed),
+                    tasks::created_at.eq(task.created_at),
+                    tasks::finished_at.eq(task.finished_at),
+                    tasks::intent_id.eq(remap(&intent_ids, task.intent_id)?),
+                    tasks::position.eq(task.position),
+                    tasks::priority.eq(task.priority),
+                    tasks::due_date.eq(task.due_date),
+                    tasks::estimated_pomodoros.eq(task.estimated_pomodoros),
+                    tasks::recurrence.eq(&task.recurrence),
+                ))
+                .execute(conn)?;
+            task_ids.insert(task.id, BaseBmc::get_last_insert_id(conn)?);
+        }
+        for task in &backup.tasks {
+            if task.parent_task_id.is_none() && task.series_id.is_none() {
+                continue;
+            }
+            let remap_task = |id: Option<i32>| id.and_then(|id| task_ids.get(&id));
+            diesel::update(tasks::table.find(task_ids[&task.id]))
+                .set((
+                    tasks::parent_task_id.eq(remap_task(task.parent_task_id)),
+                    tasks::series_id.eq(remap_task(task.series_id)),
+                ))
+                .execute(conn)?;
+        }
+
+        for task_session in &backup.task_sessions {
+            diesel::insert_into(task_sessions::table)
+                .values((
+                    task_sessions::task_id.eq(remap(&task_ids, task_session.task_id)?),
+                    task_sessions::session_id.eq(remap(&session_ids, task_session.session_id)?),
+                ))
+                .execute(conn)?;
+        }
+
+        for script in &backup.scripts {
+            diesel::insert_into(scripts::table)
+                .values((
+                    scripts::label.eq(&script.label),
+                    scripts::body.eq(&script.body),
+                    scripts::enabled.eq(script.enabled),
+                    scripts::exec_on_session_start.eq(script.exec_on_session_start),
+                    scripts::exec_on_session_pause.eq(script.exec_on_session_pause),
+                    scripts::exec_on_session_complete.eq(script.exec_on_session_complete),
+                    scripts::exec_on_break_start.eq(script.exec_on_break_start),
+                    scripts::exec_on_break_pause.eq(script.exec_on_break_pause),
+                    scripts::exec_on_break_complete.eq(script.exec_on_break_complete),
+                ))
+                .execute(conn)?;
+        }
+
+        let mut theme_ids = HashMap::new();
+        for theme in &backup.themes {
+            diesel::insert_into(themes::table)
+                .values((
+                    themes::label.eq(&theme.label),
+                    themes::favorite.eq(theme.favorite),
+                    themes::window_hex.eq(&theme.window_hex),
+                    themes::base_hex.eq(&theme.base_hex),
+                    themes::text_hex.eq(&theme.text_hex),
+                    themes::primary_hex.eq(&theme.primary_hex),
+                    themes::variant.eq(&theme.variant),
+                ))
+                .execute(conn)?;
+            theme_ids.insert(theme.id, BaseBmc::get_last_insert_id(conn)?);
+        }
+
+        for rest_day in &backup.rest_days {
+            diesel::insert_into(rest_days::table)
+                .values(rest_days::day.eq(rest_day.day))
+                .execute(conn)?;
+        }
+
+        let mut configs = backup.configs.clone();
+        let settings = &mut configs.settings;
+        for theme_id in [
+            &mut settings.idle_theme_id,
+            &mut settings.focus_theme_id,
+            &mut settings.break_theme_id,
+            &mut settings.long_break_theme_id,
+        ] {
+            // Settings pointing to a theme which no longer exists are left as they were.
+            *theme_id = theme_ids.get(theme_id).copied().unwrap_or(*theme_id);
+        }
+        configs.queue.remap_intents(&intent_ids);
+
+        Ok(configs)
+    })
+}
+
+/// Saves restored configs to disk, should only be called once the database restore succeeded.
+pub fn save_backup_configs(configs: &BackupConfigs) -> Result<()> {
+    ConfigManager::save(&configs.timer)?;
+    ConfigManager::save(&configs.settings)?;
+    ConfigManager::save(&configs.queue)?;
+    Ok(())
+}
+
+fn remap(ids: &HashMap<i32, i32>, id: i32) -> Result<i32> {
+    ids.get(&id)
+        .copied()
+        .ok_or_else(|| ExportError::InvalidBackup.into())
+}
+
+#[cfg(test)]
+mod backup_tests {
+    use chrono::NaiveDate;
+
+    use crate::{
+        bmc::{
+            IntentBmc, IntentTagBmc, RestDayBmc, ScriptBmc, SessionBmc, SessionPauseBmc, TagBmc,
+            TaskBmc, ThemeBmc,
+        },
+        db::Db,
+        models::{
+            CreateIntent, CreateIntentTag, CreateRestDay, CreateScript, CreateSession,
+            CreateSessionPause, CreateTag, CreateTask, TaskPriority,
+        },
+        timer::SessionType,
+    };
+
+    use super::*;
+
+    fn default_configs() -> BackupConfigs {
+        BackupConfigs {
+            timer: TimerConfig::default(),
+            settings: SettingsConfig::default(),
+            queue: QueueConfig::default(),
+        }
+    }
+
+    /// Fills the database with a bit of everything, the first intent and theme are removed
+    /// so the ids in the backup don't start at 1.
+    fn seed(conn: &mut SqliteConnection) {
+        ThemeBmc::create_default_themes(conn).unwrap();
+        ThemeBmc::delete(conn, 1).unwrap();
+        let removed_id = IntentBmc::create(
+            conn,
+            &CreateIntent {
+                label: "removed".to_string(),
+                parent_id: None,
+            },
+        )
+        .unwrap();
+        let intent_id = IntentBmc::create(
+            conn,
+            &CreateIntent {
+                label: "foo".to_string(),
+                parent_id: None,
+            },
+        )
+        .unwrap();
+        IntentBmc::delete(conn, removed_id).unwrap();
+        let tag_id = TagBmc::create(
+            conn,
+            &CreateTag {
+                label: "bar".to_string(),
+            },
+        )
+        .unwrap();
+        IntentTagBmc::create(conn, &CreateIntentTag { intent_id, tag_id }).unwrap();
+        let session_id = SessionBmc::create(
+            conn,
+            &CreateSession {
+                duration: 1500,
+                started_at: 1688947200,
+                intent_id,
+                session_type: SessionType::Focus,
+                planned_duration: Some(1500),
+            },
+        )
+        .unwrap();
+        SessionPauseBmc::create(
+            conn,
+            &CreateSessionPause {
+                session_id,
+                started_at: 1688947800,
+                finished_at: 1688947860,
+            },
+        )
+        .unwrap();
+        let task_id = TaskBmc::create(
+            conn,
+            &CreateTask {
+                body: "baz".to_string(),
+                intent_id,
+                priority: TaskPriority::Normal,
+                due_date: None,
+                parent_task_id: None,
+            },
+        )
+        .unwrap();
+        TaskBmc::set_estimate(conn, task_id, Some(2)).unwrap();
+        TaskBmc::link_session(conn, task_id, session_id).unwrap();
+        ScriptBmc::create(
+            conn,
+            &CreateScript {
+                label: "dummy.sh".to_string(),
+                body: "<bash code here>".to_string(),
+            },
+        )
+        .unwrap();
+        RestDayBmc::create(
+            conn,
+            &CreateRestDay {
+                day: NaiveDate::from_ymd_opt(2023, 7, 10).unwrap(),
+            },
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_backup_roundtrip() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        seed(&mut conn);
+        let backup = create_backup(&mut conn, default_configs()).unwrap();
+        assert_eq!(backup.version, BACKUP_VERSION);
+        assert_eq!(backup.intents[0].id, 2);
+
+        // Go through JSON like a backup file on disk would.
+        let json = serde_json::to_string(&backup).unwrap();
+        let backup: Backup = serde_json::from_str(&json).unwrap();
+
+        let mut conn = Db::establish_test_connection().unwrap();
+        let configs = restore_backup(&mut conn, &backup).unwrap();
+
+        let intents = IntentBmc::get_flat_list(&mut conn).unwrap();
+        assert_eq!(intents.len(), 1);
+        let intent_id = intents[0].id;
+        assert_eq!(intent_id, 1);
+        let tags = IntentBmc::get_tags(&mut conn, intent_id).unwrap();
+        assert_eq!(tags[0].label, "bar");
+
+        let sessions = SessionBmc::get_list(&mut conn, None).unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].intent_id, intent_id);
+        assert_eq!(sessions[0].started_at, 1688947200);
+        let pauses = SessionPauseBmc::get_list_by_sessions(&mut conn, &[sessions[0].id]).unwrap();
+        assert_eq!(pauses.len(), 1);
+
+        let tasks = TaskBmc::get_list(&mut conn, None).unwrap();
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].intent_id, intent_id);
+        let estimate = TaskBmc::get_estimate(&mut conn, tasks[0].id, 25).unwrap();
+        assert_eq!(estimate.estimated_pomodoros, Some(2));
+        assert_eq!(estimate.actual_pomodoros, 1);
+        assert_eq!(ScriptBmc::get_list(&mut conn).unwrap().len(), 1);
+        assert_eq!(RestDayBmc::get_list(&mut conn).unwrap().len(), 1);
+        assert_eq!(ThemeBmc::get_list(&mut conn).unwrap().len(), 5);
+
+        // Themes 2 to 6 were restored as 1 to 5, the missing theme 1 is kept as is.
+        assert_eq!(configs.settings.idle_theme_id, 1);
+        assert_eq!(configs.settings.focus_theme_id, 1);
+        assert_eq!(configs.settings.break_theme_id, 2);
+        assert_eq!(configs.settings.long_break_theme_id, 3);
+    }
+
+    #[test]
+    fn test_restore_v1_backup() {
+        let backup: Backup = serde_json::from_str(include_str!("fixtures/backup_v1.json")).unwrap();
+        assert_eq!(backup.version, BACKUP_VERSION);
+        assert!(backup.task_sessions.is_empty());
+        assert!(backup.configs.settings.ics_feed_dir.is_none());
+
+        let mut conn = Db::establish_test_connection().unwrap();
+        let configs = restore_backup(&mut conn, &backup).unwrap();
+        assert_eq!(configs.settings.focus_theme_id, 1);
+
+        let intents = IntentBmc::get_flat_list(&mut conn).unwrap();
+        assert_eq!(intents.len(), 1);
+        assert_eq!(intents[0].label, "foo");
+        assert!(intents[0].parent_id.is_none());
+        let tasks = TaskBmc::get_list(&mut conn, None).unwrap();
+        assert_eq!(tasks.len(), 1);
+        assert!(tasks[0].completed);
+        assert_eq!(tasks[0].priority, TaskPriority::Normal);
+        assert!(tasks[0].parent_task_id.is_none() && tasks[0].recurrence.is_none());
+        let sessions = SessionBmc::get_list(&mut conn, None).unwrap();
+        assert_eq!(sessions[0].summary.as_deref(), Some("wrote the parser"));
+
+        // Backing the restored data up again gives a current backup with the same content.
+        let backup = create_backup(&mut conn, configs).unwrap();
+        let json = serde_json::to_string(&backup).unwrap();
+        let backup: Backup = serde_json::from_str(&json).unwrap();
+        assert_eq!(backup.intents.len(), 1);
+        assert_eq!(backup.tasks[0].body, "baz");
+        assert_eq!(backup.sessions[0].intent_id, backup.intents[0].id);
+        assert_eq!(backup.session_pauses.len(), 1);
+        assert_eq!(backup.intent_tags.len(), 1);
+        assert_eq!(backup.scripts.len(), 1);
+        assert_eq!(backup.themes.len(), 1);
+        assert_eq!(backup.rest_days.len(), 1);
+    }
+
+    #[test]
+    fn test_restore_unsupported_version() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let mut backup = create_backup(&mut conn, default_configs()).unwrap();
+        backup.version = BACKUP_VERSION + 1;
+
+        let result = restore_backup(&mut conn, &backup);
+        assert!(matches!(
+            result,
+            Err(Error::ExportError(ExportError::UnsupportedBackupVersion))
+        ));
+    }
+
+    #[test]
+    fn test_failed_restore_leaves_db_untouched() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        seed(&mut conn);
+        let mut backup = create_backup(&mut conn, default_configs()).unwrap();
+        // Break the backup, the session points to an intent which is not in it.
+        backup.sessions[0].intent_id = 999;
+        backup.intents[0].label = "changed".to_string();
+
+        let result = restore_backup(&mut conn, &backup);
+        assert!(matches!(
+            result,
+            Err(Error::ExportError(ExportError::InvalidBackup))
+        ));
+
+        let intents = IntentBmc::get_flat_list(&mut conn).unwrap();
+        assert_eq!(intents.len(), 1);
+        assert_eq!(intents[0].label, "foo");
+        assert_eq!(ThemeBmc::get_list(&mut conn).unwrap().len(), 5);
+    }
+}
@@ -0,0 +1,281 @@
+use std::{
+    collections::HashMap,
+    io,
+    path::{Path, PathBuf},
+};
+
+use diesel::SqliteConnection;
+
+use crate::{
+    bmc::{GetSessionsOptions, GetTasksOptions, IntentBmc, SessionBmc, TaskBmc, TaskSortBy},
+    day::DayBoundary,
+    prelude::Result,
+};
+
+use super::ExportOptions;
+
+const DATETIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// Writes `sessions.csv`, `tasks.csv` and `intents.csv` into `dir`, returns the written paths.
+/// Dates are local to the configured timezone, the date range filters sessions by their
+/// start and tasks by their creation.
+pub fn export_csv(
+    conn: &mut SqliteConnection,
+    dir: &Path,
+    options: &ExportOptions,
+    boundary: &DayBoundary,
+) -> Result<Vec<PathBuf>> {
+    let started_from = options.from.map(|day| boundary.start_of(day));
+    let started_to = options.to.map(|day| boundary.range_of(day).1);
+    let in_range = |timestamp: i64| {
+        started_from.map_or(true, |from| timestamp >= from)
+            && started_to.map_or(true, |to| timestamp < to)
+    };
+    let format_timestamp = |timestamp: i64| {
+        boundary
+            .to_local(timestamp)
+            .format(DATETIME_FORMAT)
+            .to_string()
+    };
+
+    let intents: Vec<_> = IntentBmc::get_flat_list(conn)?
+        .into_iter()
+        .filter(|intent| options.intent_id.map_or(true, |id| intent.id == id))
+        .collect();
+    let mut labels = HashMap::new();
+    let mut tags = HashMap::new();
+    for intent in &intents {
+        let intent_tags: Vec<_> = IntentBmc::get_tags(conn, intent.id)?
+            .into_iter()
+            .map(|tag| tag.label)
+            .collect();
+        labels.insert(intent.id, intent.label.clone());
+        tags.insert(intent.id, intent_tags.join("; "));
+    }
+
+    let session_options = GetSessionsOptions {
+        intent_id: options.intent_id,
+        session_types: None,
+        include_manual: None,
+        day: None,
+        started_from,
+        started_to,
+        offset: None,
+        limit: None,
+    };
+    let mut sessions = SessionBmc::get_list(conn, Some(session_options))?;
+    sessions.sort_by_key(|session| session.started_at);
+    let session_rows = sessions.iter().map(|session| {
+        vec![
+            session.id.to_string(),
+            boundary.day_of(session.started_at).to_string(),
+            format_timestamp(session.started_at),
+            format_timestamp(session.finished_at),
+            format!("{:.2}", session.duration as f64 / 60.0),
+            labels.get(&session.intent_id).cloned().unwrap_or_default(),
+            tags.get(&session.intent_id).cloned().unwrap_or_default(),
+            session.summary.clone().unwrap_or_default(),
+            session.is_manual.to_string(),
+        ]
+    });
+
+    let task_options = GetTasksOptions {
+        intent_id: options.intent_id,
+        offset: None,
+        limit: None,
+        completed: None,
+        sort_by: Some(TaskSortBy::CreatedAt),
+        due: None,
+        today: None,
+    };
+    let tasks = TaskBmc::get_list(conn, Some(task_options))?;
+    let task_rows = tasks
+        .iter()
+        .filter(|task| in_range(task.created_at))
+        .map(|task| {
+            vec![
+                task.id.to_string(),
+                labels.get(&task.intent_id).cloned().unwrap_or_default(),
+                task.body.clone(),
+                task.completed.to_string(),
+                format_timestamp(task.created_at),
+                task.finished_at.map(format_timestamp).unwrap_or_default(),
+                format!("{:?}", task.priority),
+                task.due_date.map(|day| day.to_string()).unwrap_or_default(),
+            ]
+        });
+
+    let intent_rows = intents.iter().map(|intent| {
+        vec![
+            intent.id.to_string(),
+            intent.label.clone(),
+            tags.get(&intent.id).cloned().unwrap_or_default(),
+            intent.pinned.to_string(),
+            format_timestamp(intent.created_at),
+            intent.archived_at.map(format_timestamp).unwrap_or_default(),
+        ]
+    });
+
+    let files = [
+        (
+            "sessions.csv",
+            vec![
+                "id",
+                "date",
+                "start",
+                "end",
+                "duration_minutes",
+                "intent",
+                "tags",
+                "summary",
+                "manual",
+            ],
+            session_rows.collect::<Vec<_>>(),
+        ),
+        (
+            "tasks.csv",
+            vec![
+                "id",
+                "intent",
+                "body",
+                "completed",
+                "created_at",
+                "finished_at",
+                "priority",
+                "due_date",
+            ],
+            task_rows.collect(),
+        ),
+        (
+            "intents.csv",
+            vec!["id", "label", "tags", "pinned", "created_at", "archived_at"],
+            intent_rows.collect(),
+        ),
+    ];
+
+    let mut paths = vec![];
+    for (name, header, rows) in files {
+        let path = dir.join(name);
+        write_csv(&path, &header, &rows).map_err(io::Error::from)?;
+        paths.push(path);
+    }
+    Ok(paths)
+}
+
+/// Writes RFC 4180 CSV with CRLF line endings, which spreadsheet tools read reliably.
+fn write_csv(path: &Path, header: &[&str], rows: &[Vec<String>]) -> csv::Result<()> {
+    let mut writer = csv::WriterBuilder::new()
+        .terminator(csv::Terminator::CRLF)
+        .from_path(path)?;
+    writer.write_record(header)?;
+    for row in rows {
+        writer.write_record(row)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod csv_export_tests {
+    use std::fs;
+
+    use chrono::NaiveDate;
+
+    use crate::{
+        bmc::{IntentTagBmc, TagBmc},
+        db::Db,
+        models::{
+            CreateIntent, CreateIntentTag, CreateManualSession, CreateTag, CreateTask, TaskPriority,
+        },
+    };
+
+    use super::*;
+
+    // 2023-07-10 00:00:00 UTC
+    const DAY: i64 = 1688947200;
+
+    #[test]
+    fn test_write_csv() {
+        let path = std::env::temp_dir().join(format!("intentio_csv_{}.csv", std::process::id()));
+        let rows = [
+            vec!["foo".to_string(), "foo, bar".to_string()],
+            vec!["say \"hi\"".to_string(), "two\nlines".to_string()],
+        ];
+        write_csv(&path, &["a", "b"], &rows).unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "a,b\r\nfoo,\"foo, bar\"\r\n\"say \"\"hi\"\"\",\"two\nlines\"\r\n"
+        );
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_export_csv() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let data = CreateIntent {
+            label: "Writing, mostly".to_string(),
+            parent_id: None,
+        };
+        let intent_id = IntentBmc::create(&mut conn, &data).unwrap();
+        for label in ["work", "deep"] {
+            let data = CreateTag {
+                label: label.to_string(),
+            };
+            let tag_id = TagBmc::create(&mut conn, &data).unwrap();
+            let data = CreateIntentTag { intent_id, tag_id };
+            IntentTagBmc::create(&mut conn, &data).unwrap();
+        }
+        for started_at in [DAY + 3600, DAY + 48 * 3600] {
+            let data = CreateManualSession {
+                intent_id,
+                started_at,
+                finished_at: started_at + 1500,
+            };
+            SessionBmc::create_manual(&mut conn, &data).unwrap();
+        }
+        let data = CreateTask {
+            body: "foo".to_string(),
+            intent_id,
+            priority: TaskPriority::Normal,
+            due_date: None,
+            parent_task_id: None,
+        };
+        TaskBmc::create(&mut conn, &data).unwrap();
+
+        let dir = std::env::temp_dir().join(format!("intentio_csv_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let options = ExportOptions {
+            from: NaiveDate::from_ymd_opt(2023, 7, 10),
+            to: NaiveDate::from_ymd_opt(2023, 7, 10),
+            intent_id: None,
+        };
+        let boundary = DayBoundary::new(Some("UTC"), 0);
+        let paths = export_csv(&mut conn, &dir, &options, &boundary).unwrap();
+
+        let sessions = fs::read_to_string(&paths[0]).unwrap();
+        let lines: Vec<_> = sessions.split("\r\n").collect();
+        assert_eq!(
+            lines[0],
+            "id,date,start,end,duration_minutes,intent,tags,summary,manual"
+        );
+        assert_eq!(
+            lines[1],
+            "1,2023-07-10,2023-07-10 01:00:00,2023-07-10 01:25:00,25.00,\"Writing, mostly\",work; deep,,true"
+        );
+        // Only the first session falls into the range.
+        assert_eq!(lines.len(), 3);
+
+        // The task was created now, outside of the range.
+        let tasks = fs::read_to_string(&paths[1]).unwrap();
+        assert_eq!(tasks.lines().count(), 1);
+
+        let intents = fs::read_to_string(&paths[2]).unwrap();
+        assert!(intents
+            .lines()
+            .nth(1)
+            .unwrap()
+            .starts_with("1,\"Writing, mostly\",work; deep,false,"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
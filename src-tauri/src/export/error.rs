@@ -0,0 +1,10 @@
+use ts_rs::TS;
+
+#[derive(thiserror::Error, TS, Debug)]
+#[ts(export, export_to = "../src/bindings/")]
+pub enum ExportError {
+    #[error("UnsupportedBackupVersion")]
+    UnsupportedBackupVersion,
+    #[error("InvalidBackup")]
+    InvalidBackup,
+}
@@ -0,0 +1,224 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use chrono::{Duration, TimeZone, Utc};
+use diesel::SqliteConnection;
+use tauri::AppHandle;
+
+use crate::{
+    bmc::{GetSessionsOptions, IntentBmc, SessionBmc},
+    config::{ConfigManager, SettingsConfig},
+    ctx::AppContext,
+    day::DayBoundary,
+    models::Session,
+    prelude::Result,
+};
+
+use super::ExportOptions;
+
+/// Name of the continuously refreshed calendar file written into the feed folder.
+pub const ICS_FEED_FILE: &str = "intentio.ics";
+/// How many past days the calendar feed covers, keeps the file small for calendar clients.
+const ICS_FEED_DAYS: i64 = 90;
+
+const ICS_DATETIME_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+/// Writes focus sessions as an iCalendar file to `path`, one VEVENT per session.
+pub fn export_ics(
+    conn: &mut SqliteConnection,
+    path: &Path,
+    options: &ExportOptions,
+    boundary: &DayBoundary,
+) -> Result<()> {
+    let session_options = GetSessionsOptions {
+        intent_id: options.intent_id,
+        session_types: None,
+        include_manual: None,
+        day: None,
+        started_from: options.from.map(|day| boundary.start_of(day)),
+        started_to: options.to.map(|day| boundary.range_of(day).1),
+        offset: None,
+        limit: None,
+    };
+    let mut sessions = SessionBmc::get_list(conn, Some(session_options))?;
+    sessions.sort_by_key(|session| session.started_at);
+
+    let mut labels = HashMap::new();
+    let mut tags = HashMap::new();
+    for intent in IntentBmc::get_flat_list(conn)? {
+        let intent_tags: Vec<_> = IntentBmc::get_tags(conn, intent.id)?
+            .into_iter()
+            .map(|tag| tag.label)
+            .collect();
+        tags.insert(intent.id, intent_tags);
+        labels.insert(intent.id, intent.label);
+    }
+
+    let calendar = to_ics(&sessions, &labels, &tags, Utc::now().timestamp());
+    fs::write(path, calendar)?;
+    Ok(())
+}
+
+/// Rewrites the calendar feed in `dir` with the sessions of the past `ICS_FEED_DAYS` days.
+pub fn write_ics_feed(
+    conn: &mut SqliteConnection,
+    dir: &Path,
+    boundary: &DayBoundary,
+) -> Result<PathBuf> {
+    let options = ExportOptions {
+        from: Some(boundary.today() - Duration::days(ICS_FEED_DAYS)),
+        ..Default::default()
+    };
+    let path = dir.join(ICS_FEED_FILE);
+    export_ics(conn, &path, &options, boundary)?;
+    Ok(path)
+}
+
+/// Refreshes the calendar feed when a feed folder is configured. Called whenever sessions
+/// change, failures are ignored so an unavailable folder never gets in the way of the timer.
+pub fn refresh_ics_feed(app_handle: &AppHandle) {
+    let Ok(settings) = ConfigManager::get::<SettingsConfig>() else {
+        return;
+    };
+    let Some(dir) = settings.ics_feed_dir.as_ref() else {
+        return;
+    };
+    let boundary = DayBoundary::from_settings(&settings);
+    let _ = app_handle.db(|db| write_ics_feed(db, Path::new(dir), &boundary));
+}
+
+/// Renders an RFC 5545 calendar, timestamps are written in UTC so no timezone
+/// definitions are needed.
+fn to_ics(
+    sessions: &[Session],
+    labels: &HashMap<i32, String>,
+    tags: &HashMap<i32, Vec<String>>,
+    stamp: i64,
+) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//Intentio//Focus sessions//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        "X-WR-CALNAME:Intentio".to_string(),
+    ];
+    for session in sessions {
+        let label = labels
+            .get(&session.intent_id)
+            .map(String::as_str)
+            .unwrap_or("Focus");
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!("UID:session-{}@intentio", session.id));
+        lines.push(format!("DTSTAMP:{}", format_datetime(stamp)));
+        lines.push(format!("DTSTART:{}", format_datetime(session.started_at)));
+        lines.push(format!("DTEND:{}", format_datetime(session.finished_at)));
+        lines.push(format!("SUMMARY:{}", escape_text(label)));
+        if let Some(summary) = session.summary.as_ref().filter(|s| !s.is_empty()) {
+            lines.push(format!("DESCRIPTION:{}", escape_text(summary)));
+        }
+        if let Some(tags) = tags.get(&session.intent_id).filter(|t| !t.is_empty()) {
+            let tags: Vec<_> = tags.iter().map(|tag| escape_text(tag)).collect();
+            lines.push(format!("CATEGORIES:{}", tags.join(",")));
+        }
+        lines.push("TRANSP:TRANSPARENT".to_string());
+        lines.push("END:VEVENT".to_string());
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    lines.iter().map(|line| fold_line(line) + "\r\n").collect()
+}
+
+fn format_datetime(timestamp: i64) -> String {
+    Utc.timestamp_opt(timestamp, 0)
+        .unwrap()
+        .format(ICS_DATETIME_FORMAT)
+        .to_string()
+}
+
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+/// Splits lines longer than 75 octets, continuation lines start with a space.
+fn fold_line(line: &str) -> String {
+    let mut folded = String::new();
+    let mut length = 0;
+    for c in line.chars() {
+        if length + c.len_utf8() > 75 {
+            folded += "\r\n ";
+            length = 1;
+        }
+        folded.push(c);
+        length += c.len_utf8();
+    }
+    folded
+}
+
+#[cfg(test)]
+mod ics_export_tests {
+    use crate::timer::SessionType;
+
+    use super::*;
+
+    fn session(id: i32, summary: Option<&str>) -> Session {
+        Session {
+            id,
+            duration: 1500,
+            summary: summary.map(String::from),
+            // 2023-07-10 08:00:00 UTC
+            started_at: 1688976000,
+            finished_at: 1688977500,
+            intent_id: 1,
+            session_type: SessionType::Focus,
+            planned_duration: Some(1500),
+            is_manual: false,
+        }
+    }
+
+    #[test]
+    fn test_escape_text() {
+        assert_eq!(escape_text("foo"), "foo");
+        assert_eq!(escape_text("a, b; c\\d"), "a\\, b\\; c\\\\d");
+        assert_eq!(escape_text("line\nbreak"), "line\\nbreak");
+    }
+
+    #[test]
+    fn test_fold_line() {
+        assert_eq!(fold_line("short"), "short");
+        let folded = fold_line(&"a".repeat(160));
+        let lines: Vec<_> = folded.split("\r\n").collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0].len(), 75);
+        assert!(lines[1].starts_with(' '));
+        assert_eq!(lines[1].len(), 75);
+        // Multi-byte characters are never split.
+        let folded = fold_line(&"ż".repeat(40));
+        assert!(folded.split("\r\n").all(|line| line.len() <= 75));
+    }
+
+    #[test]
+    fn test_to_ics() {
+        let labels = HashMap::from([(1, "Writing".to_string())]);
+        let tags = HashMap::from([(1, vec!["book".to_string(), "draft".to_string()])]);
+        let sessions = vec![session(1, Some("Chapter 1, part 2")), session(2, None)];
+
+        let calendar = to_ics(&sessions, &labels, &tags, 1688977500);
+        assert!(calendar.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(calendar.ends_with("END:VCALENDAR\r\n"));
+        assert_eq!(calendar.matches("BEGIN:VEVENT").count(), 2);
+        assert!(calendar.contains("UID:session-1@intentio\r\n"));
+        assert!(calendar.contains("DTSTART:20230710T080000Z\r\n"));
+        assert!(calendar.contains("DTEND:20230710T082500Z\r\n"));
+        assert!(calendar.contains("SUMMARY:Writing\r\n"));
+        assert!(calendar.contains("DESCRIPTION:Chapter 1\\, part 2\r\n"));
+        assert!(calendar.contains("CATEGORIES:book,draft\r\n"));
+        assert_eq!(calendar.matches("DESCRIPTION").count(), 1);
+    }
+}
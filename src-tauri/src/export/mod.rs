@@ -0,0 +1,28 @@
+//! Getting the app's data out of the database into portable file formats.
+
+mod backup;
+mod csv;
+mod error;
+mod ics;
+
+pub use backup::*;
+pub use csv::*;
+pub use error::*;
+pub use ics::*;
+
+use chrono::NaiveDate;
+use serde::Deserialize;
+use ts_rs::TS;
+
+/// Narrows down what gets exported, every filter is optional.
+#[derive(TS, Deserialize, Debug, Clone, Default)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct ExportOptions {
+    /// First day of the range, inclusive.
+    #[ts(type = "string | null")]
+    pub from: Option<NaiveDate>,
+    /// Last day of the range, inclusive.
+    #[ts(type = "string | null")]
+    pub to: Option<NaiveDate>,
+    pub intent_id: Option<i32>,
+}
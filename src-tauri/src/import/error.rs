@@ -0,0 +1,10 @@
+use ts_rs::TS;
+
+#[derive(thiserror::Error, TS, Debug)]
+#[ts(export, export_to = "../src/bindings/")]
+pub enum ImportError {
+    #[error("InvalidFile")]
+    InvalidFile,
+    #[error("MissingColumn")]
+    MissingColumn,
+}
@@ -0,0 +1,248 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use serde_json::Value;
+use ts_rs::TS;
+
+use crate::{day::DayBoundary, prelude::Result};
+
+use super::{ImportError, Importer, ParsedImport, RawRecord};
+
+/// Names of the columns, or JSON fields, holding each value of a session.
+/// Either `finished_at` or `duration` has to be present in the file.
+#[derive(TS, Deserialize, Debug, Clone)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct ColumnMapping {
+    pub intent: String,
+    pub started_at: String,
+    pub finished_at: Option<String>,
+    /// Column with `h:mm:ss` durations or plain minutes.
+    pub duration: Option<String>,
+    pub summary: Option<String>,
+    /// Column with tags separated by commas or semicolons.
+    pub tags: Option<String>,
+    pub task: Option<String>,
+    /// `strftime` like format of the datetime columns, RFC 3339, `%Y-%m-%d %H:%M:%S`
+    /// and unix timestamps are recognized when missing.
+    pub datetime_format: Option<String>,
+}
+
+impl Default for ColumnMapping {
+    fn default() -> Self {
+        Self {
+            intent: "intent".to_string(),
+            started_at: "started_at".to_string(),
+            finished_at: Some("finished_at".to_string()),
+            duration: Some("duration".to_string()),
+            summary: Some("summary".to_string()),
+            tags: Some("tags".to_string()),
+            task: Some("task".to_string()),
+            datetime_format: None,
+        }
+    }
+}
+
+impl ColumnMapping {
+    /// Picks the mapped values of a row out of `fields`, matching names case insensitively.
+    fn raw_record<'a>(&self, fields: &HashMap<String, &'a str>) -> RawRecord<'a> {
+        let get = |name: &Option<String>| {
+            name.as_ref()
+                .and_then(|name| fields.get(&name.to_lowercase()).copied())
+        };
+        RawRecord {
+            intent: fields.get(&self.intent.to_lowercase()).copied(),
+            started_at: fields.get(&self.started_at.to_lowercase()).copied(),
+            finished_at: get(&self.finished_at),
+            duration: get(&self.duration),
+            summary: get(&self.summary),
+            tags: get(&self.tags),
+            task: get(&self.task),
+        }
+    }
+
+    /// Checks that the values required to build a session are present in the file.
+    fn validate(&self, names: &[String]) -> Result<()> {
+        let has = |name: &String| names.contains(&name.to_lowercase());
+        let has_end = self.finished_at.as_ref().map_or(false, has)
+            || self.duration.as_ref().map_or(false, has);
+        if !has(&self.intent) || !has(&self.started_at) || !has_end {
+            return Err(ImportError::MissingColumn.into());
+        }
+        Ok(())
+    }
+}
+
+pub struct GenericCsvImporter {
+    mapping: ColumnMapping,
+}
+
+impl GenericCsvImporter {
+    pub fn new(mapping: ColumnMapping) -> Self {
+        Self { mapping }
+    }
+}
+
+impl Importer for GenericCsvImporter {
+    fn parse(&self, content: &str, boundary: &DayBoundary) -> Result<ParsedImport> {
+        let (headers, rows) = read_csv(content)?;
+        self.mapping.validate(&headers)?;
+
+        let mut parsed = ParsedImport::default();
+        for (index, row) in rows.iter().enumerate() {
+            let fields = headers.iter().cloned().zip(row.iter()).collect();
+            let record = self.mapping.raw_record(&fields).into_record(
+                index + 1,
+                self.mapping.datetime_format.as_deref(),
+                boundary,
+            );
+            parsed.push(index + 1, record);
+        }
+        Ok(parsed)
+    }
+}
+
+pub struct GenericJsonImporter {
+    mapping: ColumnMapping,
+}
+
+impl GenericJsonImporter {
+    pub fn new(mapping: ColumnMapping) -> Self {
+        Self { mapping }
+    }
+}
+
+impl Importer for GenericJsonImporter {
+    fn parse(&self, content: &str, boundary: &DayBoundary) -> Result<ParsedImport> {
+        let objects: Vec<serde_json::Map<String, Value>> =
+            serde_json::from_str(content).map_err(|_| ImportError::InvalidFile)?;
+
+        let mut parsed = ParsedImport::default();
+        for (index, object) in objects.iter().enumerate() {
+            let values: Vec<(String, String)> = object
+                .iter()
+                .map(|(key, value)| (key.to_lowercase(), json_to_string(value)))
+                .collect();
+            let names: Vec<String> = values.iter().map(|(key, _)| key.clone()).collect();
+            // Objects may leave out optional fields, so every one of them is validated.
+            if self.mapping.validate(&names).is_err() {
+                parsed.push(index + 1, Err("Missing fields".to_string()));
+                continue;
+            }
+            let fields = values
+                .iter()
+                .map(|(key, value)| (key.clone(), value.as_str()))
+                .collect();
+            let record = self.mapping.raw_record(&fields).into_record(
+                index + 1,
+                self.mapping.datetime_format.as_deref(),
+                boundary,
+            );
+            parsed.push(index + 1, record);
+        }
+        Ok(parsed)
+    }
+}
+
+/// Reads a CSV with a header row, header names are lowercased.
+pub(super) fn read_csv(content: &str) -> Result<(Vec<String>, Vec<csv::StringRecord>)> {
+    let content = content.trim_start_matches('\u{feff}');
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(content.as_bytes());
+
+    let headers = reader
+        .headers()
+        .map_err(|_| ImportError::InvalidFile)?
+        .iter()
+        .map(|header| header.to_lowercase())
+        .collect();
+    let rows = reader
+        .records()
+        .collect::<std::result::Result<_, _>>()
+        .map_err(|_| ImportError::InvalidFile)?;
+    Ok((headers, rows))
+}
+
+fn json_to_string(value: &Value) -> String {
+    match value {
+        Value::String(value) => value.clone(),
+        Value::Array(values) => values
+            .iter()
+            .map(json_to_string)
+            .collect::<Vec<_>>()
+            .join(","),
+        Value::Null => String::new(),
+        value => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod generic_importer_tests {
+    use crate::prelude::Error;
+
+    use super::*;
+
+    fn boundary() -> DayBoundary {
+        DayBoundary::new(Some("UTC"), 0)
+    }
+
+    #[test]
+    fn test_generic_csv() {
+        let content = "\
+Intent,Started_At,Duration,Tags,Note
+Writing,2023-07-10 08:00:00,25:00,\"draft, book\",First chapter
+Writing,2023-07-10 09:00:00,,,
+";
+        let mapping = ColumnMapping {
+            summary: Some("note".to_string()),
+            ..Default::default()
+        };
+        let parsed = GenericCsvImporter::new(mapping)
+            .parse(content, &boundary())
+            .unwrap();
+
+        assert_eq!(parsed.records.len(), 1);
+        let record = &parsed.records[0];
+        assert_eq!(record.intent, "Writing");
+        assert_eq!(record.started_at, 1688976000);
+        assert_eq!(record.finished_at, 1688976000 + 1500);
+        assert_eq!(record.tags, vec!["draft", "book"]);
+        assert_eq!(record.summary.as_deref(), Some("First chapter"));
+
+        assert_eq!(parsed.errors.len(), 1);
+        assert_eq!(parsed.errors[0].row, 2);
+    }
+
+    #[test]
+    fn test_generic_csv_missing_column() {
+        let content = "intent,started_at\nfoo,1688976000\n";
+        let result = GenericCsvImporter::new(ColumnMapping::default()).parse(content, &boundary());
+        assert!(matches!(
+            result,
+            Err(Error::ImportError(ImportError::MissingColumn))
+        ));
+    }
+
+    #[test]
+    fn test_generic_json() {
+        let content = r#"[
+            {"intent": "foo", "started_at": 1688976000, "finished_at": 1688977500, "tags": ["a", "b"]},
+            {"intent": "bar"}
+        ]"#;
+        let parsed = GenericJsonImporter::new(ColumnMapping::default())
+            .parse(content, &boundary())
+            .unwrap();
+
+        assert_eq!(parsed.records.len(), 1);
+        assert_eq!(parsed.records[0].duration, 1500);
+        assert_eq!(parsed.records[0].tags, vec!["a", "b"]);
+        assert_eq!(parsed.errors.len(), 1);
+
+        let result = GenericJsonImporter::new(ColumnMapping::default()).parse("{}", &boundary());
+        assert!(matches!(
+            result,
+            Err(Error::ImportError(ImportError::InvalidFile))
+        ));
+    }
+}
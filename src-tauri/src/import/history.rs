@@ -0,0 +1,451 @@
+use std::collections::{HashMap, HashSet};
+
+use diesel::prelude::*;
+use diesel::SqliteConnection;
+use serde::Serialize;
+use ts_rs::TS;
+
+use crate::{
+    bmc::{IntentBmc, IntentTagBmc, SessionBmc, TagBmc, TaskBmc},
+    models::{
+        CreateIntent, CreateIntentTag, CreateSession, CreateTag, CreateTask, Intent, TaskPriority,
+        UpdateSession,
+    },
+    prelude::{Error, Result},
+    timer::SessionType,
+};
+
+use super::{ImportRecord, ImportRowError, ParsedImport};
+
+/// Separates the labels of nested intents in a full path, e.g. `Course / Lecture`.
+const INTENT_PATH_SEPARATOR: &str = " / ";
+
+#[derive(TS, Serialize, Debug, Clone)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct ImportEntry {
+    #[serde(flatten)]
+    pub record: ImportRecord,
+    /// Set when a session with the same time range already exists in the database
+    /// or earlier in the file, duplicates are skipped by the import.
+    pub duplicate: bool,
+    /// Set when the session overlaps, without matching it, a session in the database or one
+    /// imported earlier in the file, e.g. the same history exported by another tool.
+    /// Overlapping sessions are skipped by the import.
+    pub overlapping: bool,
+}
+
+/// What an import will do, or did, to the database.
+#[derive(TS, Serialize, Debug, Clone, Default)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct ImportPreview {
+    pub entries: Vec<ImportEntry>,
+    pub errors: Vec<ImportRowError>,
+    /// Labels of intents which will be created.
+    pub new_intents: Vec<String>,
+    /// Labels of tags which will be created.
+    pub new_tags: Vec<String>,
+    /// Bodies of tasks which will be created.
+    pub new_tasks: Vec<String>,
+}
+
+impl ImportPreview {
+    fn importable(&self) -> impl Iterator<Item = &ImportRecord> {
+        self.entries
+            .iter()
+            .filter(|entry| !entry.duplicate && !entry.overlapping)
+            .map(|entry| &entry.record)
+    }
+}
+
+/// Dry run of the import, nothing is written to the database. Records whose intent label
+/// is shared by several intents are reported as errors, they have to use the full path.
+pub fn preview_import(conn: &mut SqliteConnection, parsed: ParsedImport) -> Result<ImportPreview> {
+    let intents = get_intent_lookup(conn)?;
+    let tags: HashSet<String> = TagBmc::get_list(conn)?
+        .into_iter()
+        .map(|tag| tag.label)
+        .collect();
+    let tasks: HashSet<(i32, String)> = TaskBmc::get_list(conn, None)?
+        .into_iter()
+        .map(|task| (task.intent_id, task.body))
+        .collect();
+
+    let mut preview = ImportPreview {
+        errors: parsed.errors,
+        ..Default::default()
+    };
+    // Time ranges of the records to be imported
+    let mut ranges: Vec<(i64, i64)> = vec![];
+    let mut planned_tasks = HashSet::new();
+    for record in parsed.records {
+        let intent_id = match resolve_intent(&intents, &record.intent) {
+            Ok(intent_id) => intent_id,
+            Err(message) => {
+                preview.errors.push(ImportRowError {
+                    row: record.row,
+                    message,
+                });
+                continue;
+            }
+        };
+        let range = (record.started_at, record.finished_at);
+        let duplicate = ranges.contains(&range) || session_exists(conn, range.0, range.1)?;
+        let overlapping = !duplicate
+            && (ranges
+                .iter()
+                .any(|&(started_at, finished_at)| started_at < range.1 && finished_at > range.0)
+                || SessionBmc::overlaps(conn, range.0, range.1, None)?);
+
+        if !duplicate && !overlapping {
+            ranges.push(range);
+            if intent_id.is_none() && !preview.new_intents.contains(&record.intent) {
+                preview.new_intents.push(record.intent.clone());
+            }
+            for tag in &record.tags {
+                if !tags.contains(tag) && !preview.new_tags.contains(tag) {
+                    preview.new_tags.push(tag.clone());
+                }
+            }
+            if let Some(task) = &record.task {
+                let exists = intent_id.map_or(false, |id| tasks.contains(&(id, task.clone())));
+                if !exists && planned_tasks.insert((record.intent.clone(), task.clone())) {
+                    preview.new_tasks.push(task.clone());
+                }
+            }
+        }
+
+        preview.entries.push(ImportEntry {
+            record,
+            duplicate,
+            overlapping,
+        });
+    }
+    Ok(preview)
+}
+
+/// Imports every record which neither duplicates nor overlaps a session in a single
+/// transaction, imported sessions count as logged by hand.
+/// Intents are matched by label or full path, tags by label, both are created when missing.
+pub fn run_import(conn: &mut SqliteConnection, parsed: ParsedImport) -> Result<ImportPreview> {
+    conn.transaction::<_, Error, _>(|conn| {
+        let preview = preview_import(conn, parsed)?;
+
+        let mut intents = get_intent_lookup(conn)?;
+        let mut tags: HashMap<String, i32> = TagBmc::get_list(conn)?
+            .into_iter()
+            .map(|tag| (tag.label, tag.id))
+            .collect();
+        let mut intent_tags = HashSet::new();
+        let mut tasks: HashSet<(i32, String)> = TaskBmc::get_list(conn, None)?
+            .into_iter()
+            .map(|task| (task.intent_id, task.body))
+            .collect();
+
+        for record in preview.importable() {
+            // Ambiguous intents were reported as errors by the preview
+            let intent_id = match resolve_intent(&intents, &record.intent) {
+                Ok(Some(id)) => id,
+                _ => {
+                    let data = CreateIntent {
+                        label: record.intent.clone(),
+                        parent_id: None,
+                    };
+                    let id = IntentBmc::create(conn, &data)?;
+                    intents.insert(record.intent.clone(), vec![id]);
+                    id
+                }
+            };
+
+            for label in &record.tags {
+                let tag_id = match tags.get(label) {
+                    Some(&id) => id,
+                    None => {
+                        let data = CreateTag {
+                            label: label.clone(),
+                        };
+                        let id = TagBmc::create(conn, &data)?;
+                        tags.insert(label.clone(), id);
+                        id
+                    }
+                };
+                if intent_tags.contains(&(intent_id, tag_id)) {
+                    continue;
+                }
+                let linked = IntentBmc::get_tags(conn, intent_id)?
+                    .iter()
+                    .any(|tag| tag.id == tag_id);
+                if !linked {
+                    IntentTagBmc::create(conn, &CreateIntentTag { intent_id, tag_id })?;
+                }
+                intent_tags.insert((intent_id, tag_id));
+            }
+
+            let data = CreateSession {
+                duration: record.duration,
+                started_at: record.started_at,
+                intent_id,
+                session_type: SessionType::Focus,
+                planned_duration: None,
+            };
+            let session_id = SessionBmc::create_imported(conn, &data, record.finished_at)?;
+            if record.summary.is_some() {
+                let data = UpdateSession {
+                    summary: record.summary.clone(),
+                    ..Default::default()
+                };
+                SessionBmc::update(conn, session_id, &data)?;
+            }
+
+            if let Some(task) = &record.task {
+                if tasks.insert((intent_id, task.clone())) {
+                    let data = CreateTask {
+                        body: task.clone(),
+                        intent_id,
+                        priority: TaskPriority::Normal,
+                        due_date: None,
+                        parent_task_id: None,
+                    };
+                    TaskBmc::create(conn, &data)?;
+                }
+            }
+        }
+
+        Ok(preview)
+    })
+}
+
+/// Ids of the intents keyed by label as well as by full path, a key shared by several
+/// intents maps to all of them.
+fn get_intent_lookup(conn: &mut SqliteConnection) -> Result<HashMap<String, Vec<i32>>> {
+    let intents = IntentBmc::get_flat_list(conn)?;
+    let by_id: HashMap<i32, &Intent> = intents.iter().map(|intent| (intent.id, intent)).collect();
+
+    let mut lookup: HashMap<String, Vec<i32>> = HashMap::new();
+    for intent in &intents {
+        let mut path = vec![intent.label.as_str()];
+        let mut parent_id = intent.parent_id;
+        while let Some(parent) = parent_id.and_then(|id| by_id.get(&id)) {
+            path.push(&parent.label);
+            parent_id = parent.parent_id;
+        }
+        if path.len() > 1 {
+            path.reverse();
+            let path = path.join(INTENT_PATH_SEPARATOR);
+            lookup.entry(path).or_default().push(intent.id);
+        }
+        lookup
+            .entry(intent.label.clone())
+            .or_default()
+            .push(intent.id);
+    }
+    Ok(lookup)
+}
+
+/// Id of the intent the label or path refers to, `None` if there is no such intent yet.
+fn resolve_intent(
+    lookup: &HashMap<String, Vec<i32>>,
+    intent: &str,
+) -> std::result::Result<Option<i32>, String> {
+    match lookup.get(intent).map(Vec::as_slice) {
+        None => Ok(None),
+        Some([id]) => Ok(Some(*id)),
+        Some(_) => Err(format!(
+            "Ambiguous intent \"{}\", use its full path instead",
+            intent
+        )),
+    }
+}
+
+fn session_exists(conn: &mut SqliteConnection, started_at: i64, finished_at: i64) -> Result<bool> {
+    use crate::db::schema::sessions::dsl;
+
+    let count: i64 = dsl::sessions
+        .filter(dsl::started_at.eq(started_at))
+        .filter(dsl::finished_at.eq(finished_at))
+        .count()
+        .get_result(conn)?;
+    Ok(count > 0)
+}
+
+#[cfg(test)]
+mod import_history_tests {
+    use crate::{
+        bmc::{GetSessionsOptions, SessionPauseBmc},
+        db::Db,
+        models::CreateManualSession,
+    };
+
+    use super::*;
+
+    fn record(row: usize, intent: &str, started_at: i64) -> ImportRecord {
+        ImportRecord {
+            row,
+            intent: intent.to_string(),
+            tags: vec!["imported".to_string()],
+            started_at,
+            finished_at: started_at + 1500,
+            duration: 1500,
+            summary: Some("foo".to_string()),
+            task: Some("bar".to_string()),
+        }
+    }
+
+    fn parsed() -> ParsedImport {
+        ParsedImport {
+            records: vec![
+                record(1, "writing", 1688976000),
+                record(2, "writing", 1688976000),
+                record(3, "reading", 1688979600),
+            ],
+            errors: vec![],
+        }
+    }
+
+    #[test]
+    fn test_preview_does_not_write() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        IntentBmc::create(
+            &mut conn,
+            &CreateIntent {
+                label: "writing".to_string(),
+                parent_id: None,
+            },
+        )
+        .unwrap();
+
+        let preview = preview_import(&mut conn, parsed()).unwrap();
+        assert_eq!(preview.entries.len(), 3);
+        assert!(preview.entries[1].duplicate);
+        assert_eq!(preview.new_intents, vec!["reading"]);
+        assert_eq!(preview.new_tags, vec!["imported"]);
+        assert_eq!(preview.new_tasks, vec!["bar", "bar"]);
+
+        assert_eq!(IntentBmc::get_flat_list(&mut conn).unwrap().len(), 1);
+        assert!(SessionBmc::get_list(&mut conn, None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_run_import() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let preview = run_import(&mut conn, parsed()).unwrap();
+        assert_eq!(preview.importable().count(), 2);
+
+        let options = GetSessionsOptions {
+            intent_id: None,
+            session_types: None,
+            include_manual: None,
+            day: None,
+            started_from: None,
+            started_to: None,
+            offset: None,
+            limit: None,
+        };
+        let sessions = SessionBmc::get_list(&mut conn, Some(options)).unwrap();
+        assert_eq!(sessions.len(), 2);
+        assert_eq!(sessions[0].finished_at - sessions[0].started_at, 1500);
+        assert_eq!(sessions[0].summary.as_deref(), Some("foo"));
+        assert!(sessions.iter().all(|session| session.is_manual));
+        let ids: Vec<i32> = sessions.iter().map(|session| session.id).collect();
+        assert!(SessionPauseBmc::get_list_by_sessions(&mut conn, &ids)
+            .unwrap()
+            .is_empty());
+
+        let intents = IntentBmc::get_flat_list(&mut conn).unwrap();
+        assert_eq!(intents.len(), 2);
+        assert_eq!(
+            IntentBmc::get_tags(&mut conn, intents[0].id).unwrap().len(),
+            1
+        );
+        assert_eq!(TagBmc::get_list(&mut conn).unwrap().len(), 1);
+        assert_eq!(TaskBmc::get_list(&mut conn, None).unwrap().len(), 2);
+
+        // Importing the same file again only finds duplicates.
+        let preview = run_import(&mut conn, parsed()).unwrap();
+        assert_eq!(preview.importable().count(), 0);
+        assert_eq!(SessionBmc::get_list(&mut conn, None).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_import_skips_overlapping_sessions() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let intent_id = IntentBmc::create(
+            &mut conn,
+            &CreateIntent {
+                label: "writing".to_string(),
+                parent_id: None,
+            },
+        )
+        .unwrap();
+        let data = CreateManualSession {
+            intent_id,
+            started_at: 1688976000,
+            finished_at: 1688977500,
+        };
+        SessionBmc::create_manual(&mut conn, &data).unwrap();
+
+        let parsed = || ParsedImport {
+            records: vec![
+                // The same session, rounded differently by another tool.
+                record(1, "writing", 1688976060),
+                record(2, "reading", 1688979600),
+                record(3, "reading", 1688980200),
+                // Adjacent to the second record.
+                record(4, "reading", 1688981100),
+            ],
+            errors: vec![],
+        };
+        let preview = preview_import(&mut conn, parsed()).unwrap();
+        let overlapping: Vec<bool> = preview
+            .entries
+            .iter()
+            .map(|entry| entry.overlapping)
+            .collect();
+        assert_eq!(overlapping, vec![true, false, true, false]);
+        assert!(preview.entries.iter().all(|entry| !entry.duplicate));
+
+        run_import(&mut conn, parsed()).unwrap();
+        assert_eq!(SessionBmc::get_list(&mut conn, None).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_import_nested_intents_by_path() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let mut lecture_ids = vec![];
+        for course in ["math", "physics"] {
+            let data = CreateIntent {
+                label: course.to_string(),
+                parent_id: None,
+            };
+            let parent_id = IntentBmc::create(&mut conn, &data).unwrap();
+            let data = CreateIntent {
+                label: "lecture".to_string(),
+                parent_id: Some(parent_id),
+            };
+            lecture_ids.push(IntentBmc::create(&mut conn, &data).unwrap());
+        }
+        let parsed = ParsedImport {
+            records: vec![
+                record(1, "lecture", 1688976000),
+                record(2, "physics / lecture", 1688979600),
+                record(3, "math", 1688983200),
+            ],
+            errors: vec![],
+        };
+
+        let preview = run_import(&mut conn, parsed).unwrap();
+        // The label alone could be either lecture.
+        assert_eq!(preview.errors.len(), 1);
+        assert_eq!(preview.errors[0].row, 1);
+        assert_eq!(preview.entries.len(), 2);
+        assert!(preview.new_intents.is_empty());
+
+        let sessions = SessionBmc::get_list(&mut conn, None).unwrap();
+        assert_eq!(sessions.len(), 2);
+        assert!(sessions
+            .iter()
+            .any(|session| session.intent_id == lecture_ids[1]));
+        assert!(sessions
+            .iter()
+            .all(|session| session.intent_id != lecture_ids[0]));
+    }
+}
@@ -0,0 +1,336 @@
+//! Bringing history kept in other pomodoro and time-tracking apps into the database.
+//!
+//! Every supported format has an `Importer` turning a file's content into `ImportRecord`s,
+//! the records are then previewed or imported by the format independent `history` module.
+
+mod error;
+mod generic;
+mod history;
+mod toggl;
+
+pub use error::*;
+pub use generic::*;
+pub use history::*;
+pub use toggl::*;
+
+use chrono::{DateTime, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::{day::DayBoundary, prelude::Result};
+
+/// Parses an exported file into records, rows which can't be understood are reported
+/// as `ImportRowError`s instead of failing the whole import.
+pub trait Importer {
+    fn parse(&self, content: &str, boundary: &DayBoundary) -> Result<ParsedImport>;
+}
+
+/// Supported formats of files to import.
+#[derive(TS, Deserialize, Debug, Clone)]
+#[ts(export, export_to = "../src/bindings/")]
+pub enum ImportFormat {
+    /// Any CSV with a header row, columns are picked by the mapping.
+    GenericCsv(ColumnMapping),
+    /// JSON array of objects, fields are picked by the mapping.
+    GenericJson(ColumnMapping),
+    /// Detailed time entries report exported from Toggl Track.
+    TogglCsv,
+}
+
+impl ImportFormat {
+    pub fn importer(&self) -> Box<dyn Importer> {
+        match self {
+            ImportFormat::GenericCsv(mapping) => Box::new(GenericCsvImporter::new(mapping.clone())),
+            ImportFormat::GenericJson(mapping) => {
+                Box::new(GenericJsonImporter::new(mapping.clone()))
+            }
+            ImportFormat::TogglCsv => Box::new(TogglCsvImporter {}),
+        }
+    }
+}
+
+/// A single focus session read from an exported file.
+#[derive(TS, Serialize, Debug, Clone, PartialEq)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct ImportRecord {
+    /// Row of the record in the file, starting at 1 for the first data row.
+    pub row: usize,
+    pub intent: String,
+    pub tags: Vec<String>,
+    #[ts(type = "number")]
+    pub started_at: i64,
+    #[ts(type = "number")]
+    pub finished_at: i64,
+    /// Focused time in seconds.
+    pub duration: i32,
+    pub summary: Option<String>,
+    pub task: Option<String>,
+}
+
+#[derive(TS, Serialize, Debug, Clone, PartialEq)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct ImportRowError {
+    pub row: usize,
+    pub message: String,
+}
+
+#[derive(Debug, Default)]
+pub struct ParsedImport {
+    pub records: Vec<ImportRecord>,
+    pub errors: Vec<ImportRowError>,
+}
+
+impl ParsedImport {
+    fn push(&mut self, row: usize, record: std::result::Result<ImportRecord, String>) {
+        match record {
+            Ok(record) => self.records.push(record),
+            Err(message) => self.errors.push(ImportRowError { row, message }),
+        }
+    }
+}
+
+/// Raw field values of a row, before they are validated.
+#[derive(Default)]
+struct RawRecord<'a> {
+    intent: Option<&'a str>,
+    tags: Option<&'a str>,
+    started_at: Option<&'a str>,
+    finished_at: Option<&'a str>,
+    duration: Option<&'a str>,
+    summary: Option<&'a str>,
+    task: Option<&'a str>,
+}
+
+impl<'a> RawRecord<'a> {
+    /// Validates the raw values, either the finish time or the duration has to be present.
+    fn into_record(
+        self,
+        row: usize,
+        datetime_format: Option<&str>,
+        boundary: &DayBoundary,
+    ) -> std::result::Result<ImportRecord, String> {
+        let intent = non_empty(self.intent).ok_or("Missing intent")?;
+        let started_at = non_empty(self.started_at)
+            .map(|value| parse_datetime(value, datetime_format, boundary))
+            .ok_or("Missing start time")?
+            .ok_or("Invalid start time")?;
+        let finished_at = match non_empty(self.finished_at) {
+            Some(value) => {
+                Some(parse_datetime(value, datetime_format, boundary).ok_or("Invalid end time")?)
+            }
+            None => None,
+        };
+        let duration = match non_empty(self.duration) {
+            Some(value) => Some(parse_duration(value).ok_or("Invalid duration")?),
+            None => None,
+        };
+
+        let (finished_at, duration) = match (finished_at, duration) {
+            (Some(finished_at), Some(duration)) => (finished_at, duration),
+            (Some(finished_at), None) => {
+                let duration = i32::try_from(finished_at - started_at)
+                    .map_err(|_| "Duration does not fit into the time range")?;
+                (finished_at, duration)
+            }
+            (None, Some(duration)) => (started_at + duration as i64, duration),
+            (None, None) => return Err("Missing end time or duration".to_string()),
+        };
+        if finished_at <= started_at {
+            return Err("End time is not after start time".to_string());
+        }
+        if duration <= 0 || duration as i64 > finished_at - started_at {
+            return Err("Duration does not fit into the time range".to_string());
+        }
+
+        Ok(ImportRecord {
+            row,
+            intent: intent.to_string(),
+            tags: self.tags.map(split_tags).unwrap_or_default(),
+            started_at,
+            finished_at,
+            duration,
+            summary: non_empty(self.summary).map(String::from),
+            task: non_empty(self.task).map(String::from),
+        })
+    }
+}
+
+fn non_empty(value: Option<&str>) -> Option<&str> {
+    value.map(str::trim).filter(|value| !value.is_empty())
+}
+
+/// Plain integers up to this are unix timestamps in seconds, i.e. until the year 5138.
+const MAX_UNIX_SECONDS: i64 = 99_999_999_999;
+/// Plain integers above `MAX_UNIX_SECONDS` and up to this are unix timestamps in milliseconds.
+const MAX_UNIX_MILLIS: i64 = 99_999_999_999_999;
+
+/// Datetimes with an offset are taken as is, the ones without are local to the
+/// configured timezone. Plain integers are read as unix timestamps in seconds or
+/// milliseconds, negative and larger ones are invalid.
+fn parse_datetime(value: &str, format: Option<&str>, boundary: &DayBoundary) -> Option<i64> {
+    if let Some(format) = format {
+        return NaiveDateTime::parse_from_str(value, format)
+            .ok()
+            .map(|local| boundary.to_utc(local));
+    }
+    if let Ok(datetime) = DateTime::parse_from_rfc3339(value) {
+        return Some(datetime.timestamp());
+    }
+    for format in ["%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M"] {
+        if let Ok(local) = NaiveDateTime::parse_from_str(value, format) {
+            return Some(boundary.to_utc(local));
+        }
+    }
+    match value.parse::<i64>().ok()? {
+        timestamp @ 0..=MAX_UNIX_SECONDS => Some(timestamp),
+        timestamp @ 0..=MAX_UNIX_MILLIS => Some(timestamp / 1000),
+        _ => None,
+    }
+}
+
+/// Reads `h:mm:ss` or `mm:ss` clock durations, plain numbers are minutes.
+/// Returns the duration in seconds, `None` if it doesn't fit.
+fn parse_duration(value: &str) -> Option<i32> {
+    if value.contains(':') {
+        let parts: Vec<i32> = value
+            .split(':')
+            .map(|part| part.trim().parse().ok())
+            .collect::<Option<_>>()?;
+        let (h, m, s) = match parts[..] {
+            [h, m, s] => (h, m, s),
+            [m, s] => (0, m, s),
+            _ => return None,
+        };
+        return h
+            .checked_mul(3600)?
+            .checked_add(m.checked_mul(60)?)?
+            .checked_add(s);
+    }
+    let seconds = value.parse::<f64>().ok()? * 60.0;
+    let seconds = seconds.round();
+    (seconds.is_finite() && seconds >= i32::MIN as f64 && seconds <= i32::MAX as f64)
+        .then(|| seconds as i32)
+}
+
+/// Tags are separated by commas or semicolons.
+fn split_tags(value: &str) -> Vec<String> {
+    value
+        .split([',', ';'])
+        .map(str::trim)
+        .filter(|tag| !tag.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+#[cfg(test)]
+mod import_tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("1:02:03"), Some(3723));
+        assert_eq!(parse_duration("25:00"), Some(1500));
+        assert_eq!(parse_duration("25"), Some(1500));
+        assert_eq!(parse_duration("0.5"), Some(30));
+        assert_eq!(parse_duration("foo"), None);
+        assert_eq!(parse_duration("1:2:3:4"), None);
+        // Overflowing durations are invalid rather than wrapping around.
+        assert_eq!(parse_duration("600000:00:00"), None);
+        assert_eq!(parse_duration("1:35791394:00"), None);
+        assert_eq!(parse_duration("99999999999"), None);
+        assert_eq!(parse_duration("inf"), None);
+    }
+
+    #[test]
+    fn test_parse_datetime() {
+        let boundary = DayBoundary::new(Some("Europe/Warsaw"), 0);
+        // 2023-07-10 08:00:00 UTC
+        let expected = Some(1688976000);
+        assert_eq!(
+            parse_datetime("2023-07-10T08:00:00Z", None, &boundary),
+            expected
+        );
+        assert_eq!(
+            parse_datetime("2023-07-10 10:00:00", None, &boundary),
+            expected
+        );
+        assert_eq!(
+            parse_datetime("10/07/2023 10:00", Some("%d/%m/%Y %H:%M"), &boundary),
+            expected
+        );
+        assert_eq!(parse_datetime("1688976000", None, &boundary), expected);
+        assert_eq!(parse_datetime("1688976000000", None, &boundary), expected);
+        // Microseconds and negative timestamps are out of range.
+        assert_eq!(parse_datetime("1688976000000000", None, &boundary), None);
+        assert_eq!(parse_datetime("-1688976000", None, &boundary), None);
+        assert_eq!(parse_datetime("yesterday", None, &boundary), None);
+    }
+
+    #[test]
+    fn test_raw_record_validation() {
+        let boundary = DayBoundary::new(Some("UTC"), 0);
+        let raw = RawRecord {
+            intent: Some("foo"),
+            tags: Some("a; b,"),
+            started_at: Some("1688976000"),
+            duration: Some("25"),
+            ..Default::default()
+        };
+        let record = raw.into_record(1, None, &boundary).unwrap();
+        assert_eq!(record.finished_at, 1688976000 + 1500);
+        assert_eq!(record.tags, vec!["a", "b"]);
+
+        let raw = RawRecord {
+            intent: Some("foo"),
+            started_at: Some("1688976000"),
+            finished_at: Some("1688976600"),
+            duration: Some("25"),
+            ..Default::default()
+        };
+        assert!(raw.into_record(1, None, &boundary).is_err());
+
+        let raw = RawRecord {
+            intent: Some(" "),
+            started_at: Some("1688976000"),
+            duration: Some("25"),
+            ..Default::default()
+        };
+        assert_eq!(
+            raw.into_record(1, None, &boundary),
+            Err("Missing intent".to_string())
+        );
+
+        // Millisecond timestamps are converted, out of range ones are rejected.
+        let raw = RawRecord {
+            intent: Some("foo"),
+            started_at: Some("1688976000000"),
+            finished_at: Some("1688977500000"),
+            ..Default::default()
+        };
+        let record = raw.into_record(1, None, &boundary).unwrap();
+        assert_eq!(record.started_at, 1688976000);
+        assert_eq!(record.duration, 1500);
+
+        let raw = RawRecord {
+            intent: Some("foo"),
+            started_at: Some("1688976000000000"),
+            duration: Some("25"),
+            ..Default::default()
+        };
+        assert_eq!(
+            raw.into_record(1, None, &boundary),
+            Err("Invalid start time".to_string())
+        );
+
+        let raw = RawRecord {
+            intent: Some("foo"),
+            started_at: Some("1688976000"),
+            finished_at: Some("1688977500000000"),
+            ..Default::default()
+        };
+        assert_eq!(
+            raw.into_record(1, None, &boundary),
+            Err("Invalid end time".to_string())
+        );
+    }
+}
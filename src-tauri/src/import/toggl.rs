@@ -0,0 +1,85 @@
+use std::collections::HashMap;
+
+use crate::{day::DayBoundary, prelude::Result};
+
+use super::{generic::read_csv, ImportError, Importer, ParsedImport, RawRecord};
+
+/// Label Toggl uses for time entries without a project.
+const NO_PROJECT: &str = "No project";
+
+/// Reads the detailed time entries CSV report of Toggl Track. Projects become intents,
+/// descriptions become session summaries and the start and end times are local.
+pub struct TogglCsvImporter {}
+
+impl Importer for TogglCsvImporter {
+    fn parse(&self, content: &str, boundary: &DayBoundary) -> Result<ParsedImport> {
+        let (headers, rows) = read_csv(content)?;
+        let required = ["start date", "start time", "duration"];
+        if required
+            .iter()
+            .any(|name| !headers.iter().any(|h| h == name))
+        {
+            return Err(ImportError::MissingColumn.into());
+        }
+
+        let mut parsed = ParsedImport::default();
+        for (index, row) in rows.iter().enumerate() {
+            let fields: HashMap<&str, &str> =
+                headers.iter().map(String::as_str).zip(row.iter()).collect();
+            let datetime = |date: &str, time: &str| match (fields.get(date), fields.get(time)) {
+                (Some(date), Some(time)) => format!("{} {}", date, time),
+                _ => String::new(),
+            };
+            let started_at = datetime("start date", "start time");
+            let finished_at = datetime("end date", "end time");
+            let project = fields
+                .get("project")
+                .copied()
+                .filter(|project| !project.is_empty())
+                .unwrap_or(NO_PROJECT);
+
+            let raw = RawRecord {
+                intent: Some(project),
+                tags: fields.get("tags").copied(),
+                started_at: Some(&started_at),
+                finished_at: Some(&finished_at),
+                duration: fields.get("duration").copied(),
+                summary: fields.get("description").copied(),
+                task: fields.get("task").copied(),
+            };
+            parsed.push(index + 1, raw.into_record(index + 1, None, boundary));
+        }
+        Ok(parsed)
+    }
+}
+
+#[cfg(test)]
+mod toggl_importer_tests {
+    use super::*;
+
+    #[test]
+    fn test_toggl_csv() {
+        let content = "\
+User,Email,Client,Project,Task,Description,Billable,Start date,Start time,End date,End time,Duration,Tags,Amount ()
+Jane,jane@example.com,,Intentio,Importers,Toggl support,No,2023-07-10,10:00:00,2023-07-10,10:25:00,00:25:00,\"rust, oss\",
+Jane,jane@example.com,,,,Reading,No,2023-07-10,11:00:00,2023-07-10,11:30:00,00:30:00,,
+Jane,jane@example.com,,,,Broken,No,2023-07-10,,2023-07-10,11:30:00,00:30:00,,
+";
+        let boundary = DayBoundary::new(Some("Europe/Warsaw"), 0);
+        let parsed = TogglCsvImporter {}.parse(content, &boundary).unwrap();
+
+        assert_eq!(parsed.records.len(), 2);
+        let record = &parsed.records[0];
+        assert_eq!(record.intent, "Intentio");
+        assert_eq!(record.task.as_deref(), Some("Importers"));
+        assert_eq!(record.summary.as_deref(), Some("Toggl support"));
+        // 2023-07-10 08:00:00 UTC
+        assert_eq!(record.started_at, 1688976000);
+        assert_eq!(record.duration, 1500);
+        assert_eq!(record.tags, vec!["rust", "oss"]);
+        assert_eq!(parsed.records[1].intent, NO_PROJECT);
+
+        assert_eq!(parsed.errors.len(), 1);
+        assert_eq!(parsed.errors[0].row, 3);
+    }
+}
@@ -0,0 +1,102 @@
+//! Tauri IPC commands to export the app's data to files.
+
+use std::{path::PathBuf, sync::Mutex};
+
+use tauri::{command, AppHandle, Manager};
+
+use crate::{
+    bmc::ThemeBmc,
+    config::{ConfigManager, QueueConfig, SettingsConfig, TimerConfig},
+    ctx::AppContext,
+    day::DayBoundary,
+    export::{self, BackupConfigs, ExportOptions},
+    prelude::Result,
+    state::AppState,
+    timer::Timer,
+};
+
+use super::EventPayload;
+
+#[command]
+pub async fn export_csv(
+    app_handle: AppHandle,
+    dir: PathBuf,
+    options: ExportOptions,
+) -> Result<Vec<PathBuf>> {
+    let config = ConfigManager::get::<SettingsConfig>()?;
+    let boundary = DayBoundary::from_settings(&config);
+    app_handle.db(|mut db| export::export_csv(&mut db, &dir, &options, &boundary))
+}
+
+#[command]
+pub async fn export_backup(app_handle: AppHandle, path: PathBuf) -> Result<()> {
+    let configs = BackupConfigs {
+        timer: ConfigManager::get::<TimerConfig>()?,
+        settings: ConfigManager::get::<SettingsConfig>()?,
+        queue: ConfigManager::get::<QueueConfig>()?,
+    };
+    let backup = app_handle.db(|mut db| export::create_backup(&mut db, configs))?;
+    export::write_backup(&path, &backup)
+}
+
+#[command]
+pub async fn import_backup(app_handle: AppHandle, path: PathBuf) -> Result<()> {
+    let backup = export::read_backup(&path)?;
+    let configs = app_handle.db(|mut db| export::restore_backup(&mut db, &backup))?;
+    export::save_backup_configs(&configs)?;
+
+    // The timer's session and queue point at intents that no longer exist, start over.
+    let timer = app_handle.state::<tokio::sync::Mutex<Timer>>();
+    timer.lock().await.reset().await;
+
+    // Theme ids changed with the restore, reload the themes held by the app state.
+    let settings = &configs.settings;
+    let themes = app_handle.db(|mut db| -> Result<_> {
+        Ok((
+            ThemeBmc::get(&mut db, settings.idle_theme_id)?,
+            ThemeBmc::get(&mut db, settings.focus_theme_id)?,
+            ThemeBmc::get(&mut db, settings.break_theme_id)?,
+            ThemeBmc::get(&mut db, settings.long_break_theme_id)?,
+        ))
+    });
+    if let Ok((idle_theme, focus_theme, break_theme, long_break_theme)) = themes {
+        let state = app_handle.state::<Mutex<AppState>>();
+        let mut state = state.lock().unwrap();
+        state.idle_theme = idle_theme;
+        state.focus_theme = focus_theme;
+        state.break_theme = break_theme;
+        state.long_break_theme = long_break_theme;
+    }
+
+    export::refresh_ics_feed(&app_handle);
+    app_handle.emit_all("backup_imported", ())?;
+    Ok(())
+}
+
+#[command]
+pub async fn export_ics(
+    app_handle: AppHandle,
+    path: PathBuf,
+    options: ExportOptions,
+) -> Result<()> {
+    let config = ConfigManager::get::<SettingsConfig>()?;
+    let boundary = DayBoundary::from_settings(&config);
+    app_handle.db(|mut db| export::export_ics(&mut db, &path, &options, &boundary))
+}
+
+/// Sets the folder of the subscribable calendar feed and writes the feed right away,
+/// `None` turns the feed off.
+#[command]
+pub async fn set_ics_feed_dir(app_handle: AppHandle, dir: Option<PathBuf>) -> Result<()> {
+    let mut config = ConfigManager::get::<SettingsConfig>()?;
+    if let Some(dir) = &dir {
+        let boundary = DayBoundary::from_settings(&config);
+        app_handle.db(|mut db| export::write_ics_feed(&mut db, dir, &boundary))?;
+    }
+    config.ics_feed_dir = dir.map(|dir| dir.to_string_lossy().to_string());
+    ConfigManager::save(&config)?;
+
+    let payload = EventPayload { data: config };
+    app_handle.emit_all("settings_config_updated", payload)?;
+    Ok(())
+}
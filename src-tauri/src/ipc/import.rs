@@ -0,0 +1,44 @@
+//! Tauri IPC commands to import history from other apps.
+
+use std::{fs, path::PathBuf};
+
+use tauri::{command, AppHandle, Manager};
+
+use crate::{
+    config::{ConfigManager, SettingsConfig},
+    ctx::AppContext,
+    day::DayBoundary,
+    export,
+    import::{self, ImportFormat, ImportPreview, ParsedImport},
+    prelude::Result,
+};
+
+fn parse_file(path: &PathBuf, format: &ImportFormat) -> Result<ParsedImport> {
+    let content = fs::read_to_string(path)?;
+    let config = ConfigManager::get::<SettingsConfig>()?;
+    let boundary = DayBoundary::from_settings(&config);
+    format.importer().parse(&content, &boundary)
+}
+
+#[command]
+pub async fn preview_import(
+    app_handle: AppHandle,
+    path: PathBuf,
+    format: ImportFormat,
+) -> Result<ImportPreview> {
+    let parsed = parse_file(&path, &format)?;
+    app_handle.db(|mut db| import::preview_import(&mut db, parsed))
+}
+
+#[command]
+pub async fn import_history(
+    app_handle: AppHandle,
+    path: PathBuf,
+    format: ImportFormat,
+) -> Result<ImportPreview> {
+    let parsed = parse_file(&path, &format)?;
+    let preview = app_handle.db(|mut db| import::run_import(&mut db, parsed))?;
+    export::refresh_ics_feed(&app_handle);
+    app_handle.emit_all("history_imported", ())?;
+    Ok(preview)
+}
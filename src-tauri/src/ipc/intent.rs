@@ -4,8 +4,13 @@ use tauri::{command, AppHandle, Manager};
 
 use crate::{
     bmc::{DeleteIntentTag, IntentBmc, IntentTagBmc},
+    config::{ConfigManager, SettingsConfig},
     ctx::AppContext,
-    models::{CreateIntent, CreateIntentTag, Intent, Tag, UpdateIntent},
+    day::DayBoundary,
+    models::{
+        CreateIntent, CreateIntentTag, Intent, IntentNode, IntentTargetProgress, SetIntentDetails,
+        SetIntentTarget, Tag, UpdateIntent,
+    },
     prelude::Result,
 };
 
@@ -37,16 +42,78 @@ pub async fn get_intent(app_handle: AppHandle, id: i32) -> Result<Intent> {
 
 #[command]
 pub async fn get_intents(app_handle: AppHandle) -> Result<Vec<Intent>> {
+    app_handle.db(|mut db| IntentBmc::get_flat_list(&mut db))
+}
+
+#[command]
+pub async fn get_intent_tree(app_handle: AppHandle) -> Result<Vec<IntentNode>> {
     app_handle.db(|mut db| IntentBmc::get_list(&mut db))
 }
 
 #[command]
-pub async fn archive_intent(app_handle: AppHandle, id: i32) -> Result<i32> {
+pub async fn set_intent_parent(
+    app_handle: AppHandle,
+    id: i32,
+    parent_id: Option<i32>,
+) -> Result<i32> {
+    let id = app_handle.db(|mut db| IntentBmc::set_parent(&mut db, id, parent_id))?;
+    app_handle.emit_all("intent_updated", id)?;
+    Ok(id)
+}
+
+#[command]
+pub async fn archive_intent(app_handle: AppHandle, id: i32, cascade: Option<bool>) -> Result<i32> {
+    if cascade == Some(true) {
+        let ids = app_handle.db(|mut db| IntentBmc::archive_with_descendants(&mut db, id))?;
+        for id in ids {
+            app_handle.emit_all("intent_archived", id)?;
+        }
+        return Ok(id);
+    }
     let id = app_handle.db(|mut db| IntentBmc::archive(&mut db, id))?;
     app_handle.emit_all("intent_archived", id)?;
     Ok(id)
 }
 
+#[command]
+pub async fn set_intent_details(
+    app_handle: AppHandle,
+    id: i32,
+    data: SetIntentDetails,
+) -> Result<i32> {
+    let id = app_handle.db(|mut db| IntentBmc::set_details(&mut db, id, &data))?;
+    app_handle.emit_all("intent_updated", id)?;
+    Ok(id)
+}
+
+/// Emits `intents_reordered` as the positions of the siblings change as well.
+#[command]
+pub async fn move_intent(app_handle: AppHandle, id: i32, position: i32) -> Result<i32> {
+    let id = app_handle.db(|mut db| IntentBmc::move_to(&mut db, id, position))?;
+    app_handle.emit_all("intents_reordered", id)?;
+    Ok(id)
+}
+
+#[command]
+pub async fn set_intent_target(
+    app_handle: AppHandle,
+    id: i32,
+    data: SetIntentTarget,
+) -> Result<i32> {
+    let id = app_handle.db(|mut db| IntentBmc::set_target(&mut db, id, &data))?;
+    app_handle.emit_all("intent_updated", id)?;
+    Ok(id)
+}
+
+#[command]
+pub async fn get_intent_target_progress(
+    app_handle: AppHandle,
+) -> Result<Vec<IntentTargetProgress>> {
+    let config = ConfigManager::get::<SettingsConfig>()?;
+    let boundary = DayBoundary::from_settings(&config);
+    app_handle.db(|mut db| IntentBmc::get_target_progress(&mut db, &boundary))
+}
+
 #[command]
 pub async fn unarchive_intent(app_handle: AppHandle, id: i32) -> Result<i32> {
     let id = app_handle.db(|mut db| IntentBmc::unarchive(&mut db, id))?;
@@ -1,7 +1,12 @@
 mod config;
+mod export;
+mod import;
 mod intent;
+mod rest_day;
 mod script;
+mod search;
 mod session;
+mod stats;
 mod state;
 mod task;
 mod theme;
@@ -10,9 +15,14 @@ mod utils;
 mod tag;
 
 pub use config::*;
+pub use export::*;
+pub use import::*;
 pub use intent::*;
+pub use rest_day::*;
 pub use script::*;
+pub use search::*;
 pub use session::*;
+pub use stats::*;
 pub use state::*;
 pub use task::*;
 pub use theme::*;
@@ -26,5 +36,5 @@ use ts_rs::TS;
 #[derive(Serialize, Clone, TS)]
 #[ts(export, export_to = "../src/bindings/")]
 pub struct EventPayload<T> {
-    data: T,
+    pub data: T,
 }
@@ -0,0 +1,29 @@
+//! Tauri IPC commands to bridge the Rest Day Backend Model Controller with client side.
+
+use tauri::{command, AppHandle, Manager};
+
+use crate::{
+    bmc::RestDayBmc,
+    ctx::AppContext,
+    models::{CreateRestDay, RestDay},
+    prelude::Result,
+};
+
+#[command]
+pub async fn create_rest_day(app_handle: AppHandle, data: CreateRestDay) -> Result<i32> {
+    let id = app_handle.db(|mut db| RestDayBmc::create(&mut db, &data))?;
+    app_handle.emit_all("rest_day_created", id)?;
+    Ok(id)
+}
+
+#[command]
+pub async fn delete_rest_day(app_handle: AppHandle, id: i32) -> Result<i32> {
+    let id = app_handle.db(|mut db| RestDayBmc::delete(&mut db, id))?;
+    app_handle.emit_all("rest_day_deleted", id)?;
+    Ok(id)
+}
+
+#[command]
+pub async fn get_rest_days(app_handle: AppHandle) -> Result<Vec<RestDay>> {
+    app_handle.db(|mut db| RestDayBmc::get_list(&mut db))
+}
@@ -0,0 +1,15 @@
+//! Tauri IPC commands to bridge the Search Backend Model Controller with client side.
+
+use tauri::{command, AppHandle};
+
+use crate::{
+    bmc::{SearchBmc, SearchOptions},
+    ctx::AppContext,
+    models::SearchHit,
+    prelude::Result,
+};
+
+#[command]
+pub async fn search(app_handle: AppHandle, options: SearchOptions) -> Result<Vec<SearchHit>> {
+    app_handle.db(|mut db| SearchBmc::search(&mut db, &options))
+}
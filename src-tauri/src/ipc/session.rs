@@ -4,30 +4,59 @@ use tauri::{command, AppHandle, Manager};
 
 use crate::{
     bmc::{GetSessionsOptions, SessionBmc},
+    config::{ConfigManager, SettingsConfig},
     ctx::AppContext,
-    models::{CreateSession, Session, UpdateSession},
+    day::DayBoundary,
+    export,
+    models::{CreateManualSession, Session, SessionDetails, UpdateSession},
     prelude::Result,
+    progress,
 };
 
 use super::EventPayload;
 
+#[command]
+pub async fn add_manual_session(app_handle: AppHandle, data: CreateManualSession) -> Result<i32> {
+    let id = app_handle.db(|mut db| SessionBmc::create_manual(&mut db, &data))?;
+    export::refresh_ics_feed(&app_handle);
+    app_handle.emit_all("session_created", id)?;
+    let duration = data.finished_at - data.started_at;
+    progress::emit_intent_target_progress(&app_handle, data.intent_id, data.started_at, duration);
+    Ok(id)
+}
+
 #[command]
 pub async fn update_session(app_handle: AppHandle, id: i32, data: UpdateSession) -> Result<i32> {
     let id = app_handle.db(|mut db| SessionBmc::update(&mut db, id, &data))?;
+    export::refresh_ics_feed(&app_handle);
     let payload = EventPayload { data: id };
     app_handle.emit_all("session_updated", payload)?;
     Ok(id)
 }
 
 #[command]
-pub async fn get_session(app_handle: AppHandle, id: i32) -> Result<Session> {
+pub async fn delete_session(app_handle: AppHandle, id: i32) -> Result<i32> {
+    let id = app_handle.db(|mut db| SessionBmc::delete(&mut db, id))?;
+    export::refresh_ics_feed(&app_handle);
+    let payload = EventPayload { data: id };
+    app_handle.emit_all("session_deleted", payload)?;
+    Ok(id)
+}
+
+#[command]
+pub async fn get_session(app_handle: AppHandle, id: i32) -> Result<SessionDetails> {
     app_handle.db(|mut db| SessionBmc::get(&mut db, id))
 }
 
 #[command]
 pub async fn get_sessions(
     app_handle: AppHandle,
-    options: Option<GetSessionsOptions>,
+    mut options: Option<GetSessionsOptions>,
 ) -> Result<Vec<Session>> {
+    // Days are resolved as configured in the settings.
+    if let Some(options) = options.as_mut().filter(|options| options.day.is_some()) {
+        let config = ConfigManager::get::<SettingsConfig>()?;
+        options.resolve_day(&DayBoundary::from_settings(&config))?;
+    }
     app_handle.db(|mut db| SessionBmc::get_list(&mut db, options))
 }
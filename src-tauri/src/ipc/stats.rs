@@ -0,0 +1,60 @@
+//! Tauri IPC commands to bridge the Stats Backend Model Controller with client side.
+
+use chrono::NaiveDate;
+use tauri::{command, AppHandle};
+
+use crate::{
+    bmc::{GetStatsOptions, StatsBmc},
+    config::{ConfigManager, SettingsConfig, TimerConfig},
+    ctx::AppContext,
+    day::DayBoundary,
+    models::{GoalProgress, HeatmapDay, HourlyDistribution, Stats, Streaks},
+    prelude::Result,
+};
+
+fn get_day_boundary() -> Result<DayBoundary> {
+    let config = ConfigManager::get::<SettingsConfig>()?;
+    Ok(DayBoundary::from_settings(&config))
+}
+
+#[command]
+pub async fn get_stats(app_handle: AppHandle, options: GetStatsOptions) -> Result<Stats> {
+    let boundary = get_day_boundary()?;
+    app_handle.db(|mut db| StatsBmc::get(&mut db, &options, &boundary))
+}
+
+#[command]
+pub async fn get_heatmap(
+    app_handle: AppHandle,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<Vec<HeatmapDay>> {
+    let boundary = get_day_boundary()?;
+    app_handle.db(|mut db| StatsBmc::get_heatmap(&mut db, from, to, &boundary))
+}
+
+#[command]
+pub async fn get_hourly_distribution(
+    app_handle: AppHandle,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+) -> Result<HourlyDistribution> {
+    let boundary = get_day_boundary()?;
+    app_handle.db(|mut db| StatsBmc::get_hourly_distribution(&mut db, from, to, &boundary))
+}
+
+#[command]
+pub async fn get_streaks(app_handle: AppHandle) -> Result<Streaks> {
+    let boundary = get_day_boundary()?;
+    let config = ConfigManager::get::<TimerConfig>()?;
+    app_handle.db(|mut db| StatsBmc::get_streaks(&mut db, &boundary, &config.rest_weekdays))
+}
+
+#[command]
+pub async fn get_goal_progress(app_handle: AppHandle) -> Result<GoalProgress> {
+    let boundary = get_day_boundary()?;
+    let config = ConfigManager::get::<TimerConfig>()?;
+    app_handle.db(|mut db| {
+        StatsBmc::get_goal_progress(&mut db, &boundary, config.daily_goal, config.weekly_goal)
+    })
+}
@@ -1,11 +1,16 @@
 //! Tauri IPC commands to bridge the Task Backend Model Controller with client side.
 
+use chrono::NaiveDate;
 use tauri::{command, AppHandle, Manager};
 
 use crate::{
-    bmc::{GetTasksOptions, TaskBmc},
+    bmc::{CompletedTasks, GetTasksOptions, TaskBmc},
+    config::{ConfigManager, SettingsConfig, TimerConfig},
     ctx::AppContext,
-    models::{CreateTask, Task, UpdateTask},
+    day::DayBoundary,
+    models::{
+        CreateTask, EstimationReport, Task, TaskEstimate, TaskProgress, TaskRecurrence, UpdateTask,
+    },
     prelude::Result,
 };
 
@@ -27,11 +32,11 @@ pub async fn update_task(app_handle: AppHandle, id: i32, data: UpdateTask) -> Re
     Ok(id)
 }
 
+/// Emits `task_deleted` for the task and every subtask deleted along with it.
 #[command]
 pub async fn delete_task(app_handle: AppHandle, id: i32) -> Result<i32> {
-    let id = app_handle.db(|mut db| TaskBmc::delete(&mut db, id))?;
-    let payload = EventPayload { data: id };
-    app_handle.emit_all("task_deleted", payload)?;
+    let ids = app_handle.db(|mut db| TaskBmc::delete(&mut db, id))?;
+    emit_for_each(&app_handle, "task_deleted", &ids)?;
     Ok(id)
 }
 
@@ -43,23 +48,177 @@ pub async fn get_task(app_handle: AppHandle, id: i32) -> Result<Task> {
 #[command]
 pub async fn get_tasks(
     app_handle: AppHandle,
-    options: Option<GetTasksOptions>,
+    mut options: Option<GetTasksOptions>,
 ) -> Result<Vec<Task>> {
+    // Due filters are relative to the day as configured in the settings.
+    if let Some(options) = options.as_mut().filter(|options| options.today.is_none()) {
+        let config = ConfigManager::get::<SettingsConfig>()?;
+        options.today = Some(DayBoundary::from_settings(&config).today());
+    }
     app_handle.db(|mut db| TaskBmc::get_list(&mut db, options))
 }
 
+/// Emits `tasks_reordered` as the positions of the other tasks of the intent change as well.
+#[command]
+pub async fn move_task(app_handle: AppHandle, id: i32, position: i32) -> Result<i32> {
+    let id = app_handle.db(|mut db| TaskBmc::move_to(&mut db, id, position))?;
+    let payload = EventPayload { data: id };
+    app_handle.emit_all("tasks_reordered", payload)?;
+    Ok(id)
+}
+
+#[command]
+pub async fn set_task_due_date(
+    app_handle: AppHandle,
+    id: i32,
+    due_date: Option<NaiveDate>,
+) -> Result<i32> {
+    let id = app_handle.db(|mut db| TaskBmc::set_due_date(&mut db, id, due_date))?;
+    let payload = EventPayload { data: id };
+    app_handle.emit_all("task_updated", payload)?;
+    Ok(id)
+}
+
+#[command]
+pub async fn set_task_recurrence(
+    app_handle: AppHandle,
+    id: i32,
+    recurrence: Option<TaskRecurrence>,
+) -> Result<i32> {
+    let id = app_handle.db(|mut db| TaskBmc::set_recurrence(&mut db, id, recurrence))?;
+    let payload = EventPayload { data: id };
+    app_handle.emit_all("task_updated", payload)?;
+    Ok(id)
+}
+
 #[command]
-pub async fn complete_task(app_handle: AppHandle, id: i32) -> Result<i32> {
-    let id = app_handle.db(|mut db| TaskBmc::complete(&mut db, id))?;
+pub async fn get_task_history(app_handle: AppHandle, id: i32) -> Result<Vec<Task>> {
+    app_handle.db(|mut db| TaskBmc::get_history(&mut db, id))
+}
+
+#[command]
+pub async fn set_task_estimate(
+    app_handle: AppHandle,
+    id: i32,
+    estimated_pomodoros: Option<i32>,
+) -> Result<i32> {
+    let id = app_handle.db(|mut db| TaskBmc::set_estimate(&mut db, id, estimated_pomodoros))?;
     let payload = EventPayload { data: id };
-    app_handle.emit_all("task_completed", payload)?;
+    app_handle.emit_all("task_updated", payload)?;
     Ok(id)
 }
 
+/// Estimated minutes are based on the focus duration from the timer config.
+#[command]
+pub async fn get_task_estimate(app_handle: AppHandle, id: i32) -> Result<TaskEstimate> {
+    let config = ConfigManager::get::<TimerConfig>()?;
+    app_handle.db(|mut db| TaskBmc::get_estimate(&mut db, id, config.focus_duration))
+}
+
+#[command]
+pub async fn get_estimation_report(
+    app_handle: AppHandle,
+    intent_id: Option<i32>,
+) -> Result<EstimationReport> {
+    let config = ConfigManager::get::<TimerConfig>()?;
+    app_handle
+        .db(|mut db| TaskBmc::get_estimation_report(&mut db, intent_id, config.focus_duration))
+}
+
+#[command]
+pub async fn get_task_progress(app_handle: AppHandle, intent_id: i32) -> Result<Vec<TaskProgress>> {
+    app_handle.db(|mut db| TaskBmc::get_progress(&mut db, intent_id))
+}
+
+#[command]
+pub async fn complete_task(app_handle: AppHandle, id: i32, cascade: Option<bool>) -> Result<i32> {
+    let today = today()?;
+    let completed = if cascade == Some(true) {
+        app_handle.db(|mut db| TaskBmc::complete_with_subtasks(&mut db, id, today))?
+    } else {
+        app_handle.db(|mut db| TaskBmc::complete(&mut db, id, today))?
+    };
+    emit_completed(&app_handle, &completed)?;
+    Ok(id)
+}
+
+/// Emits `task_uncompleted` for the task and every parent reopened along with it.
 #[command]
 pub async fn uncomplete_task(app_handle: AppHandle, id: i32) -> Result<i32> {
-    let id = app_handle.db(|mut db| TaskBmc::uncomplete(&mut db, id))?;
+    let ids = app_handle.db(|mut db| TaskBmc::uncomplete(&mut db, id))?;
+    emit_for_each(&app_handle, "task_uncompleted", &ids)?;
+    Ok(id)
+}
+
+#[command]
+pub async fn copy_task(app_handle: AppHandle, id: i32, intent_id: i32) -> Result<i32> {
+    let id = app_handle.db(|mut db| TaskBmc::copy_to_intent(&mut db, id, intent_id))?;
     let payload = EventPayload { data: id };
-    app_handle.emit_all("task_uncompleted", payload)?;
+    app_handle.emit_all("task_created", payload)?;
     Ok(id)
 }
+
+#[command]
+pub async fn move_tasks_to_intent(
+    app_handle: AppHandle,
+    ids: Vec<i32>,
+    intent_id: i32,
+) -> Result<Vec<i32>> {
+    let ids = app_handle.db(|mut db| TaskBmc::move_many_to_intent(&mut db, &ids, intent_id))?;
+    emit_for_each(&app_handle, "task_updated", &ids)?;
+    Ok(ids)
+}
+
+#[command]
+pub async fn complete_tasks(app_handle: AppHandle, ids: Vec<i32>) -> Result<Vec<i32>> {
+    let today = today()?;
+    let completed = app_handle.db(|mut db| TaskBmc::complete_many(&mut db, &ids, today))?;
+    emit_completed(&app_handle, &completed)?;
+    Ok(completed.completed_ids)
+}
+
+#[command]
+pub async fn uncomplete_tasks(app_handle: AppHandle, ids: Vec<i32>) -> Result<Vec<i32>> {
+    let ids = app_handle.db(|mut db| TaskBmc::uncomplete_many(&mut db, &ids))?;
+    emit_for_each(&app_handle, "task_uncompleted", &ids)?;
+    Ok(ids)
+}
+
+#[command]
+pub async fn delete_tasks(app_handle: AppHandle, ids: Vec<i32>) -> Result<Vec<i32>> {
+    let ids = app_handle.db(|mut db| TaskBmc::delete_many(&mut db, &ids))?;
+    emit_for_each(&app_handle, "task_deleted", &ids)?;
+    Ok(ids)
+}
+
+/// Deletes tasks completed more than `days` days ago, of every intent unless one is given.
+#[command]
+pub async fn clear_completed_tasks(
+    app_handle: AppHandle,
+    days: i64,
+    intent_id: Option<i32>,
+) -> Result<Vec<i32>> {
+    let ids = app_handle.db(|mut db| TaskBmc::clear_completed(&mut db, days, intent_id))?;
+    emit_for_each(&app_handle, "task_deleted", &ids)?;
+    Ok(ids)
+}
+
+fn emit_for_each(app_handle: &AppHandle, event: &str, ids: &[i32]) -> Result<()> {
+    for id in ids {
+        app_handle.emit_all(event, EventPayload { data: *id })?;
+    }
+    Ok(())
+}
+
+/// Emits `task_completed` for the completed tasks and `task_created` for the generated
+/// next instances of recurring ones.
+fn emit_completed(app_handle: &AppHandle, completed: &CompletedTasks) -> Result<()> {
+    emit_for_each(app_handle, "task_completed", &completed.completed_ids)?;
+    emit_for_each(app_handle, "task_created", &completed.created_ids)
+}
+
+/// The current day as configured in the settings.
+fn today() -> Result<NaiveDate> {
+    let config = ConfigManager::get::<SettingsConfig>()?;
+    Ok(DayBoundary::from_settings(&config).today())
+}
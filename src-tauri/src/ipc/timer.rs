@@ -2,9 +2,11 @@ use tauri::{command, AppHandle};
 use tokio::sync::Mutex;
 
 use crate::{
+    bmc::{IntentBmc, TaskBmc},
+    ctx::AppContext,
     models::Intent,
-    prelude::Result,
-    timer::{Queue, QueueSession, Timer, TimerSession},
+    prelude::{Minutes, Result},
+    timer::{Queue, QueueSession, RestoredSessionAction, Timer, TimerSession},
 };
 
 #[command]
@@ -25,6 +27,25 @@ pub async fn timer_set_session_intent(
     Ok(())
 }
 
+/// Switches the session over to the task's intent first, so the session and its task agree.
+#[command]
+pub async fn timer_set_session_task(
+    app_handle: AppHandle,
+    state: tauri::State<'_, Mutex<Timer>>,
+    task_id: Option<i32>,
+) -> Result<()> {
+    let mut timer = state.lock().await;
+    if let Some(task_id) = task_id {
+        let intent = app_handle.db(|mut db| {
+            let task = TaskBmc::get(&mut db, task_id)?;
+            IntentBmc::get(&mut db, task.intent_id)
+        })?;
+        timer.set_session_intent(app_handle, intent).await;
+    }
+    timer.set_session_task(task_id).await?;
+    Ok(())
+}
+
 #[command]
 pub async fn timer_play(state: tauri::State<'_, Mutex<Timer>>) -> Result<()> {
     let mut timer = state.lock().await;
@@ -53,6 +74,40 @@ pub async fn timer_skip(state: tauri::State<'_, Mutex<Timer>>) -> Result<()> {
     Ok(())
 }
 
+#[command]
+pub async fn timer_extend(state: tauri::State<'_, Mutex<Timer>>, minutes: Minutes) -> Result<()> {
+    let mut timer = state.lock().await;
+    timer.extend(minutes).await?;
+    Ok(())
+}
+
+#[command]
+pub async fn timer_shorten(state: tauri::State<'_, Mutex<Timer>>, minutes: Minutes) -> Result<()> {
+    let mut timer = state.lock().await;
+    timer.shorten(minutes).await?;
+    Ok(())
+}
+
+#[command]
+pub async fn timer_snooze_break(
+    state: tauri::State<'_, Mutex<Timer>>,
+    minutes: Minutes,
+) -> Result<()> {
+    let mut timer = state.lock().await;
+    timer.snooze_break(minutes).await?;
+    Ok(())
+}
+
+#[command]
+pub async fn timer_resolve_restored_session(
+    state: tauri::State<'_, Mutex<Timer>>,
+    action: RestoredSessionAction,
+) -> Result<()> {
+    let mut timer = state.lock().await;
+    timer.resolve_restored_session(action).await?;
+    Ok(())
+}
+
 #[command]
 pub async fn timer_get_queue(state: tauri::State<'_, Mutex<Timer>>) -> Result<Queue> {
     let timer = state.lock().await;
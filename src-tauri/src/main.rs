@@ -6,11 +6,15 @@
 mod bmc;
 mod config;
 mod ctx;
+mod day;
 mod db;
 mod error;
+mod export;
+mod import;
 mod ipc;
 mod models;
 mod prelude;
+mod progress;
 mod setup;
 mod state;
 mod timer;
@@ -56,6 +60,12 @@ fn main() -> Result<()> {
             // Intent
             get_intent,
             get_intents,
+            get_intent_tree,
+            set_intent_parent,
+            set_intent_details,
+            move_intent,
+            set_intent_target,
+            get_intent_target_progress,
             create_intent,
             update_intent,
             delete_intent,
@@ -69,12 +79,49 @@ fn main() -> Result<()> {
             delete_task,
             get_task,
             get_tasks,
+            move_task,
+            set_task_due_date,
+            set_task_recurrence,
+            get_task_history,
+            set_task_estimate,
+            get_task_estimate,
+            get_estimation_report,
+            get_task_progress,
             complete_task,
             uncomplete_task,
+            copy_task,
+            move_tasks_to_intent,
+            complete_tasks,
+            uncomplete_tasks,
+            delete_tasks,
+            clear_completed_tasks,
             // Session
+            add_manual_session,
             update_session,
+            delete_session,
             get_session,
             get_sessions,
+            // Stats
+            get_stats,
+            get_heatmap,
+            get_hourly_distribution,
+            get_streaks,
+            get_goal_progress,
+            // Export
+            export_csv,
+            export_backup,
+            import_backup,
+            export_ics,
+            set_ics_feed_dir,
+            // Import
+            preview_import,
+            import_history,
+            // Search
+            search,
+            // Rest days
+            create_rest_day,
+            delete_rest_day,
+            get_rest_days,
             // Script
             create_script,
             update_script,
@@ -84,10 +131,15 @@ fn main() -> Result<()> {
             // Timer
             timer_get_session,
             timer_set_session_intent,
+            timer_set_session_task,
             timer_play,
             timer_stop,
             timer_restart,
             timer_skip,
+            timer_extend,
+            timer_shorten,
+            timer_snooze_break,
+            timer_resolve_restored_session,
             timer_get_queue,
             timer_add_to_queue,
             timer_remove_from_queue,
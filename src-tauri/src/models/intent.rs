@@ -1,9 +1,17 @@
 use diesel::prelude::*;
-use diesel::Queryable;
+use diesel::{
+    deserialize::{self, FromSql, FromSqlRow},
+    expression::AsExpression,
+    serialize::{self, IsNull, Output, ToSql},
+    sql_types::Text,
+    sqlite::{Sqlite, SqliteValue},
+    Queryable,
+};
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 
 use crate::db::schema::{intent_tags, intents};
+use crate::prelude::Minutes;
 
 #[derive(Queryable, Selectable, Serialize, Deserialize, TS, Debug, Clone)]
 #[diesel(table_name = intents, check_for_backend(diesel::sqlite::Sqlite))]
@@ -16,6 +24,76 @@ pub struct Intent {
     pub created_at: i64,
     #[ts(type = "number")]
     pub archived_at: Option<i64>,
+    /// Intent this one is nested under, e.g. the project of a workstream.
+    #[serde(default)]
+    pub parent_id: Option<i32>,
+    /// Period over which `target` and `budget` are counted.
+    #[serde(default)]
+    pub target_period: IntentTargetPeriod,
+    /// Focus time to aim for, sessions of sub-intents count towards it too.
+    #[serde(default)]
+    #[ts(type = "number | null")]
+    pub target: Option<Minutes>,
+    /// Focus time that should not be exceeded, e.g. a billable cap.
+    #[serde(default)]
+    #[ts(type = "number | null")]
+    pub budget: Option<Minutes>,
+    /// Hex color, e.g. `#ff8800`.
+    #[serde(default)]
+    pub color: Option<String>,
+    /// Emoji or icon name shown next to the label.
+    #[serde(default)]
+    pub icon: Option<String>,
+    /// Markdown description.
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Position among the intents sharing the same parent.
+    #[serde(default)]
+    pub position: i32,
+}
+
+#[derive(
+    TS, Deserialize, Serialize, AsExpression, FromSqlRow, Debug, Clone, Copy, PartialEq, Default,
+)]
+#[diesel(sql_type = Text)]
+#[ts(export, export_to = "../src/bindings/")]
+pub enum IntentTargetPeriod {
+    /// Current ISO week.
+    #[default]
+    Weekly,
+    /// Whole lifetime of the intent.
+    Total,
+}
+
+impl ToSql<Text, Sqlite> for IntentTargetPeriod {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Sqlite>) -> serialize::Result {
+        let value = match self {
+            IntentTargetPeriod::Weekly => "Weekly",
+            IntentTargetPeriod::Total => "Total",
+        };
+        out.set_value(value);
+        Ok(IsNull::No)
+    }
+}
+
+impl FromSql<Text, Sqlite> for IntentTargetPeriod {
+    fn from_sql(value: SqliteValue<'_, '_, '_>) -> deserialize::Result<Self> {
+        let value = <String as FromSql<Text, Sqlite>>::from_sql(value)?;
+        match value.as_str() {
+            "Weekly" => Ok(IntentTargetPeriod::Weekly),
+            "Total" => Ok(IntentTargetPeriod::Total),
+            _ => Err(format!("Unrecognized target period: {}", value).into()),
+        }
+    }
+}
+
+/// Intent along with its nested sub-intents.
+#[derive(Serialize, TS, Debug, Clone)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct IntentNode {
+    #[serde(flatten)]
+    pub intent: Intent,
+    pub children: Vec<IntentNode>,
 }
 
 #[derive(Insertable, Deserialize, TS, Debug, Clone)]
@@ -23,6 +101,7 @@ pub struct Intent {
 #[ts(export, export_to = "../src/bindings/")]
 pub struct CreateIntent {
     pub label: String,
+    pub parent_id: Option<i32>,
 }
 
 #[derive(AsChangeset, TS, Deserialize)]
@@ -33,8 +112,65 @@ pub struct UpdateIntent {
     pub pinned: Option<bool>,
 }
 
+/// Replaces the target and budget of an intent, `None` clears them.
+#[derive(AsChangeset, TS, Deserialize, Debug, Clone)]
+#[diesel(table_name = intents, treat_none_as_null = true)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct SetIntentTarget {
+    pub target_period: IntentTargetPeriod,
+    #[ts(type = "number | null")]
+    pub target: Option<Minutes>,
+    #[ts(type = "number | null")]
+    pub budget: Option<Minutes>,
+}
+
+/// Replaces the color, icon and description of an intent, `None` clears them.
+#[derive(AsChangeset, TS, Deserialize, Debug, Clone, Default)]
+#[diesel(table_name = intents, treat_none_as_null = true)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct SetIntentDetails {
+    pub color: Option<String>,
+    pub icon: Option<String>,
+    pub description: Option<String>,
+}
+
+/// Focus time of an intent and its sub-intents within the target period.
+#[derive(Serialize, TS, Debug, Clone, PartialEq)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct IntentTargetProgress {
+    pub intent_id: i32,
+    pub target_period: IntentTargetPeriod,
+    /// Seconds focused within the period.
+    #[ts(type = "number")]
+    pub focused: i64,
+    #[ts(type = "number | null")]
+    pub target: Option<Minutes>,
+    #[ts(type = "number | null")]
+    pub budget: Option<Minutes>,
+}
+
+impl IntentTargetProgress {
+    pub fn is_target_reached(&self) -> bool {
+        self.target
+            .map_or(false, |target| self.focused >= target * 60)
+    }
+
+    pub fn is_budget_exceeded(&self) -> bool {
+        self.budget
+            .map_or(false, |budget| self.focused > budget * 60)
+    }
+
+    /// Progress as it was before the given amount of seconds was focused.
+    pub fn before(&self, seconds: i64) -> Self {
+        Self {
+            focused: self.focused - seconds,
+            ..self.clone()
+        }
+    }
+}
+
 // Struct for many-to-many relationship
-#[derive(Queryable, Identifiable)]
+#[derive(Queryable, Identifiable, Serialize, Deserialize, Debug, Clone)]
 #[diesel(table_name = intent_tags, check_for_backend(diesel::sqlite::Sqlite))]
 pub struct IntentTag {
     pub id: i32,
@@ -1,13 +1,19 @@
 mod intent;
+mod rest_day;
 mod script;
+mod search;
 mod session;
+mod stats;
 mod tag;
 mod task;
 mod theme;
 
 pub use intent::*;
+pub use rest_day::*;
 pub use script::*;
+pub use search::*;
 pub use session::*;
+pub use stats::*;
 pub use tag::*;
 pub use task::*;
 pub use theme::*;
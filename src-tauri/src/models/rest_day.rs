@@ -0,0 +1,25 @@
+use chrono::NaiveDate;
+use diesel::prelude::*;
+use diesel::Queryable;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::db::schema::rest_days;
+
+/// A day off, e.g. a vacation day, which does not break the focus streak.
+#[derive(Queryable, Selectable, Serialize, Deserialize, TS, Debug, Clone, PartialEq)]
+#[diesel(table_name = rest_days, check_for_backend(diesel::sqlite::Sqlite))]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct RestDay {
+    pub id: i32,
+    #[ts(type = "string")]
+    pub day: NaiveDate,
+}
+
+#[derive(Insertable, Deserialize, TS, Debug, Clone)]
+#[diesel(table_name = rest_days, check_for_backend(diesel::sqlite::Sqlite))]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct CreateRestDay {
+    #[ts(type = "string")]
+    pub day: NaiveDate,
+}
@@ -0,0 +1,61 @@
+use diesel::sql_types::{Double, Integer, Text};
+use diesel::QueryableByName;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+#[derive(Serialize, Deserialize, TS, Debug, Clone, Copy, PartialEq)]
+#[ts(export, export_to = "../src/bindings/")]
+pub enum SearchHitKind {
+    Intent,
+    Task,
+    Tag,
+    /// Matched by the session's summary.
+    Session,
+}
+
+impl SearchHitKind {
+    /// Value of the `kind` column in the search index.
+    pub fn as_index_kind(&self) -> &'static str {
+        match self {
+            SearchHitKind::Intent => "intent",
+            SearchHitKind::Task => "task",
+            SearchHitKind::Tag => "tag",
+            SearchHitKind::Session => "session",
+        }
+    }
+
+    pub fn from_index_kind(kind: &str) -> Option<Self> {
+        match kind {
+            "intent" => Some(SearchHitKind::Intent),
+            "task" => Some(SearchHitKind::Task),
+            "tag" => Some(SearchHitKind::Tag),
+            "session" => Some(SearchHitKind::Session),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Serialize, TS, Debug, Clone, PartialEq)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct SearchHit {
+    pub kind: SearchHitKind,
+    /// Id of the matched intent, task, tag or session.
+    pub id: i32,
+    /// HTML-escaped matched text with the matching terms wrapped in `<mark>` and `</mark>`.
+    pub snippet: String,
+    /// BM25 score, lower is more relevant.
+    pub rank: f64,
+}
+
+/// Raw row of the search index query.
+#[derive(QueryableByName, Debug)]
+pub struct SearchIndexRow {
+    #[diesel(sql_type = Text)]
+    pub kind: String,
+    #[diesel(sql_type = Integer)]
+    pub item_id: i32,
+    #[diesel(sql_type = Text)]
+    pub snippet: String,
+    #[diesel(sql_type = Double)]
+    pub rank: f64,
+}
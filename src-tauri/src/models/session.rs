@@ -3,9 +3,10 @@ use diesel::Queryable;
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 
-use crate::db::schema::sessions;
+use crate::db::schema::{session_pauses, sessions};
+use crate::timer::SessionType;
 
-#[derive(Queryable, Selectable, Serialize, TS, Debug, Clone)]
+#[derive(Queryable, Selectable, Serialize, Deserialize, TS, Debug, Clone)]
 #[diesel(table_name = sessions, check_for_backend(diesel::sqlite::Sqlite))]
 #[ts(export, export_to = "../src/bindings/")]
 pub struct Session {
@@ -17,6 +18,9 @@ pub struct Session {
     #[ts(type = "number")]
     pub finished_at: i64,
     pub intent_id: i32,
+    pub session_type: SessionType,
+    pub planned_duration: Option<i32>,
+    pub is_manual: bool,
 }
 
 #[derive(Insertable, Deserialize, TS, Debug, Clone)]
@@ -27,11 +31,63 @@ pub struct CreateSession {
     #[ts(type = "number")]
     pub started_at: i64,
     pub intent_id: i32,
+    #[serde(default)]
+    pub session_type: SessionType,
+    pub planned_duration: Option<i32>,
 }
 
-#[derive(AsChangeset, TS, Deserialize)]
+/// A focus session logged by hand, e.g. for work done away from the computer.
+#[derive(Deserialize, TS, Debug, Clone)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct CreateManualSession {
+    pub intent_id: i32,
+    #[ts(type = "number")]
+    pub started_at: i64,
+    #[ts(type = "number")]
+    pub finished_at: i64,
+}
+
+#[derive(AsChangeset, TS, Deserialize, Default)]
 #[diesel(table_name = sessions, check_for_backend(diesel::sqlite::Sqlite))]
 #[ts(export, export_to = "../src/bindings/")]
 pub struct UpdateSession {
     pub summary: Option<String>,
+    pub duration: Option<i32>,
+    #[ts(type = "number | null")]
+    pub started_at: Option<i64>,
+    #[ts(type = "number | null")]
+    pub finished_at: Option<i64>,
+    pub intent_id: Option<i32>,
+}
+
+/// A session together with the pauses that interrupted it.
+#[derive(Serialize, TS, Debug, Clone)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct SessionDetails {
+    #[serde(flatten)]
+    pub session: Session,
+    pub pauses: Vec<SessionPause>,
+}
+
+#[derive(Queryable, Selectable, Serialize, Deserialize, TS, Debug, Clone, PartialEq)]
+#[diesel(table_name = session_pauses, check_for_backend(diesel::sqlite::Sqlite))]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct SessionPause {
+    pub id: i32,
+    pub session_id: i32,
+    #[ts(type = "number")]
+    pub started_at: i64,
+    #[ts(type = "number")]
+    pub finished_at: i64,
+}
+
+#[derive(Insertable, Deserialize, TS, Debug, Clone)]
+#[diesel(table_name = session_pauses, check_for_backend(diesel::sqlite::Sqlite))]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct CreateSessionPause {
+    pub session_id: i32,
+    #[ts(type = "number")]
+    pub started_at: i64,
+    #[ts(type = "number")]
+    pub finished_at: i64,
 }
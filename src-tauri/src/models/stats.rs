@@ -0,0 +1,102 @@
+use chrono::NaiveDate;
+use diesel::sql_types::{BigInt, Double, Integer, Nullable, Text};
+use diesel::QueryableByName;
+use serde::Serialize;
+use ts_rs::TS;
+
+use crate::prelude::Minutes;
+
+/// Aggregated focus time over a set of sessions, durations are in seconds.
+#[derive(Serialize, TS, Debug, Clone)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct Stats {
+    #[ts(type = "number")]
+    pub total_duration: i64,
+    #[ts(type = "number")]
+    pub sessions_count: i64,
+    pub average_duration: f64,
+    pub entries: Vec<StatsEntry>,
+}
+
+/// Focus time of a single group, e.g. a day or an intent.
+#[derive(QueryableByName, Serialize, TS, Debug, Clone, PartialEq)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct StatsEntry {
+    /// Period (`2023-07-10`, `2023-W28`, `2023-07`) or intent/tag label.
+    #[diesel(sql_type = Text)]
+    pub key: String,
+    /// Intent or tag id, empty when grouping by date.
+    #[diesel(sql_type = Nullable<Integer>)]
+    pub id: Option<i32>,
+    /// Color of the intent when grouping by intent.
+    #[diesel(sql_type = Nullable<Text>)]
+    pub color: Option<String>,
+    #[diesel(sql_type = BigInt)]
+    #[ts(type = "number")]
+    pub total_duration: i64,
+    #[diesel(sql_type = BigInt)]
+    #[ts(type = "number")]
+    pub sessions_count: i64,
+    #[diesel(sql_type = Double)]
+    pub average_duration: f64,
+}
+
+/// Consecutive days with at least one focus session, rest days are skipped over.
+#[derive(Serialize, TS, Debug, Clone, PartialEq)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct Streaks {
+    pub current: u32,
+    pub longest: u32,
+}
+
+/// Focus time of the current day and week against the configured goals.
+#[derive(Serialize, TS, Debug, Clone, PartialEq)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct GoalProgress {
+    /// Seconds focused today.
+    #[ts(type = "number")]
+    pub daily_focused: i64,
+    #[ts(type = "number")]
+    pub daily_goal: Minutes,
+    /// Seconds focused this week.
+    #[ts(type = "number")]
+    pub weekly_focused: i64,
+    #[ts(type = "number")]
+    pub weekly_goal: Minutes,
+}
+
+impl GoalProgress {
+    pub fn is_daily_goal_reached(&self) -> bool {
+        self.daily_goal > 0 && self.daily_focused >= self.daily_goal * 60
+    }
+}
+
+/// Focused seconds of a single day, used to render a calendar heatmap.
+#[derive(Serialize, TS, Debug, Clone, PartialEq)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct HeatmapDay {
+    #[ts(type = "string")]
+    pub day: NaiveDate,
+    #[ts(type = "number")]
+    pub duration: i64,
+}
+
+/// Focused seconds by local weekday and hour, indexed as `hours[weekday][hour]`
+/// with weekdays starting on Monday.
+#[derive(Serialize, TS, Debug, Clone, PartialEq)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct HourlyDistribution {
+    #[ts(type = "Array<Array<number>>")]
+    pub hours: Vec<Vec<i64>>,
+}
+
+/// Totals over the whole filtered range.
+#[derive(QueryableByName, Debug, Clone)]
+pub struct StatsTotals {
+    #[diesel(sql_type = BigInt)]
+    pub total_duration: i64,
+    #[diesel(sql_type = BigInt)]
+    pub sessions_count: i64,
+    #[diesel(sql_type = Double)]
+    pub average_duration: f64,
+}
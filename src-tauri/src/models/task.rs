@@ -1,11 +1,20 @@
+use chrono::{Datelike, NaiveDate};
 use diesel::prelude::*;
-use diesel::Queryable;
+use diesel::{
+    deserialize::{self, FromSql, FromSqlRow},
+    expression::AsExpression,
+    serialize::{self, IsNull, Output, ToSql},
+    sql_types::{Integer, Text},
+    sqlite::{Sqlite, SqliteValue},
+    Queryable,
+};
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 
-use crate::db::schema::tasks;
+use crate::db::schema::{task_sessions, tasks};
+use crate::prelude::Minutes;
 
-#[derive(Queryable, Selectable, Serialize, TS, Debug, Clone)]
+#[derive(Queryable, Selectable, Serialize, Deserialize, TS, Debug, Clone)]
 #[diesel(table_name = tasks, check_for_backend(diesel::sqlite::Sqlite))]
 #[ts(export, export_to = "../src/bindings/")]
 pub struct Task {
@@ -17,6 +26,184 @@ pub struct Task {
     #[ts(type = "number")]
     pub finished_at: Option<i64>,
     pub intent_id: i32,
+    /// Position among the tasks sharing the same intent and parent task.
+    #[serde(default)]
+    pub position: i32,
+    #[serde(default)]
+    pub priority: TaskPriority,
+    #[serde(default)]
+    #[ts(type = "string | null")]
+    pub due_date: Option<NaiveDate>,
+    /// Task this one is a step of.
+    #[serde(default)]
+    pub parent_task_id: Option<i32>,
+    /// Number of focus sessions the task is expected to take.
+    #[serde(default)]
+    pub estimated_pomodoros: Option<i32>,
+    /// Rule the next instance is generated by, only the latest instance of a series has one.
+    #[serde(default)]
+    pub recurrence: Option<TaskRecurrence>,
+    /// First task of the recurring series this task is an instance of.
+    #[serde(default)]
+    pub series_id: Option<i32>,
+}
+
+/// Stored as an integer so that tasks can be sorted by it.
+#[derive(
+    TS,
+    Deserialize,
+    Serialize,
+    AsExpression,
+    FromSqlRow,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Default,
+)]
+#[diesel(sql_type = Integer)]
+#[ts(export, export_to = "../src/bindings/")]
+pub enum TaskPriority {
+    Low,
+    #[default]
+    Normal,
+    High,
+}
+
+impl ToSql<Integer, Sqlite> for TaskPriority {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Sqlite>) -> serialize::Result {
+        let value = match self {
+            TaskPriority::Low => 0,
+            TaskPriority::Normal => 1,
+            TaskPriority::High => 2,
+        };
+        out.set_value(value);
+        Ok(IsNull::No)
+    }
+}
+
+impl FromSql<Integer, Sqlite> for TaskPriority {
+    fn from_sql(value: SqliteValue<'_, '_, '_>) -> deserialize::Result<Self> {
+        let value = <i32 as FromSql<Integer, Sqlite>>::from_sql(value)?;
+        match value {
+            0 => Ok(TaskPriority::Low),
+            1 => Ok(TaskPriority::Normal),
+            2 => Ok(TaskPriority::High),
+            _ => Err(format!("Unrecognized task priority: {}", value).into()),
+        }
+    }
+}
+
+/// Stored as text, e.g. `Weekly:0,4` for Mondays and Fridays.
+#[derive(TS, Deserialize, Serialize, AsExpression, FromSqlRow, Debug, Clone, PartialEq)]
+#[diesel(sql_type = Text)]
+#[ts(export, export_to = "../src/bindings/")]
+pub enum TaskRecurrence {
+    Daily,
+    /// Monday to Friday.
+    Weekdays,
+    /// Days of the week, `0` being Monday.
+    Weekly(Vec<u32>),
+    /// Day of the month, clamped to the last day in shorter months.
+    Monthly(u32),
+}
+
+impl TaskRecurrence {
+    /// Weekly rules need at least one day and days must exist.
+    pub fn is_valid(&self) -> bool {
+        match self {
+            TaskRecurrence::Weekly(days) => !days.is_empty() && days.iter().all(|day| *day < 7),
+            TaskRecurrence::Monthly(day) => (1..=31).contains(day),
+            _ => true,
+        }
+    }
+
+    /// First day after `after` that the rule falls on, but not before `earliest`.
+    pub fn next_after(&self, after: NaiveDate, earliest: NaiveDate) -> NaiveDate {
+        let mut day = after.succ_opt().unwrap().max(earliest);
+        if let TaskRecurrence::Monthly(day_of_month) = self {
+            let (mut year, mut month) = (day.year(), day.month());
+            loop {
+                let last_day = Self::last_day_of_month(year, month);
+                let candidate =
+                    NaiveDate::from_ymd_opt(year, month, (*day_of_month).min(last_day)).unwrap();
+                if candidate >= day {
+                    return candidate;
+                }
+                (year, month) = if month == 12 {
+                    (year + 1, 1)
+                } else {
+                    (year, month + 1)
+                };
+            }
+        }
+        // Every valid weekly rule falls on one of the next seven days.
+        for _ in 0..7 {
+            if self.falls_on(day) {
+                break;
+            }
+            day = day.succ_opt().unwrap();
+        }
+        day
+    }
+
+    fn falls_on(&self, day: NaiveDate) -> bool {
+        let weekday = day.weekday().num_days_from_monday();
+        match self {
+            TaskRecurrence::Daily => true,
+            TaskRecurrence::Weekdays => weekday < 5,
+            TaskRecurrence::Weekly(days) => days.contains(&weekday),
+            TaskRecurrence::Monthly(day_of_month) => day.day() == *day_of_month,
+        }
+    }
+
+    fn last_day_of_month(year: i32, month: u32) -> u32 {
+        let next_month = if month == 12 {
+            NaiveDate::from_ymd_opt(year + 1, 1, 1)
+        } else {
+            NaiveDate::from_ymd_opt(year, month + 1, 1)
+        };
+        next_month.unwrap().pred_opt().unwrap().day()
+    }
+}
+
+impl ToSql<Text, Sqlite> for TaskRecurrence {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Sqlite>) -> serialize::Result {
+        let value = match self {
+            TaskRecurrence::Daily => "Daily".to_string(),
+            TaskRecurrence::Weekdays => "Weekdays".to_string(),
+            TaskRecurrence::Weekly(days) => {
+                let days: Vec<String> = days.iter().map(|day| day.to_string()).collect();
+                format!("Weekly:{}", days.join(","))
+            }
+            TaskRecurrence::Monthly(day) => format!("Monthly:{}", day),
+        };
+        out.set_value(value);
+        Ok(IsNull::No)
+    }
+}
+
+impl FromSql<Text, Sqlite> for TaskRecurrence {
+    fn from_sql(value: SqliteValue<'_, '_, '_>) -> deserialize::Result<Self> {
+        let value = <String as FromSql<Text, Sqlite>>::from_sql(value)?;
+        let (kind, args) = value.split_once(':').unwrap_or((value.as_str(), ""));
+        match kind {
+            "Daily" => Ok(TaskRecurrence::Daily),
+            "Weekdays" => Ok(TaskRecurrence::Weekdays),
+            "Weekly" => {
+                let days = args
+                    .split(',')
+                    .map(|day| day.parse())
+                    .collect::<std::result::Result<_, _>>()?;
+                Ok(TaskRecurrence::Weekly(days))
+            }
+            "Monthly" => Ok(TaskRecurrence::Monthly(args.parse()?)),
+            _ => Err(format!("Unrecognized task recurrence: {}", value).into()),
+        }
+    }
 }
 
 #[derive(Insertable, Deserialize, TS, Debug, Clone)]
@@ -25,6 +212,13 @@ pub struct Task {
 pub struct CreateTask {
     pub body: String,
     pub intent_id: i32,
+    #[serde(default)]
+    pub priority: TaskPriority,
+    #[serde(default)]
+    #[ts(type = "string | null")]
+    pub due_date: Option<NaiveDate>,
+    #[serde(default)]
+    pub parent_task_id: Option<i32>,
 }
 
 #[derive(AsChangeset, TS, Deserialize)]
@@ -32,4 +226,59 @@ pub struct CreateTask {
 #[ts(export, export_to = "../src/bindings/")]
 pub struct UpdateTask {
     pub body: Option<String>,
+    pub priority: Option<TaskPriority>,
+    /// Moves the task along with its subtasks to another intent.
+    pub intent_id: Option<i32>,
+}
+
+/// Completed and total subtasks of a task, counting all nesting levels.
+#[derive(Serialize, TS, Debug, Clone, PartialEq)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct TaskProgress {
+    pub task_id: i32,
+    pub completed: i32,
+    pub total: i32,
+}
+
+/// Focus session spent working on a task.
+#[derive(Queryable, Identifiable, Serialize, Deserialize, Debug, Clone)]
+#[diesel(table_name = task_sessions, check_for_backend(diesel::sqlite::Sqlite))]
+pub struct TaskSession {
+    pub id: i32,
+    pub task_id: i32,
+    pub session_id: i32,
+}
+
+#[derive(Insertable, Deserialize, Debug, Clone)]
+#[diesel(table_name = task_sessions, check_for_backend(diesel::sqlite::Sqlite))]
+pub struct CreateTaskSession {
+    pub task_id: i32,
+    pub session_id: i32,
+}
+
+/// Estimated pomodoros of a task next to the focus sessions which actually went into it.
+/// Estimated minutes assume every pomodoro lasts the configured focus duration.
+#[derive(Serialize, TS, Debug, Clone, PartialEq)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct TaskEstimate {
+    pub task_id: i32,
+    pub estimated_pomodoros: Option<i32>,
+    pub actual_pomodoros: i32,
+    #[ts(type = "number | null")]
+    pub estimated_minutes: Option<Minutes>,
+    #[ts(type = "number")]
+    pub actual_minutes: Minutes,
+}
+
+/// How well completed tasks kept to their estimates.
+#[derive(Serialize, TS, Debug, Clone, PartialEq)]
+#[ts(export, export_to = "../src/bindings/")]
+pub struct EstimationReport {
+    /// Actual pomodoros divided by estimated ones over all tasks, above 1 when tasks
+    /// tend to take longer than expected.
+    pub ratio: Option<f64>,
+    pub on_estimate: i32,
+    pub underestimated: i32,
+    pub overestimated: i32,
+    pub tasks: Vec<TaskEstimate>,
 }
@@ -0,0 +1,51 @@
+//! Progress events shared by the timer and the IPC commands, sent once a session is logged.
+
+use tauri::{AppHandle, Manager};
+
+use crate::{
+    bmc::IntentBmc,
+    config::{ConfigManager, SettingsConfig},
+    ctx::AppContext,
+    day::DayBoundary,
+};
+
+/// Lets the client know how far along the targets of an intent and its parents are after
+/// a focus session was logged, emits `intent_target_reached` and `intent_budget_exceeded`
+/// once the session pushes the focused time over them.
+pub fn emit_intent_target_progress(
+    app_handle: &AppHandle,
+    intent_id: i32,
+    started_at: i64,
+    session_duration: i64,
+) {
+    let settings = ConfigManager::get::<SettingsConfig>().unwrap_or_default();
+    let boundary = DayBoundary::from_settings(&settings);
+    let progress = app_handle.db(|db| {
+        IntentBmc::get_session_target_progress(
+            db,
+            &boundary,
+            intent_id,
+            started_at,
+            session_duration,
+        )
+    });
+    let Ok(progress) = progress else {
+        return;
+    };
+
+    for (before, progress) in progress {
+        if progress.is_target_reached() && !before.is_target_reached() {
+            app_handle
+                .emit_all("intent_target_reached", progress.clone())
+                .unwrap();
+        }
+        if progress.is_budget_exceeded() && !before.is_budget_exceeded() {
+            app_handle
+                .emit_all("intent_budget_exceeded", progress.clone())
+                .unwrap();
+        }
+        app_handle
+            .emit_all("intent_target_progress", progress)
+            .unwrap();
+    }
+}
@@ -1,26 +1,29 @@
 use std::sync::{Arc, Mutex};
 
 use crate::{
-    bmc::ThemeBmc,
+    bmc::{TaskBmc, ThemeBmc},
     config::{ConfigManager, QueueConfig, SettingsConfig, TimerConfig},
     ctx::AppContext,
+    day::DayBoundary,
     db::Db,
+    ipc::EventPayload,
     prelude::Result,
     state::{AppState, TimerState},
-    timer::Timer,
+    timer::{Timer, TimerSnapshot},
 };
-use tauri::{App, Manager};
+use tauri::{App, AppHandle, Manager};
 use tauri_hotkey::{Hotkey, HotkeyManager, Key, Modifier};
 
 pub fn setup_hook(app: &mut App) {
     // Disables GTK's scroll bar
     std::env::set_var("GTK_OVERLAY_SCROLLING", "0");
 
-    setup_timer(app);
     setup_database(app).expect("should set up database");
+    setup_timer(app);
     setup_config().expect("should set up config");
     setup_hotkeys_manager(app).expect("should set up hotkeys");
     setup_state(app).expect("should set up state");
+    setup_task_rollover(app);
     build_main_window(app).expect("should build main window");
     build_notifications_window(app).expect("should build notifications window");
 }
@@ -28,7 +31,10 @@ pub fn setup_hook(app: &mut App) {
 fn setup_timer(app: &mut App) {
     let app_handle = app.app_handle();
 
-    let timer = Timer::init(app_handle);
+    let timer = match ConfigManager::get::<TimerSnapshot>() {
+        Ok(snapshot) => Timer::restore(app_handle, snapshot),
+        Err(_) => Timer::init(app_handle),
+    };
 
     app.manage(tokio::sync::Mutex::new(timer));
 }
@@ -57,6 +63,38 @@ fn setup_config() -> Result<()> {
     Ok(())
 }
 
+/// Rolls recurring tasks over on start up and whenever a new day starts afterwards.
+fn setup_task_rollover(app: &mut App) {
+    let app_handle = app.app_handle();
+
+    tauri::async_runtime::spawn(async move {
+        loop {
+            roll_over_recurring_tasks(&app_handle);
+
+            let settings = ConfigManager::get::<SettingsConfig>().unwrap_or_default();
+            let now = chrono::Utc::now().timestamp();
+            let next_day_start = DayBoundary::from_settings(&settings).next_day_start(now);
+            let delay = std::time::Duration::from_secs((next_day_start - now) as u64);
+            tokio::time::sleep(delay).await;
+        }
+    });
+}
+
+/// Generates the next instances of recurring tasks whose day has passed, relative to the
+/// day as configured in the settings. Emits `task_created` for every generated task.
+fn roll_over_recurring_tasks(app_handle: &AppHandle) {
+    let settings = ConfigManager::get::<SettingsConfig>().unwrap_or_default();
+    let today = DayBoundary::from_settings(&settings).today();
+    let Ok(ids) = app_handle.db(|db| TaskBmc::roll_over(db, today)) else {
+        return;
+    };
+    for id in ids {
+        app_handle
+            .emit_all("task_created", EventPayload { data: id })
+            .unwrap();
+    }
+}
+
 fn setup_hotkeys_manager(app: &mut App) -> Result<()> {
     let mut hm = HotkeyManager::new();
     let app_handle = Arc::new(app.app_handle());
@@ -0,0 +1,237 @@
+use std::time::{Duration, Instant};
+
+use crate::{config::FocusMode, prelude::Minutes};
+
+use super::{SessionType, TimerError, TimerResult};
+
+/// Monotonic clock of a session, measures the time spent playing without the pauses.
+#[derive(Debug, Clone, Default)]
+pub struct SessionClock {
+    /// Elapsed time carried over from a previous run.
+    carried: Duration,
+    /// Instant at which the session was first played.
+    started_at: Option<Instant>,
+    /// Instant at which the session was last paused.
+    paused_at: Option<Instant>,
+    /// Total time spent paused since the session was first played.
+    paused: Duration,
+}
+
+impl SessionClock {
+    /// Clock of a session which already ran for `elapsed` before the app restarted.
+    pub fn carried(elapsed: Duration) -> Self {
+        Self {
+            carried: elapsed,
+            ..Default::default()
+        }
+    }
+
+    pub fn play(&mut self, now: Instant) {
+        match self.started_at {
+            None => self.started_at = Some(now),
+            Some(_) => {
+                if let Some(paused_at) = self.paused_at.take() {
+                    self.paused += now.saturating_duration_since(paused_at);
+                }
+            }
+        }
+    }
+
+    pub fn pause(&mut self, now: Instant) {
+        if self.started_at.is_some() && self.paused_at.is_none() {
+            self.paused_at = Some(now);
+        }
+    }
+
+    /// Time spent playing up to `now`.
+    pub fn elapsed(&self, now: Instant) -> Duration {
+        let clock_elapsed = match self.started_at {
+            Some(started_at) => self
+                .paused_at
+                .unwrap_or(now)
+                .saturating_duration_since(started_at)
+                .saturating_sub(self.paused),
+            None => Duration::ZERO,
+        };
+        self.carried + clock_elapsed
+    }
+}
+
+/// Time left of a session lasting `duration` minutes.
+pub fn remaining(duration: Minutes, elapsed: Duration) -> Duration {
+    Duration::from_secs(duration.max(0) as u64 * 60).saturating_sub(elapsed)
+}
+
+/// Time until the next whole elapsed second, or until the deadline if that comes first.
+/// Keeps ticking every second once the deadline has passed, e.g. while in overtime.
+pub fn next_tick_in(elapsed: Duration, remaining: Duration) -> Duration {
+    let until_next_second = Duration::from_nanos(1_000_000_000 - elapsed.subsec_nanos() as u64);
+    match remaining {
+        remaining if remaining.is_zero() => until_next_second,
+        remaining => until_next_second.min(remaining),
+    }
+}
+
+/// Whether a completed session should keep running instead of switching over.
+pub fn can_enter_overtime(
+    session_type: &SessionType,
+    is_overtime: bool,
+    focus_mode: &FocusMode,
+) -> bool {
+    *session_type == SessionType::Focus && !is_overtime && *focus_mode != FocusMode::Fixed
+}
+
+/// Break length proportional to the focus time actually spent, never below a minute.
+pub fn flowtime_break_duration(focus_elapsed: Duration, ratio: i64) -> Minutes {
+    let focus_minutes = focus_elapsed.as_secs() as f64 / 60.0;
+    let break_minutes = focus_minutes / ratio.max(1) as f64;
+    (break_minutes.round() as Minutes).max(1)
+}
+
+/// A change to the length of the running session.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DurationChange {
+    Extend(Minutes),
+    Shorten(Minutes),
+    /// Pushes back the end of a break.
+    Snooze(Minutes),
+}
+
+impl DurationChange {
+    /// Length of a session of the given type after the change, sessions last at least a minute.
+    pub fn apply(self, session_type: &SessionType, duration: Minutes) -> TimerResult<Minutes> {
+        let (minutes, delta) = match self {
+            DurationChange::Extend(minutes) => (minutes, minutes),
+            DurationChange::Shorten(minutes) => (minutes, -minutes),
+            DurationChange::Snooze(_) if *session_type == SessionType::Focus => {
+                return Err(TimerError::NotOnBreak);
+            }
+            DurationChange::Snooze(minutes) => (minutes, minutes),
+        };
+        if minutes <= 0 || duration + delta < 1 {
+            return Err(TimerError::InvalidDuration);
+        }
+        Ok(duration + delta)
+    }
+}
+
+#[cfg(test)]
+mod clock_tests {
+    use super::*;
+
+    const SECOND: Duration = Duration::from_secs(1);
+
+    #[test]
+    fn test_elapsed_without_pauses() {
+        let start = Instant::now();
+        let mut clock = SessionClock::default();
+        assert_eq!(clock.elapsed(start), Duration::ZERO);
+
+        clock.play(start);
+        assert_eq!(clock.elapsed(start + 90 * SECOND), 90 * SECOND);
+    }
+
+    #[test]
+    fn test_elapsed_with_pauses_and_carried_time() {
+        let start = Instant::now();
+        let mut clock = SessionClock::carried(60 * SECOND);
+        assert_eq!(clock.elapsed(start), 60 * SECOND);
+
+        clock.play(start);
+        clock.pause(start + 10 * SECOND);
+        // Time doesn't pass while paused, pausing twice keeps the first pause.
+        clock.pause(start + 20 * SECOND);
+        assert_eq!(clock.elapsed(start + 30 * SECOND), 70 * SECOND);
+
+        clock.play(start + 40 * SECOND);
+        assert_eq!(clock.elapsed(start + 50 * SECOND), 80 * SECOND);
+        clock.pause(start + 55 * SECOND);
+        clock.play(start + 65 * SECOND);
+        assert_eq!(clock.elapsed(start + 70 * SECOND), 90 * SECOND);
+    }
+
+    #[test]
+    fn test_remaining() {
+        assert_eq!(remaining(25, 60 * SECOND), 24 * 60 * SECOND);
+        assert_eq!(remaining(1, 90 * SECOND), Duration::ZERO);
+        assert_eq!(remaining(-5, Duration::ZERO), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_next_tick_in() {
+        let half = Duration::from_millis(500);
+        // Lines up with the next whole elapsed second.
+        assert_eq!(next_tick_in(10 * SECOND + half, 60 * SECOND), half);
+        assert_eq!(next_tick_in(10 * SECOND, 60 * SECOND), SECOND);
+        // Never sleeps past the deadline.
+        assert_eq!(
+            next_tick_in(10 * SECOND, Duration::from_millis(200)),
+            Duration::from_millis(200)
+        );
+        // Keeps ticking once the deadline has passed.
+        assert_eq!(next_tick_in(10 * SECOND + half, Duration::ZERO), half);
+    }
+
+    #[test]
+    fn test_can_enter_overtime() {
+        let focus = SessionType::Focus;
+        assert!(can_enter_overtime(&focus, false, &FocusMode::Overtime));
+        assert!(can_enter_overtime(&focus, false, &FocusMode::Flowtime));
+        assert!(!can_enter_overtime(&focus, false, &FocusMode::Fixed));
+        // Overtime is only entered once.
+        assert!(!can_enter_overtime(&focus, true, &FocusMode::Overtime));
+        assert!(!can_enter_overtime(
+            &SessionType::Break,
+            false,
+            &FocusMode::Overtime
+        ));
+    }
+
+    #[test]
+    fn test_flowtime_break_duration() {
+        assert_eq!(flowtime_break_duration(50 * 60 * SECOND, 5), 10);
+        assert_eq!(flowtime_break_duration(52 * 60 * SECOND, 5), 10);
+        assert_eq!(flowtime_break_duration(53 * 60 * SECOND, 5), 11);
+        // At least a minute, and a broken ratio doesn't divide by zero.
+        assert_eq!(flowtime_break_duration(60 * SECOND, 5), 1);
+        assert_eq!(flowtime_break_duration(10 * 60 * SECOND, 0), 10);
+    }
+
+    #[test]
+    fn test_duration_change() {
+        let focus = SessionType::Focus;
+        let on_break = SessionType::Break;
+        assert_eq!(DurationChange::Extend(5).apply(&focus, 25).unwrap(), 30);
+        assert_eq!(DurationChange::Shorten(5).apply(&focus, 25).unwrap(), 20);
+        assert_eq!(DurationChange::Shorten(24).apply(&focus, 25).unwrap(), 1);
+        assert_eq!(DurationChange::Snooze(5).apply(&on_break, 5).unwrap(), 10);
+        assert_eq!(
+            DurationChange::Snooze(5)
+                .apply(&SessionType::LongBreak, 10)
+                .unwrap(),
+            15
+        );
+
+        for change in [
+            DurationChange::Extend(0),
+            DurationChange::Extend(-5),
+            DurationChange::Shorten(0),
+            DurationChange::Shorten(-5),
+            DurationChange::Shorten(25),
+            DurationChange::Snooze(0),
+        ] {
+            let session_type = match change {
+                DurationChange::Snooze(_) => &on_break,
+                _ => &focus,
+            };
+            assert!(matches!(
+                change.apply(session_type, 25),
+                Err(TimerError::InvalidDuration)
+            ));
+        }
+        assert!(matches!(
+            DurationChange::Snooze(5).apply(&focus, 25),
+            Err(TimerError::NotOnBreak)
+        ));
+    }
+}
@@ -9,4 +9,10 @@ pub enum TimerError {
     EmptyQueue,
     #[error("QueueIndexOutOfRange")]
     QueueIndexOutOfRange,
+    #[error("NoRestoredSession")]
+    NoRestoredSession,
+    #[error("InvalidDuration")]
+    InvalidDuration,
+    #[error("NotOnBreak")]
+    NotOnBreak,
 }
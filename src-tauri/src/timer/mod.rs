@@ -1,26 +1,31 @@
 use crate::{
     config::{ConfigManager, TimerConfig},
+    ctx::AppContext,
     models::Intent,
+    prelude::{Minutes, Result},
 };
-use std::sync::atomic::AtomicU32;
+use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
 use tauri::AppHandle;
-use tokio::time::sleep;
+use tokio::time::{sleep_until, Instant};
 use tokio::{
     sync::{Mutex, OwnedMutexGuard},
     task::JoinHandle,
 };
 
+mod clock;
 mod error;
 mod queue;
 mod session;
+mod snapshot;
 
+pub use clock::DurationChange;
 pub use error::*;
 pub use queue::*;
 pub use session::*;
+pub use snapshot::*;
 
-type TimerResult<T> = Result<T, TimerError>;
+type TimerResult<T> = std::result::Result<T, TimerError>;
 
 pub struct Timer {
     session: Option<Arc<Mutex<TimerSession>>>,
@@ -39,9 +44,34 @@ impl Timer {
             session_handle: None,
         }
     }
+    /// Rebuilds the timer from a snapshot written by a previous run, a session or queued
+    /// sessions whose intent has been deleted since are discarded. Expects the database to be
+    /// set up.
+    pub fn restore(app_handle: AppHandle, snapshot: TimerSnapshot) -> Self {
+        let session = snapshot
+            .session
+            .and_then(|session| app_handle.db(|db| session.validate(db)))
+            .map(|session| TimerSession::restore(app_handle.clone(), session))
+            .map(|session| Arc::new(Mutex::new(session)));
+        let queue = app_handle.db(|db| snapshot.queue.validate(db));
+        let queue = TimerQueue::restore(app_handle, queue);
+
+        Self {
+            session,
+            iteration: Arc::new(AtomicU32::new(snapshot.iteration)),
+            queue: Arc::new(Mutex::new(queue)),
+            session_handle: None,
+        }
+    }
     fn get_config() -> TimerConfig {
         ConfigManager::get::<TimerConfig>().unwrap()
     }
+    /// Writes the timer's state to disk so that it survives an app restart.
+    fn save_snapshot(session: Option<&TimerSession>, iteration: &AtomicU32, queue: &TimerQueue) {
+        let snapshot = TimerSnapshot::capture(session, iteration, queue);
+        // A failed write shouldn't interrupt the timer
+        ConfigManager::save(&snapshot).ok();
+    }
 }
 
 // Methods for interacting with timer
@@ -55,6 +85,8 @@ impl Timer {
 
         session.play();
         session.emit();
+        Self::save_snapshot(Some(&*session), &self.iteration, &*self.queue.lock().await);
+        drop(session);
 
         let session = self.get_session_clone().await?;
         let iteration = self.iteration.clone();
@@ -66,26 +98,38 @@ impl Timer {
         };
         let handle = tokio::spawn(async move {
             loop {
-                sleep(Duration::from_secs(1)).await;
+                // Sleep until the next tick derived from the session's clock
+                let next_tick_at = session.lock().await.next_tick_at();
+                sleep_until(Instant::from_std(next_tick_at)).await;
 
                 let mut session = session.clone().lock_owned().await;
                 // Kills thread if session has been paused
                 if !session.is_playing {
                     break;
                 }
-                // Increments elapsed time
-                session.time_elapsed += 1;
+                // Refreshes elapsed time from the clock
                 session.emit();
-                // If there is time remaining, skip over to the next loop iteration
-                if session.time_elapsed < session.duration * 60 {
+                // Let focus run into overtime if enabled
+                if session.is_complete() && session.can_enter_overtime() {
+                    session.enter_overtime();
+                    session.emit();
+                }
+                // If there is time remaining, or the session runs overtime,
+                // skip over to the next loop iteration
+                if !session.is_complete() || session.is_overtime {
+                    // Keep the snapshot at most a minute behind
+                    if session.time_elapsed % 60 == 0 {
+                        Self::save_snapshot(Some(&*session), &iteration, &*queue.lock().await);
+                    }
                     continue;
                 };
 
                 let mut queue = queue.lock().await;
-                // Switch session
-                let auto_start_next = session.next_session(iteration.clone(), &mut queue).unwrap();
+                // Switch session, a session which failed to save stops the timer
+                let auto_start_next = session.next_session(iteration.clone(), &mut queue);
+                Self::save_snapshot(Some(&*session), &iteration, &queue);
                 // Determine whether to autostart the next one
-                if auto_start_next {
+                if let Ok(true) = auto_start_next {
                     session.play();
                     continue;
                 };
@@ -100,25 +144,75 @@ impl Timer {
         let mut session = self.get_session_guard().await?;
         session.stop();
         session.emit();
+        Self::save_snapshot(Some(&*session), &self.iteration, &*self.queue.lock().await);
         Ok(())
     }
-    pub async fn restart(&mut self) -> TimerResult<()> {
+    pub async fn restart(&mut self) -> Result<()> {
         let mut session = self.get_session_guard().await?;
         session.stop();
-        session.save();
+        let saved = session.save();
         session.restart();
         session.emit();
-        Ok(())
+        Self::save_snapshot(Some(&*session), &self.iteration, &*self.queue.lock().await);
+        saved
     }
-    pub async fn skip(&mut self) -> TimerResult<()> {
+    pub async fn skip(&mut self) -> Result<()> {
         let mut session = self.get_session_guard().await?;
         let iteration = self.iteration.clone();
         let queue_guard = self.queue.clone();
         let mut queue = queue_guard.lock().await;
 
         session.stop();
-        session.next_session(iteration, &mut *queue)?;
-        Ok(())
+        let switched = session.next_session(iteration, &mut *queue);
+        Self::save_snapshot(Some(&*session), &self.iteration, &queue);
+        switched.map(|_| ())
+    }
+    pub async fn extend(&mut self, minutes: Minutes) -> TimerResult<()> {
+        self.adjust_duration(DurationChange::Extend(minutes)).await
+    }
+    pub async fn shorten(&mut self, minutes: Minutes) -> TimerResult<()> {
+        self.adjust_duration(DurationChange::Shorten(minutes)).await
+    }
+    /// Pushes back the end of the current break.
+    pub async fn snooze_break(&mut self, minutes: Minutes) -> TimerResult<()> {
+        self.adjust_duration(DurationChange::Snooze(minutes)).await
+    }
+    /// Drops the session and the queue and starts counting iterations anew, e.g. once a
+    /// restored backup replaced the intents they point at.
+    pub async fn reset(&mut self) {
+        if let Some(handle) = self.session_handle.take() {
+            handle.abort();
+        }
+        self.session = None;
+        self.iteration.store(0, Ordering::SeqCst);
+        let mut queue = self.queue.lock().await;
+        queue.clear();
+        Self::save_snapshot(None, &self.iteration, &queue);
+    }
+    /// Settles a session restored from a previous run.
+    pub async fn resolve_restored_session(&mut self, action: RestoredSessionAction) -> Result<()> {
+        let mut session = self.get_session_guard().await?;
+        if !session.is_restored {
+            return Err(TimerError::NoRestoredSession.into());
+        }
+
+        let mut saved = Ok(());
+        match action {
+            RestoredSessionAction::Resume => {
+                drop(session);
+                return Ok(self.play().await?);
+            }
+            RestoredSessionAction::Discard => {
+                session.restart();
+            }
+            RestoredSessionAction::Save => {
+                saved = session.save();
+                session.restart();
+            }
+        }
+        session.emit();
+        Self::save_snapshot(Some(&*session), &self.iteration, &*self.queue.lock().await);
+        saved
     }
 }
 
@@ -135,19 +229,35 @@ impl Timer {
         let session_guard = session.lock_owned().await;
         Ok(session_guard)
     }
+    async fn adjust_duration(&mut self, change: DurationChange) -> TimerResult<()> {
+        let mut session = self.get_session_guard().await?;
+        session.adjust_duration(change)?;
+        session.emit();
+        Self::save_snapshot(Some(&*session), &self.iteration, &*self.queue.lock().await);
+        Ok(())
+    }
+    async fn persist(&self) {
+        let session = match &self.session {
+            Some(session) => Some(session.lock().await),
+            None => None,
+        };
+        let queue = self.queue.lock().await;
+        Self::save_snapshot(session.as_deref(), &self.iteration, &queue);
+    }
 }
 
 // Session related methods
 impl Timer {
     pub async fn get_session(&self) -> TimerResult<TimerSession> {
-        let session = self.get_session_guard().await?.clone();
+        let mut session = self.get_session_guard().await?.clone();
+        session.sync();
         Ok(session)
     }
     pub async fn set_session_intent(&mut self, app_handle: AppHandle, intent: Intent) {
         match &mut self.session {
             Some(session) => {
                 let mut session = session.lock().await;
-                session.intent = intent;
+                session.set_intent(intent);
                 session.emit();
             }
             None => {
@@ -156,12 +266,22 @@ impl Timer {
                     _type: SessionType::Focus,
                     duration: config.focus_duration,
                     intent,
+                    task_id: None,
                 };
-                let session = TimerSession::new(app_handle, data);
+                let mut session = TimerSession::new(app_handle, data);
                 session.emit();
                 self.session = Some(Arc::new(Mutex::new(session)));
             }
         }
+        self.persist().await;
+    }
+    /// Sets the task the current session is spent on, `None` stops attributing sessions.
+    pub async fn set_session_task(&mut self, task_id: Option<i32>) -> TimerResult<()> {
+        let mut session = self.get_session_guard().await?;
+        session.task_id = task_id;
+        session.emit();
+        Self::save_snapshot(Some(&*session), &self.iteration, &*self.queue.lock().await);
+        Ok(())
     }
 }
 
@@ -171,35 +291,43 @@ impl Timer {
         self.queue.lock().await.get()
     }
     pub async fn add_to_queue(&mut self, session: QueueSession) {
-        let mut queue = self.queue.lock().await;
-        queue.add(session);
+        self.queue.lock().await.add(session);
+        self.persist().await;
     }
     pub async fn remove_from_queue(&mut self, idx: usize) -> TimerResult<()> {
-        let mut queue = self.queue.lock().await;
-        queue.remove(idx)
+        self.queue.lock().await.remove(idx)?;
+        self.persist().await;
+        Ok(())
     }
     pub async fn reorder_queue(&mut self, idx: usize, target_idx: usize) -> TimerResult<()> {
-        let mut queue = self.queue.lock().await;
-        queue.reorder(idx, target_idx)
+        self.queue.lock().await.reorder(idx, target_idx)?;
+        self.persist().await;
+        Ok(())
     }
     pub async fn clear_queue(&mut self) {
-        let mut queue = self.queue.lock().await;
-        queue.clear()
+        self.queue.lock().await.clear();
+        self.persist().await;
     }
     pub async fn increment_queue_session_iterations(&mut self, idx: usize) -> TimerResult<()> {
-        let mut queue = self.queue.lock().await;
-        queue.increment_session_iterations(idx)
+        self.queue.lock().await.increment_session_iterations(idx)?;
+        self.persist().await;
+        Ok(())
     }
     pub async fn decrement_queue_session_iterations(&mut self, idx: usize) -> TimerResult<()> {
-        let mut queue = self.queue.lock().await;
-        queue.decrement_session_iterations(idx)
+        self.queue.lock().await.decrement_session_iterations(idx)?;
+        self.persist().await;
+        Ok(())
     }
     pub async fn update_queue_session_duration(
         &mut self,
         idx: usize,
         duration: i64,
     ) -> TimerResult<()> {
-        let mut queue = self.queue.lock().await;
-        queue.update_session_duration(idx, duration)
+        self.queue
+            .lock()
+            .await
+            .update_session_duration(idx, duration)?;
+        self.persist().await;
+        Ok(())
     }
 }
@@ -1,9 +1,13 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 use tauri::{AppHandle, Manager};
 use ts_rs::TS;
 
+use diesel::SqliteConnection;
+
 use super::{SessionType, TimerError, TimerResult, TimerSession};
-use crate::models::Intent;
+use crate::{bmc::IntentBmc, models::Intent};
 
 #[derive(TS, Serialize, Deserialize, Debug, Clone)]
 #[ts(export, export_to = "../src/bindings/")]
@@ -26,9 +30,25 @@ impl QueueSession {
     pub fn update_duration(&mut self, duration: i64) {
         self.duration = duration;
     }
+    /// Refreshes the intent from the database, returns `None` when the intent has been
+    /// deleted in the meantime.
+    pub fn validate(mut self, conn: &mut SqliteConnection) -> Option<Self> {
+        self.intent = IntentBmc::get(conn, self.intent.id).ok()?;
+        Some(self)
+    }
+    /// Points the session at the intent's new id, returns `false` if the intent is not in `ids`.
+    pub fn remap_intent(&mut self, ids: &HashMap<i32, i32>) -> bool {
+        match ids.get(&self.intent.id) {
+            Some(&id) => {
+                self.intent.id = id;
+                true
+            }
+            None => false,
+        }
+    }
 }
 
-#[derive(TS, Serialize, Debug, Clone)]
+#[derive(TS, Serialize, Deserialize, Debug, Clone)]
 #[ts(export, export_to = "../src/bindings/")]
 pub struct Queue {
     data: Vec<QueueSession>,
@@ -38,6 +58,15 @@ impl Queue {
     pub fn new() -> Self {
         Self { data: vec![] }
     }
+    /// Drops the sessions whose intents have been deleted, see [`QueueSession::validate`].
+    pub fn validate(self, conn: &mut SqliteConnection) -> Self {
+        let data = self
+            .data
+            .into_iter()
+            .filter_map(|session| session.validate(conn))
+            .collect();
+        Self { data }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -53,6 +82,9 @@ impl TimerQueue {
             app_handle,
         }
     }
+    pub fn restore(app_handle: AppHandle, queue: Queue) -> Self {
+        Self { queue, app_handle }
+    }
 }
 
 impl TimerQueue {
@@ -76,10 +108,9 @@ impl TimerQueue {
         session_queue.iterations -= 1;
 
         session._type = SessionType::Focus;
-        session.duration = session_queue.duration;
-        session.intent = session_queue.intent.clone();
-        session.time_elapsed = 0;
-        session.started_at = None;
+        session.plan(session_queue.duration);
+        session.set_intent(session_queue.intent.clone());
+        session.restart();
 
         if session_queue.iterations <= 0 {
             self.remove(0)?;
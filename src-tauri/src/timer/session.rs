@@ -1,24 +1,44 @@
-use std::sync::{
-    atomic::{AtomicU32, Ordering},
-    Arc,
+use std::{
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
 };
 
 use serde::{Deserialize, Serialize};
 use tauri::{AppHandle, Manager};
 use ts_rs::TS;
 
+use diesel::{
+    deserialize::{self, FromSql, FromSqlRow},
+    expression::AsExpression,
+    serialize::{self, IsNull, Output, ToSql},
+    sql_types::Text,
+    sqlite::{Sqlite, SqliteValue},
+    Connection,
+};
+
 use crate::{
-    bmc::SessionBmc,
-    config::TimerConfig,
+    bmc::{SessionBmc, SessionPauseBmc, StatsBmc, TaskBmc},
+    config::{ConfigManager, FocusMode, SettingsConfig, TimerConfig},
     ctx::AppContext,
-    models::{CreateSession, Intent},
+    day::DayBoundary,
+    export,
+    models::{CreateSession, CreateSessionPause, GoalProgress, Intent},
+    prelude::{Error, Minutes, Result},
+    progress,
 };
 
-use super::{Timer, TimerQueue, TimerResult};
+use super::{
+    clock::{self, DurationChange, SessionClock},
+    SessionSnapshot, Timer, TimerQueue, TimerResult,
+};
 
 type AutoStartNext = bool;
 
-#[derive(TS, Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[derive(TS, Deserialize, Serialize, AsExpression, FromSqlRow, Debug, Clone, PartialEq)]
+#[diesel(sql_type = Text)]
 #[ts(export, export_to = "../src/bindings/")]
 pub enum SessionType {
     Focus,
@@ -26,11 +46,49 @@ pub enum SessionType {
     LongBreak,
 }
 
+impl Default for SessionType {
+    fn default() -> Self {
+        SessionType::Focus
+    }
+}
+
+impl ToSql<Text, Sqlite> for SessionType {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Sqlite>) -> serialize::Result {
+        let value = match self {
+            SessionType::Focus => "Focus",
+            SessionType::Break => "Break",
+            SessionType::LongBreak => "LongBreak",
+        };
+        out.set_value(value);
+        Ok(IsNull::No)
+    }
+}
+
+impl FromSql<Text, Sqlite> for SessionType {
+    fn from_sql(value: SqliteValue<'_, '_, '_>) -> deserialize::Result<Self> {
+        let value = <String as FromSql<Text, Sqlite>>::from_sql(value)?;
+        match value.as_str() {
+            "Focus" => Ok(SessionType::Focus),
+            "Break" => Ok(SessionType::Break),
+            "LongBreak" => Ok(SessionType::LongBreak),
+            _ => Err(format!("Unrecognized session type: {}", value).into()),
+        }
+    }
+}
+
 #[derive(Deserialize, Debug, Clone)]
 pub struct CreateTimerSession {
     pub _type: SessionType,
     pub duration: i64,
     pub intent: Intent,
+    pub task_id: Option<i32>,
+}
+
+/// A pause within a session, kept in memory until the session is saved.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TimerPause {
+    pub started_at: i64,
+    pub finished_at: i64,
 }
 
 #[derive(TS, Serialize, Debug, Clone)]
@@ -40,11 +98,32 @@ pub struct TimerSession {
     pub is_playing: bool,
     #[ts(type = "number")]
     pub duration: i64,
+    /// Length the session was planned with, before any extending or shortening.
+    #[ts(type = "number")]
+    pub planned_duration: i64,
     #[ts(type = "number")]
     pub time_elapsed: i64,
     #[ts(type = "number")]
+    pub time_remaining_ms: i64,
+    #[ts(type = "number")]
     pub started_at: Option<i64>,
     pub intent: Intent,
+    /// Task of the intent being worked on, focus sessions are attributed to it once saved.
+    pub task_id: Option<i32>,
+    /// Whether a focus session has run past its planned length.
+    pub is_overtime: bool,
+    /// Whether the session was restored from a previous run and awaits the user's decision.
+    pub is_restored: bool,
+
+    /// Finished pauses since the session was first played.
+    #[serde(skip_serializing)]
+    pauses: Vec<TimerPause>,
+    /// Timestamp of the ongoing pause, if any.
+    #[serde(skip_serializing)]
+    paused_at: Option<i64>,
+    /// Monotonic clock the elapsed time is derived from.
+    #[serde(skip_serializing)]
+    clock: SessionClock,
 
     #[serde(skip_serializing)]
     pub app_handle: AppHandle,
@@ -55,95 +134,295 @@ impl TimerSession {
         Self {
             _type: data._type,
             duration: data.duration,
+            planned_duration: data.duration,
             intent: data.intent,
+            task_id: data.task_id,
             is_playing: false,
             time_elapsed: 0,
+            time_remaining_ms: data.duration * 60 * 1000,
             started_at: None,
+            is_overtime: false,
+            is_restored: false,
+            pauses: vec![],
+            paused_at: None,
+            clock: SessionClock::default(),
             app_handle,
         }
     }
+
+    /// Rebuilds a paused session from its snapshot.
+    pub fn restore(app_handle: AppHandle, snapshot: SessionSnapshot) -> Self {
+        let data = CreateTimerSession {
+            _type: snapshot._type,
+            duration: snapshot.duration,
+            intent: snapshot.intent,
+            task_id: snapshot.task_id,
+        };
+        let mut session = Self::new(app_handle, data);
+        if let Some(planned_duration) = snapshot.planned_duration {
+            session.planned_duration = planned_duration;
+        }
+        session.started_at = snapshot.started_at;
+        session.is_restored = snapshot.started_at.is_some();
+        // A session the app closed on while playing is paused from its last tick, so the
+        // time the app was closed is recorded as a pause once the session is resumed
+        let last_active_at = snapshot
+            .last_active_at()
+            .map(|timestamp| timestamp.min(chrono::Utc::now().timestamp()));
+        session.paused_at = snapshot.paused_at.or(last_active_at);
+        session.pauses = snapshot.pauses;
+        session.clock = SessionClock::carried(Duration::from_millis(snapshot.time_elapsed_ms));
+        session.sync();
+        session
+    }
 }
 
 impl TimerSession {
     pub fn play(&mut self) {
+        let timestamp = chrono::Utc::now().timestamp();
         if let None = self.started_at {
-            let timestamp = chrono::Utc::now().timestamp();
             self.started_at = Some(timestamp);
         }
+        if let Some(paused_at) = self.paused_at.take() {
+            self.pauses.push(TimerPause {
+                started_at: paused_at,
+                finished_at: timestamp,
+            });
+        }
+        self.clock.play(Instant::now());
+        self.is_restored = false;
         self.is_playing = true;
     }
 
     pub fn stop(&mut self) {
+        self.clock.pause(Instant::now());
+        if self.started_at.is_some() && self.paused_at.is_none() {
+            self.paused_at = Some(chrono::Utc::now().timestamp());
+        }
         self.is_playing = false;
     }
 
     pub fn restart(&mut self) {
         self.time_elapsed = 0;
+        self.time_remaining_ms = self.duration * 60 * 1000;
         self.started_at = None;
+        self.is_overtime = false;
+        self.is_restored = false;
+        self.pauses.clear();
+        self.paused_at = None;
+        self.clock = SessionClock::default();
+    }
+
+    /// Switches to another intent, the active task is dropped unless the intent stays the same.
+    pub fn set_intent(&mut self, intent: Intent) {
+        if self.intent.id != intent.id {
+            self.task_id = None;
+        }
+        self.intent = intent;
+    }
+
+    /// Gives the session a new planned length.
+    pub fn plan(&mut self, duration: Minutes) {
+        self.duration = duration;
+        self.planned_duration = duration;
+    }
+
+    /// Changes the length of the session, a session pushed past its elapsed time leaves
+    /// overtime.
+    pub fn adjust_duration(&mut self, change: DurationChange) -> TimerResult<()> {
+        self.duration = change.apply(&self._type, self.duration)?;
+        self.is_overtime = self.is_overtime && self.is_complete();
+        Ok(())
+    }
+
+    /// Time spent playing, derived from the monotonic clock so it never drifts.
+    pub fn elapsed(&self) -> Duration {
+        self.clock.elapsed(Instant::now())
+    }
+
+    /// Time left until the session reaches its deadline.
+    pub fn remaining(&self) -> Duration {
+        clock::remaining(self.duration, self.elapsed())
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.remaining().is_zero()
+    }
+
+    /// Instant of the next whole elapsed second, so ticks stay lined up with the deadline.
+    pub fn next_tick_at(&self) -> Instant {
+        let now = Instant::now();
+        let elapsed = self.clock.elapsed(now);
+        now + clock::next_tick_in(elapsed, clock::remaining(self.duration, elapsed))
+    }
+
+    /// Whether a completed session should keep running instead of switching over.
+    pub fn can_enter_overtime(&self) -> bool {
+        clock::can_enter_overtime(
+            &self._type,
+            self.is_overtime,
+            &Timer::get_config().focus_mode,
+        )
     }
 
-    pub fn emit(&self) {
+    pub fn enter_overtime(&mut self) {
+        self.is_overtime = true;
+        self.app_handle
+            .emit_all("focus_overtime_started", ())
+            .unwrap();
+    }
+
+    /// Refreshes the serialized time values from the monotonic clock.
+    pub fn sync(&mut self) {
+        self.time_elapsed = self.elapsed().as_secs() as i64;
+        self.time_remaining_ms = self.remaining().as_millis() as i64;
+    }
+
+    pub fn emit(&mut self) {
+        self.sync();
         self.app_handle
             .emit_all("timer_session_updated", self.clone())
             .unwrap();
     }
 
-    pub fn save(&self) {
-        let can_be_saved = self._type == SessionType::Focus
-            && self.started_at.is_some()
-            && self.time_elapsed >= 60;
-
-        if can_be_saved {
-            let data = CreateSession {
-                duration: self.time_elapsed as i32,
-                started_at: self.started_at.unwrap(),
-                intent_id: self.intent.id,
-            };
-            let session_id = self
-                .app_handle
-                .db(|mut db| SessionBmc::create(&mut db, &data))
-                .unwrap();
-            self.app_handle
-                .emit_all("session_created", session_id)
-                .unwrap();
+    /// Stores the session in the history once it ran for at least a minute. Callers finish
+    /// switching sessions before propagating an error, as the session may already be stored.
+    pub fn save(&self) -> Result<()> {
+        let time_elapsed = self.elapsed().as_secs() as i64;
+        let Some(started_at) = self.started_at.filter(|_| time_elapsed >= 60) else {
+            return Ok(());
+        };
 
-            let config = Timer::get_config();
-            // Open session summary window
-            if config.session_summary {
-                tauri::WindowBuilder::new(
-                    &self.app_handle,
-                    "summary",
-                    tauri::WindowUrl::App(format!("/summary?sessionId={}", session_id).into()),
-                )
-                .title("Session Summary")
-                .inner_size(320f64, 336f64)
-                .max_inner_size(320f64, 336f64)
-                .min_inner_size(320f64, 336f64)
-                .fullscreen(false)
-                .resizable(false)
-                .decorations(false)
-                .always_on_top(true)
-                .center()
-                .transparent(true)
-                .build()
-                .unwrap();
-            };
+        let data = CreateSession {
+            duration: time_elapsed as i32,
+            started_at,
+            intent_id: self.intent.id,
+            session_type: self._type.clone(),
+            planned_duration: Some((self.planned_duration * 60) as i32),
+        };
+        let config = ConfigManager::get::<TimerConfig>()?;
+        // Progress is compared before and after saving, as a session crossing the day
+        // boundary only adds part of its time to today
+        let goal_progress = match self._type {
+            SessionType::Focus => self.get_goal_progress(&config).ok(),
+            _ => None,
         };
+        // An ongoing pause marks the end of the session, not an interruption
+        let finished_at = self
+            .paused_at
+            .unwrap_or_else(|| chrono::Utc::now().timestamp());
+        let session_id = self.app_handle.db(|db| {
+            db.transaction::<_, Error, _>(|db| {
+                let session_id = SessionBmc::create_finished(db, &data, finished_at)?;
+                for pause in &self.pauses {
+                    let data = CreateSessionPause {
+                        session_id,
+                        started_at: pause.started_at,
+                        finished_at: pause.finished_at,
+                    };
+                    SessionPauseBmc::create(db, &data)?;
+                }
+                // The task may have been deleted while the session was running
+                if let (SessionType::Focus, Some(task_id)) = (&self._type, self.task_id) {
+                    if TaskBmc::get(db, task_id).is_ok() {
+                        TaskBmc::link_session(db, task_id, session_id)?;
+                    }
+                }
+                Ok(session_id)
+            })
+        })?;
+        self.app_handle.emit_all("session_created", session_id)?;
+        export::refresh_ics_feed(&self.app_handle);
+
+        if self._type == SessionType::Focus {
+            if let Some(goal_progress) = goal_progress {
+                self.emit_goal_progress(&config, &goal_progress);
+            }
+            progress::emit_intent_target_progress(
+                &self.app_handle,
+                self.intent.id,
+                started_at,
+                time_elapsed,
+            );
+        }
+
+        // Open session summary window
+        if config.session_summary && self._type == SessionType::Focus {
+            self.open_summary_window(session_id);
+        }
+        Ok(())
+    }
+
+    /// Shows the summary of the saved session, a summary window still open from an earlier
+    /// session is reused. The session is stored by now, so failing to show the window
+    /// doesn't fail the save.
+    fn open_summary_window(&self, session_id: i32) {
+        let url = format!("/summary?sessionId={}", session_id);
+        if let Some(window) = self.app_handle.get_window("summary") {
+            window
+                .eval(&format!("window.location.replace('{}')", url))
+                .ok();
+            window.set_focus().ok();
+            return;
+        }
+        tauri::WindowBuilder::new(
+            &self.app_handle,
+            "summary",
+            tauri::WindowUrl::App(url.into()),
+        )
+        .title("Session Summary")
+        .inner_size(320f64, 368f64)
+        .max_inner_size(320f64, 368f64)
+        .min_inner_size(320f64, 368f64)
+        .fullscreen(false)
+        .resizable(false)
+        .decorations(false)
+        .always_on_top(true)
+        .center()
+        .transparent(true)
+        .build()
+        .ok();
+    }
+
+    fn get_goal_progress(&self, config: &TimerConfig) -> Result<GoalProgress> {
+        let settings = ConfigManager::get::<SettingsConfig>().unwrap_or_default();
+        let boundary = DayBoundary::from_settings(&settings);
+        self.app_handle.db(|db| {
+            StatsBmc::get_goal_progress(db, &boundary, config.daily_goal, config.weekly_goal)
+        })
+    }
+
+    /// Lets the client know how far along the focus goals are after saving a session,
+    /// emits `daily_goal_reached` once the session pushes the daily total over the goal.
+    fn emit_goal_progress(&self, config: &TimerConfig, previous: &GoalProgress) {
+        let Ok(progress) = self.get_goal_progress(config) else {
+            return;
+        };
+
+        if progress.is_daily_goal_reached() && !previous.is_daily_goal_reached() {
+            self.app_handle
+                .emit_all("daily_goal_reached", progress.clone())
+                .unwrap();
+        }
+        self.app_handle
+            .emit_all("daily_goal_progress", progress)
+            .unwrap();
     }
 
     pub fn next_session(
         &mut self,
         iteration: Arc<AtomicU32>,
         queue: &mut TimerQueue,
-    ) -> TimerResult<AutoStartNext> {
+    ) -> Result<AutoStartNext> {
         let config = Timer::get_config();
+        let saved;
         let auto_start_next = match self._type {
             SessionType::Focus => {
                 self.app_handle
                     .emit_all("focus_session_completed", ())
                     .unwrap();
                 // Try to save session before switching to a break
-                self.save();
+                saved = self.save();
 
                 let iter_val = iteration.load(Ordering::SeqCst) as i64;
                 // Switch over to a long break
@@ -158,6 +437,7 @@ impl TimerSession {
             }
             SessionType::Break => {
                 self.app_handle.emit_all("break_completed", ()).unwrap();
+                saved = self.save();
 
                 if queue.is_empty() {
                     self.set_focus_session(&config);
@@ -170,6 +450,7 @@ impl TimerSession {
                 self.app_handle
                     .emit_all("long_break_completed", ())
                     .unwrap();
+                saved = self.save();
 
                 if queue.is_empty() {
                     self.set_focus_session(&config);
@@ -179,31 +460,48 @@ impl TimerSession {
                 config.auto_start_focus
             }
         };
-        if !auto_start_next {
+        // Don't carry on unattended when the finished session couldn't be stored
+        if !auto_start_next || saved.is_err() {
             self.stop();
         };
         self.emit();
+        saved?;
         Ok(auto_start_next)
     }
 
+    /// Finished pauses along with the ongoing one, if any.
+    pub fn pauses(&self) -> (&[TimerPause], Option<i64>) {
+        (&self.pauses, self.paused_at)
+    }
+
     fn set_focus_session(&mut self, config: &TimerConfig) {
         self._type = SessionType::Focus;
-        self.duration = config.focus_duration;
-        self.time_elapsed = 0;
-        self.started_at = None;
+        self.plan(config.focus_duration);
+        self.restart();
     }
 
     fn set_break_session(&mut self, config: &TimerConfig) {
+        let duration = match config.focus_mode {
+            FocusMode::Flowtime => {
+                clock::flowtime_break_duration(self.elapsed(), config.flowtime_break_ratio)
+            }
+            _ => config.break_duration,
+        };
         self._type = SessionType::Break;
-        self.duration = config.break_duration;
-        self.time_elapsed = 0;
-        self.started_at = None;
+        self.plan(duration);
+        self.restart();
     }
 
     fn set_long_break_session(&mut self, config: &TimerConfig) {
+        let duration = match config.focus_mode {
+            FocusMode::Flowtime => {
+                clock::flowtime_break_duration(self.elapsed(), config.flowtime_break_ratio)
+                    .max(config.long_break_duration)
+            }
+            _ => config.long_break_duration,
+        };
         self._type = SessionType::LongBreak;
-        self.duration = config.long_break_duration;
-        self.time_elapsed = 0;
-        self.started_at = None;
+        self.plan(duration);
+        self.restart();
     }
 }
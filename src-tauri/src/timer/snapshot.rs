@@ -0,0 +1,250 @@
+use std::{
+    path::PathBuf,
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use diesel::SqliteConnection;
+
+use crate::{
+    bmc::{IntentBmc, TaskBmc},
+    config::Config,
+    models::Intent,
+};
+
+use super::{Queue, SessionType, TimerPause, TimerQueue, TimerSession};
+
+/// On-disk copy of the timer's state, used to pick up where the app left off after a restart.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TimerSnapshot {
+    pub session: Option<SessionSnapshot>,
+    pub iteration: u32,
+    pub queue: Queue,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SessionSnapshot {
+    pub _type: SessionType,
+    pub duration: i64,
+    #[serde(default)]
+    pub planned_duration: Option<i64>,
+    pub time_elapsed_ms: u64,
+    pub started_at: Option<i64>,
+    pub intent: Intent,
+    #[serde(default)]
+    pub task_id: Option<i32>,
+    #[serde(default)]
+    pub pauses: Vec<TimerPause>,
+    #[serde(default)]
+    pub paused_at: Option<i64>,
+}
+
+impl SessionSnapshot {
+    /// Refreshes the intent from the database, returns `None` when the intent has been
+    /// deleted in the meantime. A task which is gone or was moved to another intent is dropped.
+    pub fn validate(mut self, conn: &mut SqliteConnection) -> Option<Self> {
+        let intent = IntentBmc::get(conn, self.intent.id).ok()?;
+        self.task_id = self.task_id.filter(|task_id| {
+            matches!(TaskBmc::get(conn, *task_id), Ok(task) if task.intent_id == intent.id)
+        });
+        self.intent = intent;
+        Some(self)
+    }
+
+    /// Time the session last ran at, derived from its start, elapsed time and pauses.
+    pub fn last_active_at(&self) -> Option<i64> {
+        let started_at = self.started_at?;
+        let paused: i64 = self
+            .pauses
+            .iter()
+            .map(|pause| pause.finished_at - pause.started_at)
+            .sum();
+        Some(started_at + (self.time_elapsed_ms / 1000) as i64 + paused)
+    }
+}
+
+/// What to do with a session restored from a previous run.
+#[derive(TS, Deserialize, Debug, Clone)]
+#[ts(export, export_to = "../src/bindings/")]
+pub enum RestoredSessionAction {
+    Resume,
+    Discard,
+    Save,
+}
+
+impl TimerSnapshot {
+    pub fn capture(
+        session: Option<&TimerSession>,
+        iteration: &AtomicU32,
+        queue: &TimerQueue,
+    ) -> Self {
+        Self {
+            session: session.map(|session| {
+                let (pauses, paused_at) = session.pauses();
+                SessionSnapshot {
+                    _type: session._type.clone(),
+                    duration: session.duration,
+                    planned_duration: Some(session.planned_duration),
+                    time_elapsed_ms: session.elapsed().as_millis() as u64,
+                    started_at: session.started_at,
+                    intent: session.intent.clone(),
+                    task_id: session.task_id,
+                    pauses: pauses.to_vec(),
+                    paused_at,
+                }
+            }),
+            iteration: iteration.load(Ordering::SeqCst),
+            queue: queue.get(),
+        }
+    }
+}
+
+impl Config for TimerSnapshot {
+    fn file_path(base_dir: &PathBuf) -> PathBuf {
+        base_dir.join("timer_snapshot.json")
+    }
+}
+
+impl Default for TimerSnapshot {
+    fn default() -> Self {
+        Self {
+            session: None,
+            iteration: 0,
+            queue: Queue::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod snapshot_tests {
+    use std::time::{Duration, Instant};
+
+    use crate::{
+        db::Db,
+        models::{CreateIntent, CreateTask, TaskPriority},
+        timer::clock::SessionClock,
+    };
+
+    use super::*;
+
+    fn session_json() -> serde_json::Value {
+        serde_json::json!({
+            "_type": "Focus",
+            "duration": 25,
+            "time_elapsed_ms": 90_500,
+            "started_at": 1688947200,
+            "intent": {
+                "id": 1,
+                "label": "foo",
+                "pinned": false,
+                "created_at": 1688900000,
+                "archived_at": null
+            }
+        })
+    }
+
+    #[test]
+    fn test_restore_snapshot_without_later_fields() {
+        let snapshot: SessionSnapshot = serde_json::from_value(session_json()).unwrap();
+        assert_eq!(snapshot.planned_duration, None);
+        assert_eq!(snapshot.task_id, None);
+        assert!(snapshot.pauses.is_empty());
+        assert_eq!(snapshot.paused_at, None);
+    }
+
+    #[test]
+    fn test_session_snapshot_last_active_at() {
+        let mut value = session_json();
+        value["pauses"] = serde_json::json!([
+            { "started_at": 1688947230, "finished_at": 1688947290 }
+        ]);
+        let snapshot: SessionSnapshot = serde_json::from_value(value).unwrap();
+        // 90 seconds of focus and a minute long pause after the start.
+        assert_eq!(snapshot.last_active_at(), Some(1688947200 + 90 + 60));
+
+        let mut value = session_json();
+        value["started_at"] = serde_json::Value::Null;
+        let snapshot: SessionSnapshot = serde_json::from_value(value).unwrap();
+        assert_eq!(snapshot.last_active_at(), None);
+    }
+
+    #[test]
+    fn test_validate_session_snapshot() {
+        let mut conn = Db::establish_test_connection().unwrap();
+        let mut intent_ids = vec![];
+        for label in ["renamed", "other"] {
+            let data = CreateIntent {
+                label: label.to_string(),
+                parent_id: None,
+            };
+            intent_ids.push(IntentBmc::create(&mut conn, &data).unwrap());
+        }
+        let mut task_ids = vec![];
+        for intent_id in &intent_ids {
+            let data = CreateTask {
+                body: "foo".to_string(),
+                intent_id: *intent_id,
+                priority: TaskPriority::Normal,
+                due_date: None,
+                parent_task_id: None,
+            };
+            task_ids.push(TaskBmc::create(&mut conn, &data).unwrap());
+        }
+        let snapshot = |task_id: i32| {
+            let mut value = session_json();
+            value["task_id"] = task_id.into();
+            serde_json::from_value::<SessionSnapshot>(value).unwrap()
+        };
+
+        // The intent is refreshed from the database and the task is kept.
+        let session = snapshot(task_ids[0]).validate(&mut conn).unwrap();
+        assert_eq!(session.intent.label, "renamed");
+        assert_eq!(session.task_id, Some(task_ids[0]));
+        // Tasks of other intents and deleted tasks are dropped.
+        let session = snapshot(task_ids[1]).validate(&mut conn).unwrap();
+        assert_eq!(session.task_id, None);
+        TaskBmc::delete(&mut conn, task_ids[0]).unwrap();
+        let session = snapshot(task_ids[0]).validate(&mut conn).unwrap();
+        assert_eq!(session.task_id, None);
+
+        // The snapshot is stale once its intent is deleted.
+        IntentBmc::delete(&mut conn, intent_ids[0]).unwrap();
+        assert!(snapshot(task_ids[0]).validate(&mut conn).is_none());
+    }
+
+    #[test]
+    fn test_snapshot_roundtrip() {
+        let mut value = session_json();
+        value["task_id"] = 3.into();
+        value["pauses"] = serde_json::json!([
+            { "started_at": 1688947500, "finished_at": 1688947560 }
+        ]);
+        value["paused_at"] = 1688947700.into();
+        let snapshot = TimerSnapshot {
+            session: Some(serde_json::from_value(value).unwrap()),
+            iteration: 2,
+            queue: Queue::new(),
+        };
+
+        let json = serde_json::to_string(&snapshot).unwrap();
+        let snapshot: TimerSnapshot = serde_json::from_str(&json).unwrap();
+        assert_eq!(snapshot.iteration, 2);
+        let session = snapshot.session.unwrap();
+        assert_eq!(session._type, SessionType::Focus);
+        assert_eq!(session.task_id, Some(3));
+        assert_eq!(session.pauses.len(), 1);
+        assert_eq!(session.paused_at, Some(1688947700));
+
+        // The restored session carries on from the elapsed time of the previous run.
+        let now = Instant::now();
+        let mut clock = SessionClock::carried(Duration::from_millis(session.time_elapsed_ms));
+        assert_eq!(clock.elapsed(now), Duration::from_millis(90_500));
+        clock.play(now);
+        assert_eq!(
+            clock.elapsed(now + Duration::from_secs(10)),
+            Duration::from_millis(100_500)
+        );
+    }
+}
@@ -0,0 +1,13 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type BmcError =
+  | "InvalidTimeRange"
+  | "OverlappingSession"
+  | "InvalidDuration"
+  | "IntentCycle"
+  | "InvalidColor"
+  | "InvalidParentTask"
+  | "InvalidEstimate"
+  | "InvalidRecurrence"
+  | "MissingToday"
+  | "UnresolvedDay";
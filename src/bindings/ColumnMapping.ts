@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export interface ColumnMapping {
+  intent: string;
+  started_at: string;
+  finished_at: string | null;
+  duration: string | null;
+  summary: string | null;
+  tags: string | null;
+  task: string | null;
+  datetime_format: string | null;
+}
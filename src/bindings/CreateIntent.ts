@@ -2,4 +2,5 @@
 
 export interface CreateIntent {
   label: string;
+  parent_id: number | null;
 }
@@ -1,7 +1,10 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SessionType } from "./SessionType";
 
 export interface CreateSession {
   duration: number;
   started_at: number;
   intent_id: number;
+  session_type: SessionType;
+  planned_duration: number | null;
 }
@@ -1,6 +1,10 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TaskPriority } from "./TaskPriority";
 
 export interface CreateTask {
   body: string;
   intent_id: number;
+  priority: TaskPriority;
+  due_date: string | null;
+  parent_task_id: number | null;
 }
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TaskEstimate } from "./TaskEstimate";
+
+export interface EstimationReport {
+  ratio: number | null;
+  on_estimate: number;
+  underestimated: number;
+  overestimated: number;
+  tasks: Array<TaskEstimate>;
+}
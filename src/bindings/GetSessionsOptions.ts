@@ -1,7 +1,13 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SessionType } from "./SessionType";
 
 export interface GetSessionsOptions {
   intent_id: number | null;
+  session_types: Array<SessionType> | null;
+  include_manual: boolean | null;
+  day: string | null;
+  started_from: number | null;
+  started_to: number | null;
   offset: number | null;
   limit: number | null;
 }
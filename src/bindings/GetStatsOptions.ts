@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { StatsGroupBy } from "./StatsGroupBy";
+
+export interface GetStatsOptions {
+  group_by: StatsGroupBy;
+  from: string | null;
+  to: string | null;
+  include_manual: boolean | null;
+}
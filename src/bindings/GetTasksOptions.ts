@@ -1,8 +1,13 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TaskDueFilter } from "./TaskDueFilter";
+import type { TaskSortBy } from "./TaskSortBy";
 
 export interface GetTasksOptions {
   intent_id: number | null;
   offset: number | null;
   limit: number | null;
   completed: boolean | null;
+  sort_by: TaskSortBy | null;
+  due: TaskDueFilter | null;
+  today: string | null;
 }
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export interface GoalProgress {
+  daily_focused: number;
+  daily_goal: number;
+  weekly_focused: number;
+  weekly_goal: number;
+}
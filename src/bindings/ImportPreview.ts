@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ImportEntry } from "./ImportEntry";
+import type { ImportRowError } from "./ImportRowError";
+
+export interface ImportPreview {
+  entries: Array<ImportEntry>;
+  errors: Array<ImportRowError>;
+  new_intents: Array<string>;
+  new_tags: Array<string>;
+  new_tasks: Array<string>;
+}
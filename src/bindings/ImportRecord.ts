@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export interface ImportRecord {
+  row: number;
+  intent: string;
+  tags: Array<string>;
+  started_at: number;
+  finished_at: number;
+  duration: number;
+  summary: string | null;
+  task: string | null;
+}
@@ -1,4 +1,5 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { IntentTargetPeriod } from "./IntentTargetPeriod";
 
 export interface Intent {
   id: number;
@@ -6,4 +7,12 @@ export interface Intent {
   pinned: boolean;
   created_at: number;
   archived_at: number;
+  parent_id: number | null;
+  target_period: IntentTargetPeriod;
+  target: number | null;
+  budget: number | null;
+  color: string | null;
+  icon: string | null;
+  description: string | null;
+  position: number;
 }
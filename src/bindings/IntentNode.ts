@@ -0,0 +1,20 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { IntentNode } from "./IntentNode";
+import type { IntentTargetPeriod } from "./IntentTargetPeriod";
+
+export interface IntentNode {
+  id: number;
+  label: string;
+  pinned: boolean;
+  created_at: number;
+  archived_at: number;
+  parent_id: number | null;
+  target_period: IntentTargetPeriod;
+  target: number | null;
+  budget: number | null;
+  color: string | null;
+  icon: string | null;
+  description: string | null;
+  position: number;
+  children: Array<IntentNode>;
+}
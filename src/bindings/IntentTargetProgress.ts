@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { IntentTargetPeriod } from "./IntentTargetPeriod";
+
+export interface IntentTargetProgress {
+  intent_id: number;
+  target_period: IntentTargetPeriod;
+  focused: number;
+  target: number | null;
+  budget: number | null;
+}
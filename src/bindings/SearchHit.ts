@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SearchHitKind } from "./SearchHitKind";
+
+export interface SearchHit {
+  kind: SearchHitKind;
+  id: number;
+  snippet: string;
+  rank: number;
+}
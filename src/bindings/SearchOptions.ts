@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SearchHitKind } from "./SearchHitKind";
+
+export interface SearchOptions {
+  query: string;
+  kinds: Array<SearchHitKind> | null;
+  limit: number | null;
+}
@@ -1,4 +1,5 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SessionType } from "./SessionType";
 
 export interface Session {
   id: number;
@@ -7,4 +8,7 @@ export interface Session {
   started_at: number;
   finished_at: number;
   intent_id: number;
+  session_type: SessionType;
+  planned_duration: number | null;
+  is_manual: boolean;
 }
@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SessionPause } from "./SessionPause";
+import type { SessionType } from "./SessionType";
+
+export interface SessionDetails {
+  id: number;
+  duration: number;
+  summary: string | null;
+  started_at: number;
+  finished_at: number;
+  intent_id: number;
+  session_type: SessionType;
+  planned_duration: number | null;
+  is_manual: boolean;
+  pauses: Array<SessionPause>;
+}
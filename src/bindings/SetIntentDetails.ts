@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export interface SetIntentDetails {
+  color: string | null;
+  icon: string | null;
+  description: string | null;
+}
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { IntentTargetPeriod } from "./IntentTargetPeriod";
+
+export interface SetIntentTarget {
+  target_period: IntentTargetPeriod;
+  target: number | null;
+  budget: number | null;
+}
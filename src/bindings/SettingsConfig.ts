@@ -12,4 +12,7 @@ export interface SettingsConfig {
   main_minimize_to_tray: boolean;
   main_always_on_top: boolean;
   main_display_on_timer_complete: boolean;
+  timezone: string | null;
+  day_start_hour: number;
+  ics_feed_dir: string | null;
 }
@@ -12,4 +12,6 @@ export interface SettingsConfigForUpdate {
   main_minimize_to_tray?: boolean;
   main_always_on_top?: boolean;
   main_display_on_timer_complete?: boolean;
+  timezone?: string | null;
+  day_start_hour?: number;
 }
@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { StatsEntry } from "./StatsEntry";
+
+export interface Stats {
+  total_duration: number;
+  sessions_count: number;
+  average_duration: number;
+  entries: Array<StatsEntry>;
+}
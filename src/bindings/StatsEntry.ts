@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export interface StatsEntry {
+  key: string;
+  id: number | null;
+  color: string | null;
+  total_duration: number;
+  sessions_count: number;
+  average_duration: number;
+}
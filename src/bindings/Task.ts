@@ -1,4 +1,6 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TaskPriority } from "./TaskPriority";
+import type { TaskRecurrence } from "./TaskRecurrence";
 
 export interface Task {
   id: number;
@@ -7,4 +9,11 @@ export interface Task {
   created_at: number;
   finished_at: number;
   intent_id: number;
+  position: number;
+  priority: TaskPriority;
+  due_date: string | null;
+  parent_task_id: number | null;
+  estimated_pomodoros: number | null;
+  recurrence: TaskRecurrence | null;
+  series_id: number | null;
 }
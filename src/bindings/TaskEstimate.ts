@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export interface TaskEstimate {
+  task_id: number;
+  estimated_pomodoros: number | null;
+  actual_pomodoros: number;
+  estimated_minutes: number | null;
+  actual_minutes: number;
+}
@@ -1,4 +1,5 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { FocusMode } from "./FocusMode";
 
 export interface TimerConfigForUpdate {
   focus_duration?: number;
@@ -8,4 +9,9 @@ export interface TimerConfigForUpdate {
   auto_start_focus?: boolean;
   auto_start_breaks?: boolean;
   session_summary?: boolean;
+  focus_mode?: FocusMode;
+  flowtime_break_ratio?: number;
+  daily_goal?: number;
+  weekly_goal?: number;
+  rest_weekdays?: Array<number>;
 }
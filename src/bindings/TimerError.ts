@@ -3,4 +3,7 @@
 export type TimerError =
   | "UndefinedSession"
   | "EmptyQueue"
-  | "QueueIndexOutOfRange";
+  | "QueueIndexOutOfRange"
+  | "NoRestoredSession"
+  | "InvalidDuration"
+  | "NotOnBreak";
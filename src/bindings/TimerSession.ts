@@ -6,7 +6,12 @@ export interface TimerSession {
   _type: SessionType;
   is_playing: boolean;
   duration: number;
+  planned_duration: number;
   time_elapsed: number;
+  time_remaining_ms: number;
   started_at: number;
   intent: Intent;
+  task_id: number | null;
+  is_overtime: boolean;
+  is_restored: boolean;
 }
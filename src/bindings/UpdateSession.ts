@@ -2,4 +2,8 @@
 
 export interface UpdateSession {
   summary: string | null;
+  duration: number | null;
+  started_at: number | null;
+  finished_at: number | null;
+  intent_id: number | null;
 }
@@ -1,5 +1,8 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TaskPriority } from "./TaskPriority";
 
 export interface UpdateTask {
   body: string | null;
+  priority: TaskPriority | null;
+  intent_id: number | null;
 }
@@ -29,6 +29,8 @@ export const WindowContainer: React.FC<WindowContainerProps> = (props) => {
       store.setSettingsConfig(data);
     },
     timer_config_updated: ({ data }) => store.setTimerConfig(data),
+    // Every id might have changed, start over with fresh data.
+    backup_imported: () => window.location.reload(),
     theme_updated: ({ data: id }) => {
       ipc.getTheme(id).then((data) => {
         if (store.currentTheme?.id === id) {
@@ -59,6 +59,30 @@ export const useNotifications = () => {
         ...BASE_NOTIFICATION,
       });
     },
+    focus_overtime_started: () => {
+      handleNotification({
+        message: "Your Focus session is now running overtime.",
+        ...BASE_NOTIFICATION,
+      });
+    },
+    daily_goal_reached: () => {
+      handleNotification({
+        message: "You have reached your daily focus goal.",
+        ...BASE_NOTIFICATION,
+      });
+    },
+    intent_target_reached: () => {
+      handleNotification({
+        message: "You have reached the focus target of an intent.",
+        ...BASE_NOTIFICATION,
+      });
+    },
+    intent_budget_exceeded: () => {
+      handleNotification({
+        message: "You have exceeded the focus budget of an intent.",
+        ...BASE_NOTIFICATION,
+      });
+    },
     break_completed: () => {
       handleNotification({
         message: "Your break has completed.",
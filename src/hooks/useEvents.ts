@@ -7,6 +7,8 @@ import { SettingsConfig } from "@/bindings/SettingsConfig";
 import { EventPayload } from "@/bindings/EventPayload";
 import { TimerSession } from "@/bindings/TimerSession";
 import { Queue } from "@/bindings/Queue";
+import { GoalProgress } from "@/bindings/GoalProgress";
+import { IntentTargetProgress } from "@/bindings/IntentTargetProgress";
 import { CreateIntentTag } from "@/bindings/CreateIntentTag";
 import { DeleteIntentTag } from "@/bindings/DeleteIntentTag";
 import { Notification } from "@/features/notification/types";
@@ -20,6 +22,7 @@ type Events = {
   intent_deleted: Id;
   intent_archived: Id;
   intent_unarchived: Id;
+  intents_reordered: Id;
   intent_tag_created: CreateIntentTag;
   intent_tag_deleted: DeleteIntentTag;
 
@@ -32,6 +35,7 @@ type Events = {
   task_deleted: EventPayload<Id>;
   task_completed: EventPayload<Id>;
   task_uncompleted: EventPayload<Id>;
+  tasks_reordered: EventPayload<Id>;
 
   script_created: EventPayload<Id>;
   script_updated: EventPayload<Id>;
@@ -43,10 +47,23 @@ type Events = {
 
   session_created: Id;
   session_updated: EventPayload<Id>;
+  session_deleted: EventPayload<Id>;
+
+  rest_day_created: Id;
+  rest_day_deleted: Id;
+
+  daily_goal_progress: GoalProgress;
+  daily_goal_reached: GoalProgress;
+  intent_target_progress: IntentTargetProgress;
+  intent_target_reached: IntentTargetProgress;
+  intent_budget_exceeded: IntentTargetProgress;
 
   settings_config_updated: EventPayload<SettingsConfig>;
   timer_config_updated: EventPayload<TimerConfig>;
 
+  backup_imported: undefined;
+  history_imported: undefined;
+
   timer_play: undefined;
   timer_skip: undefined;
   timer_session_updated: TimerSession;
@@ -57,6 +74,7 @@ type Events = {
   preview_theme: Theme;
 
   focus_session_completed: undefined;
+  focus_overtime_started: undefined;
   break_completed: undefined;
   long_break_completed: undefined;
 };
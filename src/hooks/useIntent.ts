@@ -54,6 +54,7 @@ export const useIntent = (id: ModelId | null) => {
     intent_updated: (id) => intent?.id === id && getIntent(id),
     intent_archived: (id) => intent?.id === id && getIntent(id),
     intent_unarchived: (id) => intent?.id === id && getIntent(id),
+    intents_reordered: () => intent && getIntent(intent.id),
     intent_deleted: (id) => intent?.id === id && setIntent(undefined),
     intent_tag_created: (data) => data.intent_id === id && getTags(id),
     intent_tag_deleted: (data) => data.intent_id === id && getTags(id),
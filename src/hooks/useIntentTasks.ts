@@ -2,12 +2,14 @@ import React from "react";
 
 import ipc from "@/ipc";
 import { Task } from "@/bindings/Task";
+import { TaskProgress } from "@/bindings/TaskProgress";
 import { UpdateTask } from "@/bindings/UpdateTask";
 
 import useEvents from "./useEvents";
 
 export const useIntentTasks = (intentId: number, completed: boolean) => {
   const [data, setData] = React.useState<Task[]>([]);
+  const [progress, setProgress] = React.useState<TaskProgress[]>([]);
 
   const getList = async () => {
     const result = await ipc.getTasks({
@@ -15,12 +17,22 @@ export const useIntentTasks = (intentId: number, completed: boolean) => {
       completed,
       limit: null,
       offset: null,
+      sort_by: null,
+      due: null,
+      today: null,
     });
     setData(result);
+    ipc.getTaskProgress(intentId).then((data) => setProgress(data));
     return result;
   };
-  const create = async (data: { body: string }) => {
-    return await ipc.createTask({ ...data, intent_id: intentId });
+  const create = async (data: { body: string; parent_task_id?: number }) => {
+    return await ipc.createTask({
+      priority: "Normal",
+      due_date: null,
+      parent_task_id: null,
+      ...data,
+      intent_id: intentId,
+    });
   };
   const update = async (id: number, data: Partial<UpdateTask>) => {
     return await ipc.updateTask(id, data);
@@ -38,11 +50,12 @@ export const useIntentTasks = (intentId: number, completed: boolean) => {
     task_deleted: () => getList(),
     task_completed: () => getList(),
     task_uncompleted: () => getList(),
+    tasks_reordered: () => getList(),
   });
 
   React.useEffect(() => {
     getList();
   }, [completed]);
 
-  return { data, getList, create, update, complete, uncomplete };
+  return { data, progress, getList, create, update, complete, uncomplete };
 };
@@ -40,6 +40,8 @@ export const useIntents = () => {
       setData((intents) => intents.filter((i) => i.id !== id)),
     intent_archived: (id) => get(id).then((data) => onIntentUpdate(id, data)),
     intent_unarchived: (id) => get(id).then((data) => onIntentUpdate(id, data)),
+    // Positions of the siblings change too
+    intents_reordered: () => getList().then((data) => setData(data)),
   });
 
   return {
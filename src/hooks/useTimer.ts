@@ -5,6 +5,7 @@ import { QueueSession } from "@/bindings/QueueSession";
 import { TimerSession } from "@/bindings/TimerSession";
 import { Intent } from "@/bindings/Intent";
 import { Queue } from "@/bindings/Queue";
+import { RestoredSessionAction } from "@/bindings/RestoredSessionAction";
 
 import useEvents from "./useEvents";
 import { TimerConfig } from "@/bindings/TimerConfig";
@@ -48,6 +49,10 @@ export const useTimer = () => {
     ipc.timerSetSessionIntent(intent);
   };
 
+  const setTask = async (taskId: number | null) => {
+    return ipc.timerSetSessionTask(taskId);
+  };
+
   const play = async () => {
     return ipc.timerPlay().catch((err) => console.log(err));
   };
@@ -64,6 +69,22 @@ export const useTimer = () => {
     return ipc.timerSkip();
   };
 
+  const extend = async (minutes: number) => {
+    return ipc.timerExtend(minutes);
+  };
+
+  const shorten = async (minutes: number) => {
+    return ipc.timerShorten(minutes);
+  };
+
+  const snoozeBreak = async (minutes: number) => {
+    return ipc.timerSnoozeBreak(minutes);
+  };
+
+  const resolveRestoredSession = async (action: RestoredSessionAction) => {
+    return ipc.timerResolveRestoredSession(action);
+  };
+
   const addToQueue = async (data: QueueSession) => {
     return ipc.timerAddToQueue(data);
   };
@@ -96,10 +117,15 @@ export const useTimer = () => {
     session,
     queue,
     setIntent,
+    setTask,
     play,
     stop,
     restart,
     skip,
+    extend,
+    shorten,
+    snoozeBreak,
+    resolveRestoredSession,
     config,
     addToQueue,
     removeFromQueue,
@@ -0,0 +1,28 @@
+import { invoke } from "@tauri-apps/api";
+
+import { ExportOptions } from "@/bindings/ExportOptions";
+
+/** Writes `sessions.csv`, `tasks.csv` and `intents.csv` into `dir`. */
+export const exportCsv = async (dir: string, options: ExportOptions) => {
+  return await invoke<string[]>("export_csv", { dir, options });
+};
+
+/** Writes a JSON backup of the whole database and the config files to `path`. */
+export const exportBackup = async (path: string) => {
+  return await invoke<void>("export_backup", { path });
+};
+
+/** Replaces all data with the content of the backup at `path`. */
+export const importBackup = async (path: string) => {
+  return await invoke<void>("import_backup", { path });
+};
+
+/** Writes focus sessions as an iCalendar file to `path`. */
+export const exportIcs = async (path: string, options: ExportOptions) => {
+  return await invoke<void>("export_ics", { path, options });
+};
+
+/** Sets the folder of the subscribable calendar feed, `null` turns it off. */
+export const setIcsFeedDir = async (dir: string | null) => {
+  return await invoke<void>("set_ics_feed_dir", { dir });
+};
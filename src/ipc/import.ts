@@ -0,0 +1,14 @@
+import { invoke } from "@tauri-apps/api";
+
+import { ImportFormat } from "@/bindings/ImportFormat";
+import { ImportPreview } from "@/bindings/ImportPreview";
+
+/** Parses the file at `path` and reports what importing it would do, nothing is written. */
+export const previewImport = async (path: string, format: ImportFormat) => {
+  return await invoke<ImportPreview>("preview_import", { path, format });
+};
+
+/** Imports sessions, intents, tags and tasks from the file at `path`, skipping duplicates and overlaps. */
+export const importHistory = async (path: string, format: ImportFormat) => {
+  return await invoke<ImportPreview>("import_history", { path, format });
+};
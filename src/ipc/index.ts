@@ -8,5 +8,10 @@ export * from "./config";
 export * from "./state";
 export * from "./timer";
 export * from "./tags";
+export * from "./stats";
+export * from "./restDays";
+export * from "./export";
+export * from "./import";
+export * from "./search";
 
 export * as default from "./index";
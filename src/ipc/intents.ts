@@ -2,8 +2,12 @@ import { invoke } from "@tauri-apps/api";
 
 import { ModelId } from "@/types";
 import { Intent } from "@/bindings/Intent";
+import { IntentNode } from "@/bindings/IntentNode";
 import { CreateIntent } from "@/bindings/CreateIntent";
 import { UpdateIntent } from "@/bindings/UpdateIntent";
+import { SetIntentDetails } from "@/bindings/SetIntentDetails";
+import { SetIntentTarget } from "@/bindings/SetIntentTarget";
+import { IntentTargetProgress } from "@/bindings/IntentTargetProgress";
 import { Tag } from "@/bindings/Tag";
 import { CreateIntentTag } from "@/bindings/CreateIntentTag";
 import { DeleteIntentTag } from "@/bindings/DeleteIntentTag";
@@ -31,8 +35,42 @@ export const getIntents = async () => {
   return await invoke<Intent[]>("get_intents");
 };
 
-export const archiveIntent = async (id: ModelId) => {
-  return await invoke<ModelId>("archive_intent", { id });
+/** Top level intents with their sub-intents nested as children. */
+export const getIntentTree = async () => {
+  return await invoke<IntentNode[]>("get_intent_tree");
+};
+
+/** Moves an intent under another one, `null` makes it a top level intent. */
+export const setIntentParent = async (
+  id: ModelId,
+  parentId: ModelId | null
+) => {
+  return await invoke<ModelId>("set_intent_parent", { id, parentId });
+};
+
+/** Color must be in the `#rrggbb` form, `null` clears a field. */
+export const setIntentDetails = async (id: ModelId, data: SetIntentDetails) => {
+  return await invoke<ModelId>("set_intent_details", { id, data });
+};
+
+/** Moves an intent to the given position among the intents sharing its parent. */
+export const moveIntent = async (id: ModelId, position: number) => {
+  return await invoke<ModelId>("move_intent", { id, position });
+};
+
+/** Target and budget are in minutes, `null` clears them. */
+export const setIntentTarget = async (id: ModelId, data: SetIntentTarget) => {
+  return await invoke<ModelId>("set_intent_target", { id, data });
+};
+
+/** Progress of every intent which has a target or a budget set. */
+export const getIntentTargetProgress = async () => {
+  return await invoke<IntentTargetProgress[]>("get_intent_target_progress");
+};
+
+/** With `cascade` all sub-intents are archived as well. */
+export const archiveIntent = async (id: ModelId, cascade = false) => {
+  return await invoke<ModelId>("archive_intent", { id, cascade });
 };
 
 export const unarchiveIntent = async (id: ModelId) => {
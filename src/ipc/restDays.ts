@@ -0,0 +1,17 @@
+import { invoke } from "@tauri-apps/api";
+
+import { ModelId } from "@/types";
+import { CreateRestDay } from "@/bindings/CreateRestDay";
+import { RestDay } from "@/bindings/RestDay";
+
+export const createRestDay = async (data: CreateRestDay) => {
+  return await invoke<number>("create_rest_day", { data });
+};
+
+export const deleteRestDay = async (id: ModelId) => {
+  return await invoke<number>("delete_rest_day", { id });
+};
+
+export const getRestDays = async () => {
+  return await invoke<RestDay[]>("get_rest_days");
+};
@@ -0,0 +1,9 @@
+import { invoke } from "@tauri-apps/api";
+
+import { SearchHit } from "@/bindings/SearchHit";
+import { SearchOptions } from "@/bindings/SearchOptions";
+
+/** Full-text search over intents, tasks, tags and session summaries, best matches first. */
+export const search = async (options: SearchOptions) => {
+  return await invoke<SearchHit[]>("search", { options });
+};
@@ -1,17 +1,27 @@
 import { invoke } from "@tauri-apps/api";
 
 import { ModelId } from "@/types";
+import { CreateManualSession } from "@/bindings/CreateManualSession";
 import { Session } from "@/bindings/Session";
+import { SessionDetails } from "@/bindings/SessionDetails";
 import { UpdateSession } from "@/bindings/UpdateSession";
 
 export const getSession = async (id: ModelId) => {
-  return await invoke<Session>("get_session", { id });
+  return await invoke<SessionDetails>("get_session", { id });
 };
 
 export const getSessions = async () => {
   return await invoke<Session[]>("get_sessions");
 };
 
+export const addManualSession = async (data: CreateManualSession) => {
+  return await invoke<number>("add_manual_session", { data });
+};
+
 export const updateSession = async (id: number, data: UpdateSession) => {
   return await invoke<number>("update_session", { id, data });
 };
+
+export const deleteSession = async (id: ModelId) => {
+  return await invoke<number>("delete_session", { id });
+};
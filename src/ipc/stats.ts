@@ -0,0 +1,35 @@
+import { invoke } from "@tauri-apps/api";
+
+import { GetStatsOptions } from "@/bindings/GetStatsOptions";
+import { GoalProgress } from "@/bindings/GoalProgress";
+import { HeatmapDay } from "@/bindings/HeatmapDay";
+import { HourlyDistribution } from "@/bindings/HourlyDistribution";
+import { Stats } from "@/bindings/Stats";
+import { Streaks } from "@/bindings/Streaks";
+
+export const getStats = async (options: GetStatsOptions) => {
+  return await invoke<Stats>("get_stats", { options });
+};
+
+/** Days are `YYYY-MM-DD` strings, both ends are inclusive. */
+export const getHeatmap = async (from: string, to: string) => {
+  return await invoke<HeatmapDay[]>("get_heatmap", { from, to });
+};
+
+export const getHourlyDistribution = async (
+  from: string | null = null,
+  to: string | null = null
+) => {
+  return await invoke<HourlyDistribution>("get_hourly_distribution", {
+    from,
+    to,
+  });
+};
+
+export const getStreaks = async () => {
+  return await invoke<Streaks>("get_streaks");
+};
+
+export const getGoalProgress = async () => {
+  return await invoke<GoalProgress>("get_goal_progress");
+};
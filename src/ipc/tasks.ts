@@ -5,6 +5,10 @@ import { Task } from "@/bindings/Task";
 import { UpdateTask } from "@/bindings/UpdateTask";
 import { CreateTask } from "@/bindings/CreateTask";
 import { GetTasksOptions } from "@/bindings/GetTasksOptions";
+import { TaskProgress } from "@/bindings/TaskProgress";
+import { TaskEstimate } from "@/bindings/TaskEstimate";
+import { TaskRecurrence } from "@/bindings/TaskRecurrence";
+import { EstimationReport } from "@/bindings/EstimationReport";
 
 export const createTask = async (data: CreateTask) => {
   return await invoke<ModelId>("create_task", { data });
@@ -26,10 +30,83 @@ export const getTasks = async (options: GetTasksOptions) => {
   return await invoke<Task[]>("get_tasks", { options });
 };
 
-export const completeTask = async (id: ModelId) => {
-  return await invoke<ModelId>("complete_task", { id });
+/** Moves a task to the given position among the tasks of its intent. */
+export const moveTask = async (id: ModelId, position: number) => {
+  return await invoke<ModelId>("move_task", { id, position });
+};
+
+/** Due date as `YYYY-MM-DD`, `null` removes it. */
+export const setTaskDueDate = async (id: ModelId, dueDate: string | null) => {
+  return await invoke<ModelId>("set_task_due_date", { id, dueDate });
+};
+
+/** `null` stops the task from recurring. */
+export const setTaskRecurrence = async (
+  id: ModelId,
+  recurrence: TaskRecurrence | null
+) => {
+  return await invoke<ModelId>("set_task_recurrence", { id, recurrence });
+};
+
+/** Completed instances of the task's recurring series, latest first. */
+export const getTaskHistory = async (id: ModelId) => {
+  return await invoke<Task[]>("get_task_history", { id });
+};
+
+/** `null` removes the estimate. */
+export const setTaskEstimate = async (
+  id: ModelId,
+  estimatedPomodoros: number | null
+) => {
+  return await invoke<ModelId>("set_task_estimate", { id, estimatedPomodoros });
+};
+
+/** Estimated pomodoros next to the focus sessions spent on the task. */
+export const getTaskEstimate = async (id: ModelId) => {
+  return await invoke<TaskEstimate>("get_task_estimate", { id });
+};
+
+/** Estimates of completed tasks against their actual sessions, optionally of one intent. */
+export const getEstimationReport = async (intentId?: ModelId) => {
+  return await invoke<EstimationReport>("get_estimation_report", { intentId });
+};
+
+/** Completed and total subtasks of every task of the intent which has subtasks. */
+export const getTaskProgress = async (intentId: ModelId) => {
+  return await invoke<TaskProgress[]>("get_task_progress", { intentId });
+};
+
+/** With `cascade` all subtasks are completed as well. */
+export const completeTask = async (id: ModelId, cascade = false) => {
+  return await invoke<ModelId>("complete_task", { id, cascade });
 };
 
 export const uncompleteTask = async (id: ModelId) => {
   return await invoke<ModelId>("uncomplete_task", { id });
 };
+
+/** Copies the task along with its subtasks to the intent, the copies start uncompleted. */
+export const copyTask = async (id: ModelId, intentId: ModelId) => {
+  return await invoke<ModelId>("copy_task", { id, intentId });
+};
+
+export const moveTasksToIntent = async (ids: ModelId[], intentId: ModelId) => {
+  return await invoke<ModelId[]>("move_tasks_to_intent", { ids, intentId });
+};
+
+export const completeTasks = async (ids: ModelId[]) => {
+  return await invoke<ModelId[]>("complete_tasks", { ids });
+};
+
+export const uncompleteTasks = async (ids: ModelId[]) => {
+  return await invoke<ModelId[]>("uncomplete_tasks", { ids });
+};
+
+export const deleteTasks = async (ids: ModelId[]) => {
+  return await invoke<ModelId[]>("delete_tasks", { ids });
+};
+
+/** Deletes tasks completed more than `days` days ago, of every intent unless one is given. */
+export const clearCompletedTasks = async (days: number, intentId?: ModelId) => {
+  return await invoke<ModelId[]>("clear_completed_tasks", { days, intentId });
+};
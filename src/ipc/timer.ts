@@ -4,6 +4,7 @@ import { QueueSession } from "@/bindings/QueueSession";
 import { TimerSession } from "@/bindings/TimerSession";
 import { Intent } from "@/bindings/Intent";
 import { Queue } from "@/bindings/Queue";
+import { RestoredSessionAction } from "@/bindings/RestoredSessionAction";
 
 export const timerGetSession = async () => {
   return await invoke<TimerSession>("timer_get_session");
@@ -13,6 +14,11 @@ export const timerSetSessionIntent = async (intent: Intent) => {
   return await invoke<void>("timer_set_session_intent", { intent });
 };
 
+/** Focus sessions get attributed to the task once saved, `null` clears it. */
+export const timerSetSessionTask = async (taskId: number | null) => {
+  return await invoke<void>("timer_set_session_task", { taskId });
+};
+
 export const timerPlay = async () => {
   return await invoke<void>("timer_play");
 };
@@ -29,6 +35,24 @@ export const timerSkip = async () => {
   return await invoke<void>("timer_skip");
 };
 
+export const timerExtend = async (minutes: number) => {
+  return await invoke<void>("timer_extend", { minutes });
+};
+
+export const timerShorten = async (minutes: number) => {
+  return await invoke<void>("timer_shorten", { minutes });
+};
+
+export const timerSnoozeBreak = async (minutes: number) => {
+  return await invoke<void>("timer_snooze_break", { minutes });
+};
+
+export const timerResolveRestoredSession = async (
+  action: RestoredSessionAction
+) => {
+  return await invoke<void>("timer_resolve_restored_session", { action });
+};
+
 export const timerSetIntent = async (id: number) => {
   return await invoke<void>("timer_set_intent", { id });
 };
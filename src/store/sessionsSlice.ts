@@ -7,6 +7,7 @@ export interface SessionsSlice {
   sessions: Session[];
   setSessions: (data: Session[]) => void;
   addSession: (data: Session) => void;
+  removeSession: (id: ModelId) => void;
   getSessionsByIntentId: (id: ModelId) => Session[];
 }
 
@@ -20,6 +21,10 @@ export const createSessionsSlice: StateCreator<
   setSessions: (sessions) => set(() => ({ sessions })),
   addSession: (session) =>
     set((state) => ({ sessions: [session, ...state.sessions] })),
+  removeSession: (id) =>
+    set((state) => ({
+      sessions: state.sessions.filter((session) => session.id !== id),
+    })),
   getSessionsByIntentId: (intentId) =>
     get().sessions.filter((session) => session.intent_id === intentId),
 });
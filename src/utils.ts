@@ -1,6 +1,7 @@
 import { Command } from "@tauri-apps/api/shell";
 import Color from "color";
 
+import { SettingsConfig } from "./bindings/SettingsConfig";
 import { Theme } from "./bindings/Theme";
 
 export function scale(
@@ -38,6 +39,17 @@ export function formatTime(minutes: number): string {
   return `${hours}h ${remainingMinutes}min`;
 }
 
+/**
+ * Returns the `YYYY-MM-DD` day a timestamp (in seconds) counts towards,
+ * honoring the configured timezone and day start hour.
+ */
+export const toDayKey = (timestamp: number, config?: SettingsConfig) => {
+  const dayStartHour = config?.day_start_hour ?? 0;
+  return new Intl.DateTimeFormat("en-CA", {
+    timeZone: config?.timezone ?? undefined,
+  }).format((timestamp - dayStartHour * 3600) * 1000);
+};
+
 export const applyTheme = (theme: Theme) => {
   document.documentElement.style.setProperty(
     "--window-color",
@@ -61,4 +73,27 @@ export const applyTheme = (theme: Theme) => {
   );
 };
 
+const INTENT_PALETTE = [
+  "#e06c75",
+  "#e5c07b",
+  "#98c379",
+  "#56b6c2",
+  "#61afef",
+  "#c678dd",
+  "#d19a66",
+  "#be5046",
+];
+
+/**
+ * Color of an intent or of a stats entry grouped by intent, intents without
+ * a color get one picked from a palette by id so it stays the same everywhere.
+ */
+export const intentColor = (intent: {
+  id: number | null;
+  color: string | null;
+}) => {
+  if (intent.color) return intent.color;
+  return INTENT_PALETTE[(intent.id ?? 0) % INTENT_PALETTE.length];
+};
+
 export * as default from "./utils";
@@ -4,7 +4,10 @@ import ReactTooltip from "react-tooltip";
 import Color from "color";
 
 import { Theme } from "@/bindings/Theme";
-import { Session } from "@/bindings/Session";
+import { HeatmapDay } from "@/bindings/HeatmapDay";
+import { SettingsConfig } from "@/bindings/SettingsConfig";
+import utils from "@/utils";
+import ipc from "@/ipc";
 
 export interface CalendarProps {
   theme: Theme;
@@ -67,69 +70,51 @@ export const Calendar: React.FC<CalendarProps> = (props) => {
 };
 
 export interface useCalendarProps {
-  sessions: Session[];
+  settings?: SettingsConfig;
+  /** Bumped to refetch the heatmap, e.g. when a session gets created. */
+  revision?: number;
 }
 
 export const useCalendar = (props: useCalendarProps) => {
-  const days = React.useMemo(() => {
-    const days: Map<string, Day> = new Map();
+  const [heatmap, setHeatmap] = React.useState<HeatmapDay[]>([]);
 
+  React.useEffect(() => {
     // sessions date range
     const dateRange = new Date();
     dateRange.setMonth(dateRange.getMonth() - 5);
 
-    // transform sessions to appropriate Date objects
-    for (let i = 0; i < props.sessions.length; i++) {
-      const timestamp = props.sessions[i].finished_at;
-
-      if (timestamp < Math.round(dateRange.getTime() / 1000)) continue;
-
-      const iso_date = new Date(timestamp * 1000).toISOString().split("T")[0];
-
-      const day = days.get(iso_date);
-      if (day) {
-        day.count += props.sessions[i].duration / 60;
-      } else {
-        days.set(iso_date, {
-          count: props.sessions[i].duration / 60,
-          date: iso_date,
-          level: 1,
-        });
-      }
-    }
-
-    // dummy objects needed in order to render graph properly
-    const range_iso = dateRange.toISOString().split("T")[0];
-    if (!days.has(range_iso)) {
-      days.set(range_iso, { date: range_iso, count: 0, level: 0 });
-    }
-    const today_iso = new Date().toISOString().split("T")[0];
-    if (!days.has(today_iso)) {
-      days.set(today_iso, { date: today_iso, count: 0, level: 0 });
-    }
-
-    const days_array = Array.from(days.values());
+    const from = utils.toDayKey(
+      Math.round(dateRange.getTime() / 1000),
+      props.settings
+    );
+    const to = utils.toDayKey(Math.round(Date.now() / 1000), props.settings);
+    ipc.getHeatmap(from, to).then((data) => setHeatmap(data));
+  }, [props.settings, props.revision]);
 
+  const days = React.useMemo(() => {
     // level assigment based on hours spent
     // "count" equals "hours" in this case
-    for (let i = 0; i < days_array.length; i++) {
-      if (days_array[i].count >= 8) {
-        days_array[i].level = 4;
-      } else if (days_array[i].count >= 6) {
-        days_array[i].level = 3;
-      } else if (days_array[i].count >= 3) {
-        days_array[i].level = 2;
-      } else if (days_array[i].count > 0) {
-        days_array[i].level = 1;
-      } else {
-        days_array[i].level = 0;
+    return heatmap.map((heatmapDay): Day => {
+      const count = heatmapDay.duration / 3600;
+      let level: Day["level"] = 0;
+      if (count >= 8) {
+        level = 4;
+      } else if (count >= 6) {
+        level = 3;
+      } else if (count >= 3) {
+        level = 2;
+      } else if (count > 0) {
+        level = 1;
       }
 
-      // needed for tooltip render
-      days_array[i].count = parseFloat(days_array[i].count.toFixed(2));
-    }
-    return days_array;
-  }, [props.sessions]);
+      // rounding needed for tooltip render
+      return {
+        date: heatmapDay.day,
+        count: parseFloat(count.toFixed(2)),
+        level,
+      };
+    });
+  }, [heatmap]);
 
   return { days };
 };
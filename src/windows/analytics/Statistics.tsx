@@ -4,58 +4,32 @@ import { IoMdTime } from "react-icons/io";
 import { MdToday } from "react-icons/md";
 
 import utils from "@/utils";
-import { Intent } from "@/bindings/Intent";
-import { Session } from "@/bindings/Session";
+import { SettingsConfig } from "@/bindings/SettingsConfig";
+import { Stats } from "@/bindings/Stats";
+import { Streaks } from "@/bindings/Streaks";
 import { IconType } from "react-icons";
 
 export interface StatisticsProps {
-  intents: Intent[];
-  sessions: Session[];
+  /** Focus stats grouped by day. */
+  stats?: Stats;
+  streaks?: Streaks;
+  settings?: SettingsConfig;
 }
 
 export const Statistics: React.FC<StatisticsProps> = (props) => {
-  const today = new Date();
+  const now = Math.round(Date.now() / 1000);
+  const today = utils.toDayKey(now, props.settings);
 
-  const totalFocused = React.useMemo(
-    () => props.sessions.reduce((p, c) => p + c.duration, 0) / 60,
-    [props.sessions]
-  );
+  const totalFocused = (props.stats?.total_duration ?? 0) / 60;
 
   const focusedToday = React.useMemo(
     () =>
-      props.sessions
-        .filter(
-          (s) =>
-            new Date(s.finished_at * 1000).toDateString() ==
-            today.toDateString()
-        )
-        .reduce((p, c) => p + c.duration, 0) / 60,
-    [props.sessions]
+      (props.stats?.entries.find((entry) => entry.key === today)
+        ?.total_duration ?? 0) / 60,
+    [props.stats, today]
   );
 
-  const dayStreak = React.useMemo(() => {
-    let dayStreak = 1;
-
-    const sorted = props.sessions.sort((a, b) => b.finished_at - a.finished_at);
-    let prevDay = new Date();
-    // there might be timezone related issues
-    for (let i = 0; i < sorted.length; i++) {
-      const s = sorted[i];
-      const date = new Date(s.finished_at * 1000);
-
-      if (date.toDateString() === prevDay.toDateString()) continue;
-      date.setDate(date.getDate() + 1);
-
-      if (date.toDateString() === prevDay.toDateString()) {
-        dayStreak += 1;
-        prevDay = new Date(s.finished_at * 1000);
-      } else {
-        break;
-      }
-    }
-
-    return dayStreak;
-  }, [props.sessions]);
+  const dayStreak = props.streaks?.current ?? 0;
 
   return (
     <div className="grow flex flex-col justify-evenly">
@@ -5,18 +5,45 @@ import { WindowContainer } from "@/components";
 import { Card, Pane, Titlebar } from "@/ui";
 import useStore from "@/store";
 import ipc from "@/ipc";
+import { Stats } from "@/bindings/Stats";
+import { Streaks } from "@/bindings/Streaks";
 
 import { Statistics } from "./Statistics";
 import { Calendar, useCalendar } from "./Calendar";
 import { useEvents } from "@/hooks";
 
 const AnalyticsWindow: React.FC = () => {
+  const [stats, setStats] = React.useState<Stats>();
+  const [streaks, setStreaks] = React.useState<Streaks>();
+  const [revision, setRevision] = React.useState(0);
   const store = useStore();
-  const calendar = useCalendar({ sessions: store.sessions });
+  const calendar = useCalendar({ settings: store.settingsConfig, revision });
+
+  const refreshStats = () => {
+    ipc
+      .getStats({ group_by: "Day", from: null, to: null, include_manual: null })
+      .then((data) => setStats(data));
+    ipc.getStreaks().then((data) => setStreaks(data));
+    setRevision((revision) => revision + 1);
+  };
 
   useEvents({
     session_created: ({ data: id }) => {
-      ipc.getSession(id).then((data) => store.addSession(data));
+      ipc.getSession(id).then((data) => {
+        if (data.session_type === "Focus") store.addSession(data);
+      });
+      refreshStats();
+    },
+    session_updated: () => refreshStats(),
+    session_deleted: ({ data: id }) => {
+      store.removeSession(id);
+      refreshStats();
+    },
+    rest_day_created: () => refreshStats(),
+    rest_day_deleted: () => refreshStats(),
+    history_imported: () => {
+      ipc.getSessions().then((data) => store.setSessions(data));
+      refreshStats();
     },
   });
 
@@ -26,13 +53,21 @@ const AnalyticsWindow: React.FC = () => {
     });
   }, []);
 
+  React.useEffect(() => {
+    refreshStats();
+  }, [store.settingsConfig, store.timerConfig]);
+
   return (
     <WindowContainer>
       <div className="grow flex flex-col gap-0.5">
         <Titlebar title="Analytics" icon={MdAnalytics} />
 
         <Pane className="grow flex flex-col" padding="lg">
-          <Statistics intents={store.intents} sessions={store.sessions} />
+          <Statistics
+            stats={stats}
+            streaks={streaks}
+            settings={store.settingsConfig}
+          />
           <Card className="border-2 bg-darker/10 hover:bg-darker/20 border-primary/60 hover:border-primary/80">
             <Calendar days={calendar.days} theme={store.currentTheme!} />
           </Card>
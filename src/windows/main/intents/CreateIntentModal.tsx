@@ -5,7 +5,7 @@ import { toast } from "react-hot-toast";
 import { ScrollArea } from "@mantine/core";
 
 import { Button, IconView, Input, Modal } from "@/ui";
-import { useTags } from "@/hooks";
+import { useIntents, useTags } from "@/hooks";
 import { TagView } from "@/components";
 import { Tag } from "@/bindings/Tag";
 import { CreateIntent } from "@/bindings/CreateIntent";
@@ -21,6 +21,7 @@ export const CreateIntentModal: React.FC<CreateIntentProps> = (props) => {
   const [viewAddTags, setViewAddTags] = React.useState(false);
   const [tags, setTags] = React.useState<Tag[]>([]);
 
+  const intents = useIntents();
   const { register, handleSubmit, setValue } = useForm<CreateIntent>();
 
   const onSubmit = handleSubmit((data) => {
@@ -39,6 +40,7 @@ export const CreateIntentModal: React.FC<CreateIntentProps> = (props) => {
   React.useEffect(() => {
     if (props.display) return;
     setValue("label", "");
+    setValue("parent_id", null);
     setTags([]);
   }, [props.display]);
 
@@ -61,6 +63,21 @@ export const CreateIntentModal: React.FC<CreateIntentProps> = (props) => {
               maxLength: 24,
             })}
           />
+          <select
+            className="w-full h-8 py-1 px-1 bg-primary/10 rounded-sm border-2 outline-none border-primary/20 focus:border-primary/50 text-text/80"
+            {...register("parent_id", {
+              setValueAs: (value) => (value ? Number(value) : null),
+            })}
+          >
+            <option value="">No parent</option>
+            {intents.data
+              .filter((intent) => !intent.archived_at)
+              .map((intent) => (
+                <option key={intent.id} value={intent.id}>
+                  {intent.label}
+                </option>
+              ))}
+          </select>
           <div className="flex flex-row items-center justify-between">
             <span className="text-text/60 font-semibold">Tags</span>
             <Button
@@ -1,15 +1,25 @@
 import React from "react";
 import { clsx, ScrollArea } from "@mantine/core";
-import { MdDangerous, MdDelete, MdEdit, MdSettings } from "react-icons/md";
+import {
+  MdArrowDownward,
+  MdArrowUpward,
+  MdDangerous,
+  MdDelete,
+  MdEdit,
+  MdSettings,
+} from "react-icons/md";
 import { RiArchiveFill, RiArchiveLine } from "react-icons/ri";
 import { toast } from "react-hot-toast";
 
 import { ModelId } from "@/types";
 import { Button, IconView, Input, Modal } from "@/ui";
 import ipc from "@/ipc";
+import utils from "@/utils";
 import { TagView } from "@/components";
 import { useIntent, useTags } from "@/hooks";
 import { UpdateIntent } from "@/bindings/UpdateIntent";
+import { SetIntentDetails } from "@/bindings/SetIntentDetails";
+import { SetIntentTarget } from "@/bindings/SetIntentTarget";
 import { Intent } from "@/bindings/Intent";
 
 interface IntentConfigModalProps {
@@ -19,6 +29,8 @@ interface IntentConfigModalProps {
 
 export const IntentConfigModal: React.FC<IntentConfigModalProps> = (props) => {
   const [label, setLabel] = React.useState("");
+  const [icon, setIcon] = React.useState("");
+  const [description, setDescription] = React.useState("");
   const [viewEditTags, setViewEditTags] = React.useState(false);
   const [viewDeleteModal, setViewDeleteModal] = React.useState(false);
 
@@ -29,15 +41,49 @@ export const IntentConfigModal: React.FC<IntentConfigModalProps> = (props) => {
     ipc.updateIntent(props.intentId, data);
   };
 
+  const setDetails = async (data: Partial<SetIntentDetails>) => {
+    if (!props.intentId || !intent.data) return;
+    const { color, icon, description } = intent.data;
+    ipc.setIntentDetails(props.intentId, {
+      color,
+      icon,
+      description,
+      ...data,
+    });
+  };
+
+  const moveIntent = async (offset: number) => {
+    if (!props.intentId || !intent.data) return;
+    const position = Math.max(0, intent.data.position + offset);
+    ipc.moveIntent(props.intentId, position);
+  };
+
+  const setTarget = async (data: Partial<SetIntentTarget>) => {
+    if (!props.intentId || !intent.data) return;
+    const { target_period, target, budget } = intent.data;
+    ipc
+      .setIntentTarget(props.intentId, {
+        target_period,
+        target,
+        budget,
+        ...data,
+      })
+      .catch(() => toast("Target and budget must be positive"));
+  };
+
   React.useEffect(() => {
     if (!intent.data) return;
     setLabel(intent.data.label!);
+    setIcon(intent.data.icon ?? "");
+    setDescription(intent.data.description ?? "");
   }, [intent.data]);
 
   // Clean up
   React.useEffect(() => {
     if (!props.intentId) {
       setLabel("");
+      setIcon("");
+      setDescription("");
       return;
     }
   }, [props.intentId]);
@@ -65,6 +111,75 @@ export const IntentConfigModal: React.FC<IntentConfigModalProps> = (props) => {
             maxLength={24}
           />
         </div>
+        {/* Appearance */}
+        <div className="flex flex-row items-center gap-1">
+          <input
+            type="color"
+            value={intent.data ? utils.intentColor(intent.data) : "#000000"}
+            onChange={(e) => setDetails({ color: e.currentTarget.value })}
+            className="w-8 h-8 shrink-0 bg-transparent cursor-pointer"
+          />
+          <Input
+            value={icon}
+            onChange={(e) => setIcon(e.currentTarget.value)}
+            onBlur={() => setDetails({ icon: icon || null })}
+            placeholder="Icon"
+            maxLength={8}
+          />
+        </div>
+        {/* Description */}
+        <div className="flex flex-col gap-1">
+          <span className="text-text/80">Description</span>
+          <textarea
+            value={description}
+            onChange={(e) => setDescription(e.currentTarget.value)}
+            onBlur={() => setDetails({ description: description || null })}
+            placeholder="Markdown supported"
+            rows={3}
+            className="w-full py-1 px-1 bg-primary/10 rounded-sm border-2 outline-none border-primary/20 focus:border-primary/50 placeholder:text-text/60 resize-none"
+          />
+        </div>
+        {/* Target */}
+        <div className="flex flex-col gap-1">
+          <div className="flex flex-row items-center justify-between">
+            <span className="text-text/80">Target</span>
+            <select
+              value={intent.data?.target_period ?? "Weekly"}
+              onChange={(e) =>
+                setTarget({
+                  target_period: e.currentTarget
+                    .value as SetIntentTarget["target_period"],
+                })
+              }
+              className="h-8 px-1 bg-primary/10 rounded-sm border-2 outline-none border-primary/20 focus:border-primary/50 text-text/80"
+            >
+              <option value="Weekly">Weekly</option>
+              <option value="Total">Total</option>
+            </select>
+          </div>
+          <div className="flex flex-row gap-1">
+            <Input
+              type="number"
+              min={1}
+              placeholder="Target (min)"
+              defaultValue={intent.data?.target ?? ""}
+              key={`target-${intent.data?.target}`}
+              onBlur={(e) =>
+                setTarget({ target: parseMinutes(e.currentTarget.value) })
+              }
+            />
+            <Input
+              type="number"
+              min={1}
+              placeholder="Budget (min)"
+              defaultValue={intent.data?.budget ?? ""}
+              key={`budget-${intent.data?.budget}`}
+              onBlur={(e) =>
+                setTarget({ budget: parseMinutes(e.currentTarget.value) })
+              }
+            />
+          </div>
+        </div>
         {/* Tags */}
         <div className="flex flex-col gap-1">
           <div className="flex flex-row items-center justify-between">
@@ -91,6 +206,12 @@ export const IntentConfigModal: React.FC<IntentConfigModalProps> = (props) => {
           <span className="text-text/80">Manage</span>
           {/* Button Bar */}
           <div className="flex flex-row items-center gap-1">
+            <Button variant="ghost" onClick={() => moveIntent(-1)}>
+              <IconView icon={MdArrowUpward} />
+            </Button>
+            <Button variant="ghost" onClick={() => moveIntent(1)}>
+              <IconView icon={MdArrowDownward} />
+            </Button>
             <Button
               variant="ghost"
               onClick={() =>
@@ -142,6 +263,9 @@ export const IntentConfigModal: React.FC<IntentConfigModalProps> = (props) => {
   );
 };
 
+/** Empty input clears the target or budget. */
+const parseMinutes = (value: string) => (value ? Number(value) : null);
+
 interface DeleteIntentProps {
   intent: Intent;
   onDelete: (id: number) => void;
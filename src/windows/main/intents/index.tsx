@@ -7,6 +7,7 @@ import { Button, IconView, ScrollArea } from "@/ui";
 import { useIntent, useIntents, useTimer } from "@/hooks";
 import { ModelId } from "@/types";
 import { Intent } from "@/bindings/Intent";
+import utils from "@/utils";
 
 import { CreateIntentModal } from "./CreateIntentModal";
 import { TagsModal } from "./TagsModal";
@@ -92,22 +93,55 @@ interface IntentsListProps {
   onSelected: (intent: Intent) => void;
 }
 
+/**
+ * Orders intents depth first so that children follow their parent,
+ * siblings are sorted pinned first, then by position.
+ */
+const flattenTree = (intents: Intent[]) => {
+  const ids = new Set(intents.map((intent) => intent.id));
+  const result: { intent: Intent; depth: number }[] = [];
+
+  const visit = (parentId: number | null, depth: number) =>
+    intents
+      .filter((intent) =>
+        parentId === null
+          ? intent.parent_id === null || !ids.has(intent.parent_id)
+          : intent.parent_id === parentId
+      )
+      .sort(
+        (a, b) =>
+          Number(b.pinned) - Number(a.pinned) ||
+          a.position - b.position ||
+          a.id - b.id
+      )
+      .forEach((intent) => {
+        result.push({ intent, depth });
+        visit(intent.id, depth + 1);
+      });
+
+  visit(null, 0);
+  return result;
+};
+
 const IntentsList: React.FC<IntentsListProps> = (props) => {
   // TODO: Sort intents by total session hours
+  const intents = flattenTree(
+    props.data.filter((intent) => !!intent.archived_at === props.viewArchive)
+  );
+
   return (
     <ScrollArea>
       <div className="flex flex-col gap-0.5">
-        {props.data
-          .filter((intent) => !!intent.archived_at === props.viewArchive)
-          .map((intent) => (
-            <IntentView
-              key={intent.id}
-              id={intent.id}
-              onConfigure={props.onConfigureIntent}
-              onClick={() => props.onSelected(intent)}
-              selected={props.selected?.id === intent.id}
-            />
-          ))}
+        {intents.map(({ intent, depth }) => (
+          <IntentView
+            key={intent.id}
+            id={intent.id}
+            depth={depth}
+            onConfigure={props.onConfigureIntent}
+            onClick={() => props.onSelected(intent)}
+            selected={props.selected?.id === intent.id}
+          />
+        ))}
       </div>
     </ScrollArea>
   );
@@ -118,6 +152,7 @@ interface IntentViewProps {
   onConfigure: (id: ModelId) => void;
   onClick: () => void;
   selected: boolean;
+  depth: number;
 }
 
 const IntentView: React.FC<IntentViewProps> = (props) => {
@@ -133,6 +168,10 @@ const IntentView: React.FC<IntentViewProps> = (props) => {
           ? "bg-primary/10 hover:bg-primary/20 border-b-2 border-primary/80"
           : "bg-base/10 hover:bg-primary/10 active:bg-primary/20 border-b-2 border-transparent"
       )}
+      style={{
+        marginLeft: `${props.depth}rem`,
+        borderLeft: `4px solid ${utils.intentColor(intent.data)}`,
+      }}
       // @ts-ignore
       onClick={(e) => !e.target.closest("button") && props.onClick()}
       data-tauri-disable-drag
@@ -148,7 +187,11 @@ const IntentView: React.FC<IntentViewProps> = (props) => {
               : "text-text/70 group-hover:text-text"
           )}
         >
-          <IconView icon={BiTargetLock} />
+          {intent.data.icon ? (
+            <span>{intent.data.icon}</span>
+          ) : (
+            <IconView icon={BiTargetLock} />
+          )}
           <span className="font-bold">{intent.data.label}</span>
         </div>
         <div className="flex flex-row gap-1 opacity-0 group-hover:opacity-100 transition-opacity duration-300">
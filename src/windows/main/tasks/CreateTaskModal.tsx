@@ -4,21 +4,38 @@ import { toast } from "react-hot-toast";
 
 import ipc from "@/ipc";
 import { Button, Input, Modal } from "@/ui";
+import { TaskPriority } from "@/bindings/TaskPriority";
+
+interface CreateTaskForm {
+  body: string;
+  priority: TaskPriority;
+  due_date: string | null;
+}
 
 interface CreateTaskModalProps {
   display: boolean;
   intentId: number;
+  /** Creates a subtask of the given task. */
+  parentTaskId?: number;
   onExit: () => void;
 }
 
 export const CreateTaskModal: React.FC<CreateTaskModalProps> = (props) => {
-  const { register, handleSubmit } = useForm<{ body: string }>();
+  const { register, handleSubmit } = useForm<CreateTaskForm>({
+    defaultValues: { priority: "Normal", due_date: null },
+  });
 
   const onSubmit = handleSubmit((data) => {
-    ipc.createTask({ ...data, intent_id: props.intentId }).then(() => {
-      toast("Task created");
-      props.onExit();
-    });
+    ipc
+      .createTask({
+        ...data,
+        intent_id: props.intentId,
+        parent_task_id: props.parentTaskId ?? null,
+      })
+      .then(() => {
+        toast("Task created");
+        props.onExit();
+      });
   });
 
   const maxLength = 64;
@@ -27,13 +44,29 @@ export const CreateTaskModal: React.FC<CreateTaskModalProps> = (props) => {
     <Modal
       display={props.display}
       onExit={props.onExit}
-      header={{ label: "Create task" }}
+      header={{ label: props.parentTaskId ? "Create subtask" : "Create task" }}
     >
       <form onSubmit={onSubmit} className="flex flex-col gap-2">
         <Input
           maxLength={maxLength}
           {...register("body", { required: true, minLength: 1, maxLength })}
         />
+        <div className="flex flex-row gap-2">
+          <select
+            className="h-8 px-1 bg-primary/10 rounded-sm border-2 outline-none border-primary/20 focus:border-primary/50 text-text/80"
+            {...register("priority")}
+          >
+            <option value="Low">Low</option>
+            <option value="Normal">Normal</option>
+            <option value="High">High</option>
+          </select>
+          <Input
+            type="date"
+            {...register("due_date", {
+              setValueAs: (value) => value || null,
+            })}
+          />
+        </div>
         <Button variant="base" className="w-full" type="submit">
           Save
         </Button>
@@ -1,11 +1,42 @@
 import React from "react";
 import { useForm } from "react-hook-form";
 import { toast } from "react-hot-toast";
+import { MdArrowDownward, MdArrowUpward } from "react-icons/md";
 
-import { Input, Modal, DangerButton } from "@/ui";
+import { Button, IconView, Input, Modal, DangerButton } from "@/ui";
 import { Task } from "@/bindings/Task";
+import { TaskPriority } from "@/bindings/TaskPriority";
+import { TaskEstimate } from "@/bindings/TaskEstimate";
+import { TaskRecurrence } from "@/bindings/TaskRecurrence";
 import ipc from "@/ipc";
-import { useConfirmDelete } from "@/hooks";
+import { useConfirmDelete, useEvents, useIntents } from "@/hooks";
+
+type RecurrenceKind = "None" | "Daily" | "Weekdays" | "Weekly" | "Monthly";
+
+const recurrenceKind = (recurrence: TaskRecurrence | null): RecurrenceKind => {
+  if (recurrence === null) return "None";
+  if (typeof recurrence === "string") return recurrence;
+  return "Weekly" in recurrence ? "Weekly" : "Monthly";
+};
+
+/** Weekly and monthly rules repeat on the day of the due date, or today. */
+const toRecurrence = (
+  kind: RecurrenceKind,
+  dueDate: string | null
+): TaskRecurrence | null => {
+  const [year, month, day] = (dueDate ?? "").split("-").map(Number);
+  const date = dueDate ? new Date(year, month - 1, day) : new Date();
+  switch (kind) {
+    case "None":
+      return null;
+    case "Weekly":
+      return { Weekly: [(date.getDay() + 6) % 7] };
+    case "Monthly":
+      return { Monthly: date.getDate() };
+    default:
+      return kind;
+  }
+};
 
 interface TaskModalProps {
   data: Task;
@@ -31,10 +62,35 @@ export const TaskModal: React.FC<TaskModalProps> = (props) => {
 
   const maxLength = 64;
 
+  const [estimate, setEstimate] = React.useState<TaskEstimate>();
+  const [completions, setCompletions] = React.useState(0);
+  const intents = useIntents();
+
+  const fetchEstimate = () => {
+    ipc.getTaskEstimate(props.data.id).then((data) => setEstimate(data));
+  };
+
   React.useEffect(() => {
     setValue("body", props.data.body);
   }, []);
 
+  React.useEffect(() => {
+    if (props.display) fetchEstimate();
+  }, [props.display, props.data.estimated_pomodoros]);
+
+  React.useEffect(() => {
+    if (!props.display) return;
+    ipc
+      .getTaskHistory(props.data.id)
+      .then((data) => setCompletions(data.length));
+  }, [props.display, props.data.series_id]);
+
+  useEvents({
+    session_created: () => {
+      if (props.display) fetchEstimate();
+    },
+  });
+
   return (
     <Modal
       display={props.display}
@@ -49,7 +105,136 @@ export const TaskModal: React.FC<TaskModalProps> = (props) => {
             {...register("body", { required: true, minLength: 1, maxLength })}
           />
         </form>
-        <div className="flex flex-row items-center justify-end">
+        <div className="flex flex-row gap-2">
+          <select
+            value={props.data.priority}
+            onChange={(e) =>
+              ipc.updateTask(props.data.id, {
+                priority: e.currentTarget.value as TaskPriority,
+              })
+            }
+            className="h-8 px-1 bg-primary/10 rounded-sm border-2 outline-none border-primary/20 focus:border-primary/50 text-text/80"
+          >
+            <option value="Low">Low</option>
+            <option value="Normal">Normal</option>
+            <option value="High">High</option>
+          </select>
+          <Input
+            type="date"
+            value={props.data.due_date ?? ""}
+            onChange={(e) =>
+              ipc.setTaskDueDate(props.data.id, e.currentTarget.value || null)
+            }
+          />
+        </div>
+        <div className="flex flex-row items-center gap-2">
+          <select
+            value={recurrenceKind(props.data.recurrence)}
+            onChange={(e) =>
+              ipc.setTaskRecurrence(
+                props.data.id,
+                toRecurrence(
+                  e.currentTarget.value as RecurrenceKind,
+                  props.data.due_date
+                )
+              )
+            }
+            className="h-8 px-1 bg-primary/10 rounded-sm border-2 outline-none border-primary/20 focus:border-primary/50 text-text/80"
+          >
+            <option value="None">Does not repeat</option>
+            <option value="Daily">Daily</option>
+            <option value="Weekdays">Weekdays</option>
+            <option value="Weekly">Weekly</option>
+            <option value="Monthly">Monthly</option>
+          </select>
+          {completions > 0 && (
+            <span className="text-sm text-text/60">
+              Completed {completions} {completions === 1 ? "time" : "times"}
+            </span>
+          )}
+        </div>
+        <div className="flex flex-row items-center gap-2">
+          <select
+            value={props.data.intent_id}
+            onChange={(e) =>
+              ipc
+                .updateTask(props.data.id, {
+                  intent_id: Number(e.currentTarget.value),
+                })
+                .then(() => {
+                  toast("Task moved");
+                  props.onExit();
+                })
+            }
+            className="grow h-8 px-1 bg-primary/10 rounded-sm border-2 outline-none border-primary/20 focus:border-primary/50 text-text/80"
+          >
+            {intents.data
+              .filter(
+                (intent) =>
+                  !intent.archived_at || intent.id === props.data.intent_id
+              )
+              .map((intent) => (
+                <option key={intent.id} value={intent.id}>
+                  {intent.label}
+                </option>
+              ))}
+          </select>
+          <Button
+            variant="ghost"
+            onClick={() =>
+              ipc
+                .copyTask(props.data.id, props.data.intent_id)
+                .then(() => toast("Task copied"))
+            }
+          >
+            Copy
+          </Button>
+        </div>
+        <div className="flex flex-row items-center gap-2">
+          <Input
+            type="number"
+            min={1}
+            placeholder="Estimated pomodoros"
+            value={props.data.estimated_pomodoros ?? ""}
+            onChange={(e) => {
+              const value = parseInt(e.currentTarget.value);
+              ipc.setTaskEstimate(props.data.id, value > 0 ? value : null);
+            }}
+          />
+          {estimate && (
+            <span className="shrink-0 text-sm text-text/60">
+              {estimate.actual_pomodoros}/{estimate.estimated_pomodoros ?? "-"}{" "}
+              ({estimate.actual_minutes}m)
+            </span>
+          )}
+          <Button
+            variant="ghost"
+            onClick={() =>
+              ipc
+                .timerSetSessionTask(props.data.id)
+                .then(() => toast("Sessions will count towards this task"))
+            }
+          >
+            Focus
+          </Button>
+        </div>
+        <div className="flex flex-row items-center justify-between">
+          <div className="flex flex-row items-center gap-1">
+            <Button
+              variant="ghost"
+              onClick={() =>
+                ipc.moveTask(props.data.id, Math.max(0, props.data.position - 1))
+              }
+            >
+              <IconView icon={MdArrowUpward} />
+            </Button>
+            <Button
+              variant="ghost"
+              onClick={() => ipc.moveTask(props.data.id, props.data.position + 1)}
+            >
+              <IconView icon={MdArrowDownward} />
+            </Button>
+          </div>
           <DangerButton variant="base" onClick={onDelete}>
             {viewConfirmDelete ? "Confirm" : "Delete"}
           </DangerButton>
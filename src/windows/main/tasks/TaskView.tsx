@@ -1,24 +1,34 @@
 import React from "react";
 import { toast } from "react-hot-toast";
 import { motion } from "framer-motion";
+import { MdAddCircle } from "react-icons/md";
 
 import ipc from "@/ipc";
+import { Button, IconView } from "@/ui";
 import { Task } from "@/bindings/Task";
+import { TaskProgress } from "@/bindings/TaskProgress";
 
 import { TaskButton } from "./TaskButton";
 import { TaskModal } from "./TaskModal";
+import { CreateTaskModal } from "./CreateTaskModal";
 
 interface TaskViewProps {
   data: Task;
+  depth?: number;
+  progress?: TaskProgress;
 }
 
 const TaskView = React.forwardRef<HTMLDivElement, TaskViewProps>(
   (props, ref) => {
     const [viewModal, setViewModal] = React.useState(false);
+    const [viewCreateSubtask, setViewCreateSubtask] = React.useState(false);
 
     const handleCheck = React.useCallback(() => {
       if (!props.data.completed) {
-        ipc.completeTask(props.data.id).then(() => toast("Task completed"));
+        // Checking off a task checks off its remaining steps too
+        ipc
+          .completeTask(props.data.id, true)
+          .then(() => toast("Task completed"));
       } else {
         ipc.uncompleteTask(props.data.id);
       }
@@ -35,7 +45,8 @@ const TaskView = React.forwardRef<HTMLDivElement, TaskViewProps>(
           exit={{ opacity: 0, scale: 0.9 }}
         >
           <div
-            className="p-0.5 bg-base/5 hover:bg-primary/10 border border-base/5 cursor-pointer"
+            className="group p-0.5 bg-base/5 hover:bg-primary/10 border border-base/5 cursor-pointer"
+            style={{ marginLeft: `${props.depth ?? 0}rem` }}
             onClick={(e) => {
               // @ts-ignore
               if (e.target.closest("button")) return;
@@ -49,8 +60,26 @@ const TaskView = React.forwardRef<HTMLDivElement, TaskViewProps>(
                 onValueChange={() => handleCheck()}
               />
               <div className="pt-0.5" style={{ wordBreak: "break-all" }}>
+                {props.data.priority === "High" && (
+                  <span className="text-danger/80 font-bold">! </span>
+                )}
                 {props.data.body}
               </div>
+              <div className="ml-auto pt-0.5 shrink-0 flex flex-row items-center gap-1 text-sm text-text/60">
+                {props.progress && (
+                  <span>
+                    {props.progress.completed}/{props.progress.total}
+                  </span>
+                )}
+                {props.data.due_date && <span>{props.data.due_date}</span>}
+                <Button
+                  onClick={() => setViewCreateSubtask(true)}
+                  variant="ghost"
+                  className="opacity-0 group-hover:opacity-100"
+                >
+                  <IconView icon={MdAddCircle} />
+                </Button>
+              </div>
             </div>
           </div>
         </motion.div>
@@ -60,6 +89,12 @@ const TaskView = React.forwardRef<HTMLDivElement, TaskViewProps>(
           display={viewModal}
           onExit={() => setViewModal(false)}
         />
+        <CreateTaskModal
+          display={viewCreateSubtask}
+          intentId={props.data.intent_id}
+          parentTaskId={props.data.id}
+          onExit={() => setViewCreateSubtask(false)}
+        />
       </>
     );
   }
@@ -3,11 +3,14 @@ import {
   MdAddCircle,
   MdCheckBox,
   MdCheckBoxOutlineBlank,
+  MdDeleteSweep,
 } from "react-icons/md";
+import { toast } from "react-hot-toast";
 
 import { Button, IconView, Tooltip } from "@/ui";
 import ipc from "@/ipc";
 import { TimerSession } from "@/bindings/TimerSession";
+import { Task } from "@/bindings/Task";
 
 import { CreateTaskModal } from "./CreateTaskModal";
 import TaskView from "./TaskView";
@@ -38,6 +41,20 @@ export const TasksView: React.FC = () => {
               </Button>
             </Tooltip>
           )}
+          {session && viewCompleted && (
+            <Tooltip label="Clear completed older than a week">
+              <Button
+                onClick={() =>
+                  ipc
+                    .clearCompletedTasks(7, session.intent.id)
+                    .then((ids) => toast(`Cleared ${ids.length} tasks`))
+                }
+                variant="ghost"
+              >
+                <IconView icon={MdDeleteSweep} />
+              </Button>
+            </Tooltip>
+          )}
           <Tooltip label={viewCompleted ? "View tasks" : "View completed"}>
             <Button
               onClick={() => setViewCompleted((prev) => !prev)}
@@ -78,13 +95,39 @@ interface TasksListProps {
   viewCompleted: boolean;
 }
 
+/** Orders tasks depth first so that subtasks follow their parent. */
+const flattenTree = (tasks: Task[]) => {
+  const ids = new Set(tasks.map((task) => task.id));
+  const result: { task: Task; depth: number }[] = [];
+
+  const visit = (parentId: number | null, depth: number) =>
+    tasks
+      .filter((task) =>
+        parentId === null
+          ? task.parent_task_id === null || !ids.has(task.parent_task_id)
+          : task.parent_task_id === parentId
+      )
+      .forEach((task) => {
+        result.push({ task, depth });
+        visit(task.id, depth + 1);
+      });
+
+  visit(null, 0);
+  return result;
+};
+
 const TasksList: React.FC<TasksListProps> = (props) => {
   const tasks = useIntentTasks(props.intentId, props.viewCompleted);
 
   return (
     <div className="grow flex flex-col gap-0.5">
-      {tasks.data.map((task) => (
-        <TaskView key={task.id} data={task} />
+      {flattenTree(tasks.data).map(({ task, depth }) => (
+        <TaskView
+          key={task.id}
+          data={task}
+          depth={depth}
+          progress={tasks.progress.find((p) => p.task_id === task.id)}
+        />
       ))}
     </div>
   );
@@ -1,8 +1,10 @@
 import React from "react";
 import { enable, isEnabled, disable } from "tauri-plugin-autostart-api";
 import { toast } from "react-hot-toast";
+import { ask, open, save } from "@tauri-apps/api/dialog";
 
 import {
+  Button,
   Card,
   CheckBox,
   Pane,
@@ -10,9 +12,24 @@ import {
   SectionsWrapper,
   ScrollArea,
 } from "@/ui";
+import { SliderCard } from "@/components";
 import ipc from "@/ipc";
+import { ColumnMapping } from "@/bindings/ColumnMapping";
+import { ImportFormat } from "@/bindings/ImportFormat";
 import useStore from "@/store";
 
+/** Column names expected in generic CSV and JSON files. */
+const DEFAULT_COLUMN_MAPPING: ColumnMapping = {
+  intent: "intent",
+  started_at: "started_at",
+  finished_at: "finished_at",
+  duration: "duration",
+  summary: "summary",
+  tags: "tags",
+  task: "task",
+  datetime_format: null,
+};
+
 const GeneralPane: React.FC = () => {
   const [isAutoStart, setIsAutoStart] = React.useState<boolean>();
 
@@ -22,6 +39,102 @@ const GeneralPane: React.FC = () => {
     isEnabled().then((value) => setIsAutoStart(value));
   }, []);
 
+  const exportCsv = async () => {
+    const dir = await open({ directory: true });
+    if (typeof dir !== "string") return;
+
+    ipc
+      .exportCsv(dir, { from: null, to: null, intent_id: null })
+      .then(() => toast("Data exported", { duration: 2000 }))
+      .catch(() => toast("Export failed", { duration: 2000 }));
+  };
+
+  const exportIcs = async () => {
+    const path = await save({
+      defaultPath: "intentio.ics",
+      filters: [{ name: "iCalendar", extensions: ["ics"] }],
+    });
+    if (!path) return;
+
+    ipc
+      .exportIcs(path, { from: null, to: null, intent_id: null })
+      .then(() => toast("Calendar exported", { duration: 2000 }))
+      .catch(() => toast("Export failed", { duration: 2000 }));
+  };
+
+  const toggleIcsFeed = async () => {
+    if (store.settingsConfig?.ics_feed_dir) {
+      ipc.setIcsFeedDir(null);
+      return;
+    }
+    const dir = await open({ directory: true });
+    if (typeof dir !== "string") return;
+
+    ipc
+      .setIcsFeedDir(dir)
+      .catch(() => toast("Calendar feed failed", { duration: 2000 }));
+  };
+
+  const exportBackup = async () => {
+    const path = await save({
+      defaultPath: "intentio-backup.json",
+      filters: [{ name: "JSON", extensions: ["json"] }],
+    });
+    if (!path) return;
+
+    ipc
+      .exportBackup(path)
+      .then(() => toast("Backup created", { duration: 2000 }))
+      .catch(() => toast("Backup failed", { duration: 2000 }));
+  };
+
+  const importBackup = async () => {
+    const path = await open({
+      filters: [{ name: "JSON", extensions: ["json"] }],
+    });
+    if (typeof path !== "string") return;
+
+    ipc
+      .importBackup(path)
+      .catch(() => toast("Backup could not be restored", { duration: 2000 }));
+  };
+
+  const importHistory = async (toggl: boolean) => {
+    const path = await open({
+      filters: [
+        { name: "CSV/JSON", extensions: toggl ? ["csv"] : ["csv", "json"] },
+      ],
+    });
+    if (typeof path !== "string") return;
+
+    let format: ImportFormat = "TogglCsv";
+    if (!toggl) {
+      format = path.endsWith(".json")
+        ? { GenericJson: DEFAULT_COLUMN_MAPPING }
+        : { GenericCsv: DEFAULT_COLUMN_MAPPING };
+    }
+
+    try {
+      const preview = await ipc.previewImport(path, format);
+      const duplicates = preview.entries.filter((entry) => entry.duplicate).length;
+      const overlapping = preview.entries.filter((entry) => entry.overlapping).length;
+      const count = preview.entries.length - duplicates - overlapping;
+      const confirmed = await ask(
+        `Import ${count} sessions, creating ${preview.new_intents.length} intents, ` +
+          `${preview.new_tags.length} tags and ${preview.new_tasks.length} tasks? ` +
+          `${duplicates} duplicates, ${overlapping} sessions overlapping existing ones ` +
+          `and ${preview.errors.length} invalid rows will be skipped.`,
+        { title: "Import history" }
+      );
+      if (!confirmed) return;
+
+      await ipc.importHistory(path, format);
+      toast("History imported", { duration: 2000 });
+    } catch {
+      toast("Import failed", { duration: 2000 });
+    }
+  };
+
   if (!store.settingsConfig || isAutoStart === undefined) return null;
 
   return (
@@ -103,6 +216,78 @@ const GeneralPane: React.FC = () => {
               </div>
             </Card>
           </Section>
+
+          <Section heading="Data">
+            <Card className="flex flex-row items-center justify-between">
+              <div className="text-text/90 group-hover:text-text">
+                Export sessions, tasks and intents
+              </div>
+              <Button variant="ghost" onClick={() => exportCsv()}>
+                CSV
+              </Button>
+            </Card>
+            <Card className="flex flex-row items-center justify-between">
+              <div className="text-text/90 group-hover:text-text">
+                Export focus sessions to calendar
+              </div>
+              <Button variant="ghost" onClick={() => exportIcs()}>
+                ICS
+              </Button>
+            </Card>
+            <Card className="flex flex-row items-center justify-between">
+              <div className="flex flex-col text-text/90 group-hover:text-text">
+                <span>Keep a calendar feed file up to date</span>
+                {store.settingsConfig.ics_feed_dir && (
+                  <span className="text-xs text-text/60">
+                    {store.settingsConfig.ics_feed_dir}
+                  </span>
+                )}
+              </div>
+              <CheckBox
+                checked={!!store.settingsConfig.ics_feed_dir}
+                onChange={() => toggleIcsFeed()}
+              />
+            </Card>
+            <Card className="flex flex-row items-center justify-between">
+              <div className="text-text/90 group-hover:text-text">
+                Backup all data and settings
+              </div>
+              <div className="flex flex-row gap-1">
+                <Button variant="ghost" onClick={() => exportBackup()}>
+                  Export
+                </Button>
+                <Button variant="ghost" onClick={() => importBackup()}>
+                  Restore
+                </Button>
+              </div>
+            </Card>
+            <Card className="flex flex-row items-center justify-between">
+              <div className="text-text/90 group-hover:text-text">
+                Import history from other apps
+              </div>
+              <div className="flex flex-row gap-1">
+                <Button variant="ghost" onClick={() => importHistory(false)}>
+                  CSV/JSON
+                </Button>
+                <Button variant="ghost" onClick={() => importHistory(true)}>
+                  Toggl
+                </Button>
+              </div>
+            </Card>
+          </Section>
+
+          <Section heading="Analytics">
+            <SliderCard
+              type="iterations"
+              label="Day starts at hour"
+              digit={store.settingsConfig.day_start_hour}
+              minDigit={0}
+              maxDigit={23}
+              onChange={(day_start_hour) =>
+                ipc.updateSettingsConfig({ day_start_hour })
+              }
+            />
+          </Section>
         </SectionsWrapper>
       </ScrollArea>
     </Pane>
@@ -6,6 +6,9 @@ import ipc from "@/ipc";
 import { Card, CheckBox, Pane, Section, SectionsWrapper, ScrollArea } from "@/ui";
 import { TimerConfigForUpdate } from "@/bindings/TimerConfigForUpdate";
 
+// Weekdays as indexed by the backend, starting on Monday.
+const WEEKDAYS = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
 const TimerPane: React.FC = () => {
   const store = useStore();
 
@@ -107,6 +110,48 @@ const TimerPane: React.FC = () => {
             </Card>
           </Section>
 
+          <Section heading="Goals">
+            <SliderCard
+              type="duration"
+              label="Daily Goal"
+              digit={config.daily_goal}
+              minDigit={0}
+              maxDigit={720}
+              onChange={(minutes) => updateConfig({ daily_goal: minutes })}
+            />
+            <SliderCard
+              type="duration"
+              label="Weekly Goal"
+              digit={config.weekly_goal}
+              minDigit={0}
+              maxDigit={3600}
+              onChange={(minutes) => updateConfig({ weekly_goal: minutes })}
+            />
+            <Card className="flex flex-col gap-1">
+              <div className="text-sm font-semibold">Rest Days</div>
+              <div className="flex flex-row items-center justify-between text-sm">
+                {WEEKDAYS.map((label, weekday) => (
+                  <div key={label} className="flex flex-col items-center gap-1">
+                    <div className="text-text/60">{label}</div>
+                    <CheckBox
+                      checked={config.rest_weekdays.includes(weekday)}
+                      onChange={(isRestDay) =>
+                        updateConfig({
+                          rest_weekdays: isRestDay
+                            ? [...config.rest_weekdays, weekday]
+                            : config.rest_weekdays.filter((d) => d !== weekday),
+                        })
+                      }
+                    />
+                  </div>
+                ))}
+              </div>
+              <div className="text-sm text-text/60">
+                Rest days without focus do not break your streak.
+              </div>
+            </Card>
+          </Section>
+
           <Section heading="Hotkeys">
             <Card className="flex flex-row items-center justify-between text-sm font-semibold">
               <div>Start/Resume</div>
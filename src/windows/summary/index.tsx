@@ -3,7 +3,7 @@ import { appWindow } from "@tauri-apps/api/window";
 import { useForm } from "react-hook-form";
 import { Textarea } from "@mantine/core";
 import { useSearchParams } from "react-router-dom";
-import { MdClose, MdNotes } from "react-icons/md";
+import { MdClose, MdNotes, MdPauseCircleOutline } from "react-icons/md";
 import { BiTargetLock } from "react-icons/bi";
 
 import { WindowContainer } from "@/components";
@@ -11,6 +11,8 @@ import { Button, IconView } from "@/ui";
 import { TimerSession } from "@/bindings/TimerSession";
 import ipc from "@/ipc";
 import { UpdateSession } from "@/bindings/UpdateSession";
+import { SessionDetails } from "@/bindings/SessionDetails";
+import { formatTimer } from "@/utils";
 
 const SummaryWindow: React.FC = () => {
   const [searchParams] = useSearchParams();
@@ -30,7 +32,7 @@ interface ContentProps {
 
 const Content: React.FC<ContentProps> = (props) => {
   return (
-    <div className="w-[20rem] h-[21rem]">
+    <div className="w-[20rem] h-[23rem]">
       <div className="relative w-screen h-screen flex flex-col bg-window/95 border-2 border-base/5 rounded-md overflow-clip">
         {/* Header */}
         <header className="h-7 flex flex-row items-center justify-between bg-base/5 rounded-t px-1">
@@ -45,6 +47,7 @@ const Content: React.FC<ContentProps> = (props) => {
         {/* Main content */}
         <main className="grow flex flex-col gap-0.5 p-0.5">
           <SessionIntent />
+          <SessionPauses {...props} />
           <SummaryForm {...props} />
         </main>
       </div>
@@ -103,4 +106,32 @@ const SessionIntent: React.FC = () => {
   );
 };
 
+const SessionPauses: React.FC<ContentProps> = (props) => {
+  const [session, setSession] = React.useState<SessionDetails | null>(null);
+
+  React.useEffect(() => {
+    ipc.getSession(props.sessionId).then((data) => setSession(data));
+  }, []);
+
+  if (!session) return null;
+
+  const pausedTime = session.pauses.reduce(
+    (total, pause) => total + (pause.finished_at - pause.started_at),
+    0
+  );
+
+  return (
+    <div className="flex flex-row items-center justify-between px-1 bg-base/5 text-text/80">
+      <div className="flex flex-row items-center gap-1">
+        <IconView icon={MdPauseCircleOutline} />
+        <span>
+          {session.pauses.length}{" "}
+          {session.pauses.length === 1 ? "interruption" : "interruptions"}
+        </span>
+      </div>
+      <span>{formatTimer(pausedTime)} paused</span>
+    </div>
+  );
+};
+
 export default SummaryWindow;